@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE moderation_configuration ADD COLUMN use_timeouts BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,12 @@
+-- Add migration script here
+CREATE TABLE custom_actions (
+    guild_id BIGINT NOT NULL,
+    name VARCHAR(32) NOT NULL,
+    role_id BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, name)
+);
+ALTER TABLE actions ALTER COLUMN action_type TYPE VARCHAR(16);
+ALTER TABLE actions ADD COLUMN custom_action VARCHAR(32) NULL;
+ALTER TABLE strike_escalations ALTER COLUMN action_type TYPE VARCHAR(16);
+ALTER TABLE strike_escalations ADD COLUMN custom_action VARCHAR(32) NULL;
+INSERT INTO global_kills (feature) VALUES ('commands.punish');
@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE actions ADD COLUMN points INT NULL;
+UPDATE actions SET points = 1 WHERE action_type = 'strike';
+ALTER TABLE strike_escalations RENAME COLUMN strike_count TO points;
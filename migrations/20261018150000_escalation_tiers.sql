@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE strike_escalations ADD COLUMN message VARCHAR(255) NULL;
+ALTER TABLE moderation_configuration ADD COLUMN escalation_channel BIGINT NULL;
+ALTER TABLE moderation_configuration ADD COLUMN strike_window INT NULL;
@@ -0,0 +1,17 @@
+-- Add migration script here
+CREATE TABLE appeals (
+    id SERIAL NOT NULL,
+    action_id VARCHAR(24) NOT NULL REFERENCES actions(id) ON DELETE CASCADE,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    reason VARCHAR(1000) NOT NULL,
+    status VARCHAR(8) NOT NULL DEFAULT 'pending',
+    reviewer_id BIGINT NULL,
+    response VARCHAR(1000) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    reviewed_at TIMESTAMP NULL,
+    PRIMARY KEY (id),
+    UNIQUE (action_id)
+);
+ALTER TABLE moderation_configuration ADD COLUMN appeal_channel BIGINT NULL;
+INSERT INTO global_kills (feature) VALUES ('event.appeals');
@@ -0,0 +1,11 @@
+-- Add migration script here
+CREATE TABLE notes (
+    id SERIAL NOT NULL,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    author_id BIGINT NOT NULL,
+    content VARCHAR(1000) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id)
+);
+INSERT INTO global_kills (feature) VALUES ('commands.note');
@@ -0,0 +1,3 @@
+-- Add migration script here
+INSERT INTO global_kills (feature) VALUES ('commands.massban');
+INSERT INTO global_kills (feature) VALUES ('commands.masskick');
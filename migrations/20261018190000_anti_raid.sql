@@ -0,0 +1,14 @@
+-- Add migration script here
+CREATE TABLE anti_raid_configuration (
+    guild_id BIGINT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    join_threshold INT NOT NULL DEFAULT 10,
+    join_window INT NOT NULL DEFAULT 10,
+    lockdown_duration INT NOT NULL DEFAULT 60,
+    quarantine_role BIGINT NULL,
+    kick_account_age INT NULL,
+    lock_channels BIGINT[] NOT NULL DEFAULT '{}',
+    PRIMARY KEY (guild_id)
+);
+INSERT INTO anti_raid_configuration (guild_id) SELECT guild_id FROM moderation_configuration;
+INSERT INTO global_kills (feature) VALUES ('event.anti_raid');
@@ -0,0 +1,17 @@
+-- Add migration script here
+CREATE TABLE spam_configuration (
+    guild_id BIGINT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    message_limit INT NULL DEFAULT 6,
+    message_window INT NOT NULL DEFAULT 5,
+    duplicate_limit INT NULL DEFAULT 3,
+    mention_limit INT NULL DEFAULT 6,
+    newline_limit INT NULL DEFAULT 20,
+    emoji_limit INT NULL DEFAULT 20,
+    punishment VARCHAR(8) NOT NULL DEFAULT 'strike',
+    mute_duration VARCHAR(32) NULL,
+    reason VARCHAR(255) NOT NULL DEFAULT 'Spamming',
+    PRIMARY KEY (guild_id)
+);
+INSERT INTO spam_configuration (guild_id) SELECT guild_id FROM moderation_configuration;
+INSERT INTO global_kills (feature) VALUES ('event.spam');
@@ -0,0 +1,16 @@
+-- Add migration script here
+CREATE TABLE filter_rules (
+    id SERIAL NOT NULL,
+    guild_id BIGINT NOT NULL,
+    name VARCHAR(32) NOT NULL,
+    pattern VARCHAR(500) NOT NULL,
+    pattern_type VARCHAR(8) NOT NULL,
+    action VARCHAR(8) NOT NULL,
+    duration VARCHAR(32) NULL,
+    exempt_roles BIGINT[] NOT NULL DEFAULT '{}',
+    exempt_channels BIGINT[] NOT NULL DEFAULT '{}',
+    PRIMARY KEY (id),
+    UNIQUE (guild_id, name)
+);
+INSERT INTO global_kills (feature) VALUES ('commands.filter');
+INSERT INTO global_kills (feature) VALUES ('event.filter');
@@ -0,0 +1,10 @@
+-- Add migration script here
+CREATE TABLE automod_mappings (
+    guild_id BIGINT NOT NULL,
+    rule_id BIGINT NOT NULL,
+    action VARCHAR(8) NOT NULL,
+    duration VARCHAR(32) NULL,
+    reason VARCHAR(200) NOT NULL DEFAULT 'Violated "{rule}" automod rule',
+    PRIMARY KEY (guild_id, rule_id)
+);
+ALTER TABLE moderation_configuration ADD COLUMN automod_log_unmapped BOOLEAN NOT NULL DEFAULT false;
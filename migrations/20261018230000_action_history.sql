@@ -0,0 +1,33 @@
+-- Add migration script here
+CREATE TABLE action_history (
+    id SERIAL NOT NULL,
+    action_id VARCHAR(24) NOT NULL,
+    guild_id BIGINT NOT NULL,
+    field VARCHAR(16) NOT NULL,
+    old_value TEXT NULL,
+    new_value TEXT NULL,
+    changed_by BIGINT NOT NULL,
+    changed_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id)
+);
+CREATE INDEX action_history_action_id ON action_history (action_id);
+CREATE TABLE removed_actions (
+    id VARCHAR(24) NOT NULL,
+    action_type VARCHAR(16) NOT NULL,
+    user_id BIGINT NOT NULL,
+    moderator_id BIGINT NOT NULL,
+    guild_id BIGINT NOT NULL,
+    reason VARCHAR(255) NOT NULL,
+    active BOOLEAN NOT NULL,
+    expiry TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL,
+    custom_action VARCHAR(32) NULL,
+    points INT NULL,
+    removed_by BIGINT NOT NULL,
+    removed_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id)
+);
+ALTER TABLE appeals DROP CONSTRAINT appeals_action_id_fkey;
+ALTER TABLE moderation_configuration ADD COLUMN removal_recovery_days INT NOT NULL DEFAULT 30;
+INSERT INTO global_kills (feature) VALUES ('commands.restore');
+INSERT INTO global_kills (feature) VALUES ('event.removal_purge');
@@ -0,0 +1,19 @@
+-- Add migration script here
+ALTER TABLE actions ADD COLUMN case_number INT NULL;
+ALTER TABLE removed_actions ADD COLUMN case_number INT NULL;
+CREATE TEMPORARY TABLE numbered_actions AS
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY guild_id ORDER BY created_at, id)::INT AS case_number
+    FROM (SELECT id, guild_id, created_at FROM actions UNION ALL SELECT id, guild_id, created_at FROM removed_actions) AS all_actions;
+UPDATE actions SET case_number = numbered_actions.case_number FROM numbered_actions WHERE actions.id = numbered_actions.id;
+UPDATE removed_actions SET case_number = numbered_actions.case_number FROM numbered_actions WHERE removed_actions.id = numbered_actions.id;
+DROP TABLE numbered_actions;
+ALTER TABLE actions ALTER COLUMN case_number SET NOT NULL;
+ALTER TABLE removed_actions ALTER COLUMN case_number SET NOT NULL;
+CREATE UNIQUE INDEX actions_guild_id_case_number ON actions (guild_id, case_number);
+CREATE TABLE case_counters (
+    guild_id BIGINT NOT NULL,
+    last_case INT NOT NULL,
+    PRIMARY KEY (guild_id)
+);
+INSERT INTO case_counters (guild_id, last_case)
+    SELECT guild_id, MAX(case_number) FROM (SELECT guild_id, case_number FROM actions UNION ALL SELECT guild_id, case_number FROM removed_actions) AS all_actions GROUP BY guild_id;
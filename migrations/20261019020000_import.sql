@@ -0,0 +1,5 @@
+-- Add migration script here
+ALTER TABLE actions ADD COLUMN import_source VARCHAR NULL;
+ALTER TABLE removed_actions ADD COLUMN import_source VARCHAR NULL;
+CREATE INDEX actions_import_source_idx ON actions (guild_id, import_source) WHERE import_source IS NOT NULL;
+INSERT INTO global_kills (feature) VALUES ('commands.import');
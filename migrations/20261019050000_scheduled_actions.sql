@@ -0,0 +1,17 @@
+-- Add migration script here
+CREATE TABLE scheduled_actions (
+    id SERIAL NOT NULL,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    moderator_id BIGINT NOT NULL,
+    action_type VARCHAR(16) NOT NULL,
+    reason VARCHAR(255) NOT NULL,
+    duration VARCHAR(32) NULL,
+    execute_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id)
+);
+CREATE INDEX scheduled_actions_execute_at ON scheduled_actions (execute_at);
+CREATE INDEX scheduled_actions_guild_id ON scheduled_actions (guild_id);
+INSERT INTO global_kills (feature) VALUES ('commands.schedule');
+INSERT INTO global_kills (feature) VALUES ('event.scheduled');
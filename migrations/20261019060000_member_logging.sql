@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE logging_configuration ADD COLUMN log_members BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE logging_configuration ADD COLUMN log_member_channel BIGINT NULL;
+INSERT INTO global_kills (feature) VALUES ('logging.member');
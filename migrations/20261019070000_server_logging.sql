@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE logging_configuration ADD COLUMN log_server BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE logging_configuration ADD COLUMN log_server_channel BIGINT NULL;
+INSERT INTO global_kills (feature) VALUES ('logging.server');
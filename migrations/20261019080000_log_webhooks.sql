@@ -0,0 +1,2 @@
+-- Add migration script here
+INSERT INTO global_kills (feature) VALUES ('logging.webhooks');
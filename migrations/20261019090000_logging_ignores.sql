@@ -0,0 +1,9 @@
+-- Add migration script here
+CREATE TABLE logging_ignores (
+    guild_id BIGINT NOT NULL,
+    log_type VARCHAR(16) NOT NULL,
+    ignored_channels BIGINT[] NOT NULL DEFAULT '{}',
+    ignored_roles BIGINT[] NOT NULL DEFAULT '{}',
+    ignored_users BIGINT[] NOT NULL DEFAULT '{}',
+    PRIMARY KEY (guild_id, log_type)
+);
@@ -0,0 +1,435 @@
+use serenity::{
+    all::{
+        ButtonStyle, ChannelType, CommandInteraction, ComponentInteractionDataKind, InputTextStyle,
+    },
+    builder::{
+        CreateActionRow, CreateButton, CreateEmbed, CreateInputText, CreateInteractionResponse,
+        CreateModal, CreateSelectMenu, CreateSelectMenuKind,
+    },
+};
+
+use crate::{
+    database::postgres::guild::get_anti_raid_config,
+    models::{
+        command::{CommandContext, CommandContextReply},
+        handler::Handler,
+        response::{Response, ResponseError},
+    },
+};
+
+use super::{
+    get_modal_values, invalid_option, timeout_error, ConfigError, ConfigStage, EMBED_COLOR,
+};
+
+const ANTI_RAID_TITLE: &str = "Configuration - Anti-Raid";
+
+pub struct AntiRaidEnter;
+#[async_trait::async_trait]
+impl ConfigStage for AntiRaidEnter {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(ANTI_RAID_TITLE)
+                            .description("Would you like to enable anti-raid protection?\nWhen too many users join in a short time, Reaper will lock the server down and alert your moderators.")
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![CreateActionRow::Buttons(vec![
+                        CreateButton::new("yes")
+                            .label("Yes")
+                            .style(ButtonStyle::Success),
+                        CreateButton::new("no")
+                            .label("No")
+                            .style(ButtonStyle::Secondary),
+                    ])]),
+            )
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+        let Some(interaction) = collector.await else {
+            return Err(timeout_error());
+        };
+        interaction
+            .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+            .await?;
+
+        let enabled = match interaction.data.custom_id.as_str() {
+            "yes" => true,
+            "no" => false,
+            _ => return Err(invalid_option()),
+        };
+        sqlx::query!(
+            "UPDATE anti_raid_configuration SET enabled = $1 WHERE guild_id = $2",
+            enabled,
+            ctx.guild.id.get() as i64
+        )
+        .execute(&handler.main_database)
+        .await?;
+
+        if enabled {
+            Ok(None)
+        } else {
+            Ok(Some(3))
+        }
+    }
+}
+
+pub struct AntiRaidThreshold;
+#[async_trait::async_trait]
+impl ConfigStage for AntiRaidThreshold {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let Some(config) = get_anti_raid_config(handler, ctx.guild.id.get() as i64).await else {
+            return Err(invalid_option());
+        };
+
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(ANTI_RAID_TITLE)
+                            .description(format!(
+                                "A lockdown currently starts when **{}** users join within **{}** seconds, and ends by itself after **{}** minutes.",
+                                config.join_threshold, config.join_window, config.lockdown_duration
+                            ))
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![CreateActionRow::Buttons(vec![
+                        CreateButton::new("threshold")
+                            .label("Change threshold")
+                            .style(ButtonStyle::Primary),
+                        CreateButton::new("skip")
+                            .label("Skip")
+                            .style(ButtonStyle::Secondary),
+                    ])]),
+            )
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+        let Some(interaction) = collector.await else {
+            return Err(timeout_error());
+        };
+
+        match interaction.data.custom_id.as_str() {
+            "skip" => {
+                interaction
+                    .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+                    .await?;
+                Ok(None)
+            }
+            "threshold" => {
+                interaction
+                    .create_response(
+                        &ctx.ctx.http,
+                        CreateInteractionResponse::Modal(
+                            CreateModal::new("threshold_modal", "Join Threshold").components(vec![
+                                CreateActionRow::InputText(
+                                    CreateInputText::new(
+                                        InputTextStyle::Short,
+                                        "Joins",
+                                        "join_threshold",
+                                    )
+                                    .value(config.join_threshold.to_string()),
+                                ),
+                                CreateActionRow::InputText(
+                                    CreateInputText::new(
+                                        InputTextStyle::Short,
+                                        "Window (seconds)",
+                                        "join_window",
+                                    )
+                                    .value(config.join_window.to_string()),
+                                ),
+                                CreateActionRow::InputText(
+                                    CreateInputText::new(
+                                        InputTextStyle::Short,
+                                        "Lockdown duration (minutes)",
+                                        "lockdown_duration",
+                                    )
+                                    .value(config.lockdown_duration.to_string()),
+                                ),
+                            ]),
+                        ),
+                    )
+                    .await?;
+
+                let Some(interaction) = message
+                    .await_modal_interaction(&ctx.ctx)
+                    .author_id(cmd.user.id)
+                    .timeout(std::time::Duration::new(60, 0))
+                    .await
+                else {
+                    return Err(timeout_error());
+                };
+                interaction
+                    .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+                    .await?;
+
+                let values = get_modal_values(&interaction);
+                let threshold = values
+                    .first()
+                    .and_then(|value| value.parse::<i32>().ok())
+                    .filter(|value| (2..=1000).contains(value));
+                let window = values
+                    .get(1)
+                    .and_then(|value| value.parse::<i32>().ok())
+                    .filter(|value| (1..=3600).contains(value));
+                let duration = values
+                    .get(2)
+                    .and_then(|value| value.parse::<i32>().ok())
+                    .filter(|value| (1..=10080).contains(value));
+                let (Some(threshold), Some(window), Some(duration)) = (threshold, window, duration)
+                else {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid threshold",
+                            Some("The amount of joins must be between 2 and 1000, the window between 1 and 3600 seconds, and the lockdown duration between 1 and 10080 minutes.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    });
+                };
+
+                sqlx::query!(
+                    "UPDATE anti_raid_configuration SET join_threshold = $1, join_window = $2, lockdown_duration = $3 WHERE guild_id = $4",
+                    threshold,
+                    window,
+                    duration,
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+                Ok(None)
+            }
+            _ => Err(invalid_option()),
+        }
+    }
+}
+
+pub struct AntiRaidResponse;
+#[async_trait::async_trait]
+impl ConfigStage for AntiRaidResponse {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let Some(config) = get_anti_raid_config(handler, ctx.guild.id.get() as i64).await else {
+            return Err(invalid_option());
+        };
+
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(ANTI_RAID_TITLE)
+                            .description("Choose how Reaper responds to a raid. During a lockdown, new joiners can be given a quarantine role, accounts younger than a number of days can be kicked, and channels can be locked.")
+                            .field(
+                                "Quarantine role",
+                                config
+                                    .quarantine_role
+                                    .map_or("None".to_string(), |role| format!("<@&{role}>")),
+                                true,
+                            )
+                            .field(
+                                "Kick accounts younger than",
+                                config
+                                    .kick_account_age
+                                    .map_or("Disabled".to_string(), |days| format!("{days} days")),
+                                true,
+                            )
+                            .field(
+                                "Locked channels",
+                                if config.lock_channels.is_empty() {
+                                    "None".to_string()
+                                } else {
+                                    config
+                                        .lock_channels
+                                        .iter()
+                                        .map(|channel| format!("<#{channel}>"))
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                },
+                                false,
+                            )
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![
+                        CreateActionRow::SelectMenu(
+                            CreateSelectMenu::new(
+                                "quarantine_role",
+                                CreateSelectMenuKind::Role {
+                                    default_roles: None,
+                                },
+                            )
+                            .placeholder("Select a quarantine role"),
+                        ),
+                        CreateActionRow::SelectMenu(
+                            CreateSelectMenu::new(
+                                "lock_channels",
+                                CreateSelectMenuKind::Channel {
+                                    channel_types: Some(vec![ChannelType::Text]),
+                                    default_channels: None,
+                                },
+                            )
+                            .placeholder("Select channels to lock")
+                            .max_values(25),
+                        ),
+                        CreateActionRow::Buttons(vec![
+                            CreateButton::new("kick_age")
+                                .label("Set account age")
+                                .style(ButtonStyle::Primary),
+                            CreateButton::new("reset")
+                                .label("Reset")
+                                .style(ButtonStyle::Danger),
+                            CreateButton::new("done")
+                                .label("Done")
+                                .style(ButtonStyle::Success),
+                        ]),
+                    ]),
+            )
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+        let Some(interaction) = collector.await else {
+            return Err(timeout_error());
+        };
+
+        if interaction.data.custom_id.as_str() != "kick_age" {
+            interaction
+                .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+                .await?;
+        }
+
+        match interaction.data.custom_id.as_str() {
+            "done" => Ok(None),
+            "reset" => {
+                sqlx::query!(
+                    "UPDATE anti_raid_configuration SET quarantine_role = NULL, kick_account_age = NULL, lock_channels = '{}' WHERE guild_id = $1",
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+                Ok(Some(0))
+            }
+            "quarantine_role" => {
+                let ComponentInteractionDataKind::RoleSelect { values } = &interaction.data.kind
+                else {
+                    return Err(invalid_option());
+                };
+                let Some(role) = values.first() else {
+                    return Err(invalid_option());
+                };
+                sqlx::query!(
+                    "UPDATE anti_raid_configuration SET quarantine_role = $1 WHERE guild_id = $2",
+                    role.get() as i64,
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+                Ok(Some(0))
+            }
+            "lock_channels" => {
+                let ComponentInteractionDataKind::ChannelSelect { values } = &interaction.data.kind
+                else {
+                    return Err(invalid_option());
+                };
+                sqlx::query!(
+                    "UPDATE anti_raid_configuration SET lock_channels = $1 WHERE guild_id = $2",
+                    &values
+                        .iter()
+                        .map(|channel| channel.get() as i64)
+                        .collect::<Vec<_>>(),
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+                Ok(Some(0))
+            }
+            "kick_age" => {
+                interaction
+                    .create_response(
+                        &ctx.ctx.http,
+                        CreateInteractionResponse::Modal(
+                            CreateModal::new("kick_age_modal", "Account Age").components(vec![
+                                CreateActionRow::InputText(
+                                    CreateInputText::new(
+                                        InputTextStyle::Short,
+                                        "Days (leave empty to disable)",
+                                        "kick_account_age",
+                                    )
+                                    .placeholder("7")
+                                    .required(false),
+                                ),
+                            ]),
+                        ),
+                    )
+                    .await?;
+
+                let Some(interaction) = message
+                    .await_modal_interaction(&ctx.ctx)
+                    .author_id(cmd.user.id)
+                    .timeout(std::time::Duration::new(60, 0))
+                    .await
+                else {
+                    return Err(timeout_error());
+                };
+                interaction
+                    .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+                    .await?;
+
+                let value = get_modal_values(&interaction)
+                    .into_iter()
+                    .next()
+                    .unwrap_or_default();
+                let kick_account_age = if value.is_empty() {
+                    None
+                } else {
+                    let Some(days) = value.parse::<i32>().ok().filter(|days| *days > 0) else {
+                        return Err(ConfigError {
+                            error: ResponseError::Execution(
+                                "Invalid account age",
+                                Some("Please enter a valid amount of days.".to_string()),
+                            ),
+                            stages_to_skip: None,
+                        });
+                    };
+                    Some(days)
+                };
+                sqlx::query!(
+                    "UPDATE anti_raid_configuration SET kick_account_age = $1 WHERE guild_id = $2",
+                    kick_account_age,
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+                Ok(Some(0))
+            }
+            _ => Err(invalid_option()),
+        }
+    }
+}
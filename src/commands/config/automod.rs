@@ -0,0 +1,318 @@
+use serenity::{
+    all::{ButtonStyle, CommandInteraction, ComponentInteractionDataKind, InputTextStyle, Rule},
+    builder::{
+        CreateActionRow, CreateButton, CreateEmbed, CreateInputText, CreateInteractionResponse,
+        CreateModal, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+    },
+};
+
+use crate::{
+    common::duration::Duration,
+    database::postgres::automod::get_automod_mappings,
+    models::{
+        automod::AutoModMapping,
+        command::{CommandContext, CommandContextReply},
+        handler::Handler,
+        response::{Response, ResponseError},
+    },
+};
+
+use super::{
+    get_modal_values, invalid_option, timeout_error, ConfigError, ConfigStage, EMBED_COLOR,
+};
+
+const AUTOMOD_TITLE: &str = "Configuration - AutoMod";
+const DEFAULT_REASON: &str = "Violated \"{rule}\" automod rule";
+
+fn describe_mapping(mapping: Option<&AutoModMapping>) -> String {
+    let Some(mapping) = mapping else {
+        return "Not mapped".to_string();
+    };
+    let mut action = mapping.action.clone();
+    if let Some(first) = action.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    match &mapping.duration {
+        Some(duration) => format!("{action} for {duration}"),
+        None => action,
+    }
+}
+
+pub struct AutoModMappings;
+
+impl AutoModMappings {
+    fn generate_message(
+        rules: &[Rule],
+        mappings: &[AutoModMapping],
+        log_unmapped: bool,
+    ) -> Response {
+        let find = |rule: &Rule| {
+            mappings
+                .iter()
+                .find(|mapping| mapping.rule_id == rule.id.get() as i64)
+        };
+
+        let mut embed = CreateEmbed::new()
+            .title(AUTOMOD_TITLE)
+            .description("Select one of this server's AutoMod rules to choose what Reaper does when it is triggered. The reason can include `{rule}` and `{keyword}`, which are replaced with the rule name and the matched keyword.")
+            .color(EMBED_COLOR);
+        if rules.is_empty() {
+            embed = embed.field("Rules", "This server has no AutoMod rules", false);
+        }
+        for rule in rules.iter().take(25) {
+            embed = embed.field(rule.name.clone(), describe_mapping(find(rule)), true);
+        }
+
+        let mut components = vec![];
+        if !rules.is_empty() {
+            components.push(CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    "rule",
+                    CreateSelectMenuKind::String {
+                        options: rules
+                            .iter()
+                            .take(25)
+                            .map(|rule| {
+                                CreateSelectMenuOption::new(
+                                    rule.name.clone(),
+                                    rule.id.get().to_string(),
+                                )
+                                .description(describe_mapping(find(rule)))
+                            })
+                            .collect(),
+                    },
+                )
+                .placeholder("Select an AutoMod rule"),
+            ));
+        }
+        components.push(CreateActionRow::Buttons(vec![
+            CreateButton::new("log_unmapped")
+                .label(if log_unmapped {
+                    "Stop logging unmapped rules"
+                } else {
+                    "Log unmapped rules"
+                })
+                .style(ButtonStyle::Primary),
+            CreateButton::new("done")
+                .label("Done")
+                .style(ButtonStyle::Success),
+        ]));
+
+        Response::new().embed(embed).components(components)
+    }
+
+    fn generate_action_message(rule: &Rule, mapping: Option<&AutoModMapping>) -> Response {
+        Response::new()
+            .embed(
+                CreateEmbed::new()
+                    .title(AUTOMOD_TITLE)
+                    .description(format!(
+                        "Select what Reaper should do when the \"{}\" rule is triggered.\nCurrently: {}",
+                        rule.name,
+                        describe_mapping(mapping)
+                    ))
+                    .color(EMBED_COLOR),
+            )
+            .components(vec![
+                CreateActionRow::SelectMenu(
+                    CreateSelectMenu::new(
+                        "action",
+                        CreateSelectMenuKind::String {
+                            options: vec![
+                                CreateSelectMenuOption::new("Strike the user", "strike"),
+                                CreateSelectMenuOption::new("Mute the user", "mute"),
+                                CreateSelectMenuOption::new("Kick the user", "kick"),
+                                CreateSelectMenuOption::new("Ban the user", "ban"),
+                                CreateSelectMenuOption::new("Do nothing", "none"),
+                            ],
+                        },
+                    )
+                    .placeholder("Select an action"),
+                ),
+                CreateActionRow::Buttons(vec![CreateButton::new("back")
+                    .label("Back")
+                    .style(ButtonStyle::Secondary)]),
+            ])
+    }
+}
+
+#[async_trait::async_trait]
+impl ConfigStage for AutoModMappings {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let guild_id = ctx.guild.id.get() as i64;
+        let rules = ctx.ctx.http.get_automod_rules(ctx.guild.id).await?;
+        let mappings = get_automod_mappings(handler, guild_id).await;
+        let log_unmapped = sqlx::query!(
+            "SELECT automod_log_unmapped FROM moderation_configuration WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_one(&handler.main_database)
+        .await?
+        .automod_log_unmapped;
+
+        let message = ctx
+            .reply_get_message(cmd, Self::generate_message(&rules, &mappings, log_unmapped))
+            .await?;
+
+        let Some(interaction) = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0))
+            .await
+        else {
+            return Err(timeout_error());
+        };
+        interaction
+            .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+            .await?;
+
+        let rule = match (interaction.data.custom_id.as_str(), &interaction.data.kind) {
+            ("done", _) => return Ok(None),
+            ("log_unmapped", _) => {
+                sqlx::query!(
+                    "UPDATE moderation_configuration SET automod_log_unmapped = $1 WHERE guild_id = $2",
+                    !log_unmapped,
+                    guild_id
+                )
+                .execute(&handler.main_database)
+                .await?;
+                return Ok(Some(0));
+            }
+            ("rule", ComponentInteractionDataKind::StringSelect { values }) => {
+                let Some(rule) = values
+                    .first()
+                    .and_then(|value| rules.iter().find(|rule| rule.id.to_string() == *value))
+                else {
+                    return Err(invalid_option());
+                };
+                rule
+            }
+            _ => return Err(invalid_option()),
+        };
+        let mapping = mappings
+            .iter()
+            .find(|mapping| mapping.rule_id == rule.id.get() as i64);
+
+        ctx.reply_get_message(cmd, Self::generate_action_message(rule, mapping))
+            .await?;
+        let Some(interaction) = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0))
+            .await
+        else {
+            return Err(timeout_error());
+        };
+
+        let action = match (interaction.data.custom_id.as_str(), &interaction.data.kind) {
+            ("action", ComponentInteractionDataKind::StringSelect { values }) => {
+                match values.first().map(String::as_str) {
+                    Some(action @ ("strike" | "mute" | "kick" | "ban" | "none")) => action,
+                    _ => return Err(invalid_option()),
+                }
+            }
+            ("back", _) => {
+                interaction
+                    .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+                    .await?;
+                return Ok(Some(0));
+            }
+            _ => return Err(invalid_option()),
+        };
+
+        if action == "none" {
+            interaction
+                .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+                .await?;
+            sqlx::query!(
+                "DELETE FROM automod_mappings WHERE guild_id = $1 AND rule_id = $2",
+                guild_id,
+                rule.id.get() as i64
+            )
+            .execute(&handler.main_database)
+            .await?;
+            return Ok(Some(0));
+        }
+
+        let mut components = vec![];
+        if action != "kick" {
+            components.push(CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Short, "Duration", "duration")
+                    .placeholder(if action == "mute" {
+                        "10m"
+                    } else {
+                        "Leave empty for the default"
+                    })
+                    .value(
+                        mapping
+                            .and_then(|mapping| mapping.duration.clone())
+                            .unwrap_or_default(),
+                    )
+                    .required(action == "mute"),
+            ));
+        }
+        components.push(CreateActionRow::InputText(
+            CreateInputText::new(InputTextStyle::Short, "Reason", "reason")
+                .max_length(200)
+                .value(
+                    mapping.map_or(DEFAULT_REASON.to_string(), |mapping| mapping.reason.clone()),
+                ),
+        ));
+        interaction
+            .create_response(
+                &ctx.ctx.http,
+                CreateInteractionResponse::Modal(
+                    CreateModal::new("automod_modal", "AutoMod Action").components(components),
+                ),
+            )
+            .await?;
+        let Some(modal_interaction) = message
+            .await_modal_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0))
+            .await
+        else {
+            return Err(timeout_error());
+        };
+        modal_interaction
+            .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+            .await?;
+
+        let mut values = get_modal_values(&modal_interaction);
+        let reason = values
+            .pop()
+            .filter(|reason| !reason.is_empty())
+            .unwrap_or(DEFAULT_REASON.to_string());
+        let duration = values.pop().filter(|duration| !duration.is_empty());
+        if duration.as_ref().is_some_and(|duration| {
+            Duration::new(duration)
+                .to_timestamp()
+                .is_none_or(|duration| duration <= time::OffsetDateTime::now_utc())
+        }) {
+            return Err(ConfigError {
+                error: ResponseError::Execution(
+                    "Invalid duration",
+                    Some("Please enter a valid duration, like `10m`.".to_string()),
+                ),
+                stages_to_skip: None,
+            });
+        }
+
+        sqlx::query!(
+            "INSERT INTO automod_mappings (guild_id, rule_id, action, duration, reason) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (guild_id, rule_id) DO UPDATE SET action = $3, duration = $4, reason = $5",
+            guild_id,
+            rule.id.get() as i64,
+            action,
+            duration,
+            reason
+        )
+        .execute(&handler.main_database)
+        .await?;
+        Ok(Some(0))
+    }
+}
@@ -1,17 +1,527 @@
 use serenity::{
-    all::{ButtonStyle, ChannelType, CommandInteraction, ComponentInteractionDataKind},
-    builder::{CreateActionRow, CreateButton, CreateEmbed, CreateSelectMenu, CreateSelectMenuKind},
+    all::{
+        ButtonStyle, ChannelId, ChannelType, CommandInteraction, ComponentInteractionDataKind,
+        RoleId, UserId,
+    },
+    builder::{
+        CreateActionRow, CreateButton, CreateEmbed, CreateInteractionResponse, CreateSelectMenu,
+        CreateSelectMenuKind, CreateSelectMenuOption,
+    },
 };
 
-use crate::models::{
-    command::{CommandContext, CommandContextReply},
-    handler::Handler,
-    response::{Response, ResponseError},
+use crate::{
+    common::logging::LogType,
+    database::postgres::logging::get_logging_ignores,
+    models::{
+        command::{CommandContext, CommandContextReply},
+        config::LoggingIgnores,
+        handler::Handler,
+        response::{Response, ResponseError},
+    },
 };
 
-use super::{ConfigError, ConfigStage, EMBED_COLOR};
+use super::{invalid_option, timeout_error, ConfigError, ConfigStage, EMBED_COLOR};
 
 const LOGGING_TITLE: &str = "Configuration - Logging";
+const IGNORABLE_LOGS: [(LogType, &str); 4] = [
+    (LogType::Message, "Message"),
+    (LogType::Voice, "Voice"),
+    (LogType::Member, "Member"),
+    (LogType::Server, "Server"),
+];
+
+fn describe_ignores(ignores: &LoggingIgnores) -> String {
+    let mention = |prefix: &str, ids: &[i64]| {
+        ids.iter()
+            .map(|id| format!("<{prefix}{id}>"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let lists = [
+        ("Channels", mention("#", &ignores.ignored_channels)),
+        ("Roles", mention("@&", &ignores.ignored_roles)),
+        ("Users", mention("@", &ignores.ignored_users)),
+    ];
+    let description = lists
+        .iter()
+        .filter(|(_, list)| !list.is_empty())
+        .map(|(name, list)| format!("{name}: {list}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if description.is_empty() {
+        "Nothing ignored".to_string()
+    } else if description.len() > 1024 {
+        format!(
+            "{} channels, {} roles and {} users ignored",
+            ignores.ignored_channels.len(),
+            ignores.ignored_roles.len(),
+            ignores.ignored_users.len()
+        )
+    } else {
+        description
+    }
+}
+
+pub struct LoggingIgnoreLists;
+
+impl LoggingIgnoreLists {
+    fn generate_message(logs: &[(LogType, &str, LoggingIgnores)]) -> Response {
+        let mut embed = CreateEmbed::new()
+            .title(LOGGING_TITLE)
+            .description(
+                "Select a log type to choose the channels, categories, roles and users it ignores.",
+            )
+            .color(EMBED_COLOR);
+        for (_, label, ignores) in logs {
+            embed = embed.field(format!("{label} logs"), describe_ignores(ignores), false);
+        }
+
+        Response::new().embed(embed).components(vec![
+            CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    "log_type",
+                    CreateSelectMenuKind::String {
+                        options: logs
+                            .iter()
+                            .map(|(log_type, label, _)| {
+                                CreateSelectMenuOption::new(
+                                    format!("{label} logs"),
+                                    log_type.to_string(),
+                                )
+                            })
+                            .collect(),
+                    },
+                )
+                .placeholder("Select a log type"),
+            ),
+            CreateActionRow::Buttons(vec![CreateButton::new("done")
+                .label("Done")
+                .style(ButtonStyle::Success)]),
+        ])
+    }
+
+    fn generate_ignore_message(
+        log_type: LogType,
+        label: &str,
+        ignores: &LoggingIgnores,
+    ) -> Response {
+        let mut components = vec![];
+        // Member logs aren't tied to a channel
+        if log_type != LogType::Member {
+            components.push(CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    "ignored_channels",
+                    CreateSelectMenuKind::Channel {
+                        channel_types: None,
+                        default_channels: Some(
+                            ignores
+                                .ignored_channels
+                                .iter()
+                                .map(|id| ChannelId::new(*id as u64))
+                                .collect(),
+                        ),
+                    },
+                )
+                .placeholder("Ignored channels and categories")
+                .min_values(0)
+                .max_values(25),
+            ));
+        }
+        components.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(
+                "ignored_roles",
+                CreateSelectMenuKind::Role {
+                    default_roles: Some(
+                        ignores
+                            .ignored_roles
+                            .iter()
+                            .map(|id| RoleId::new(*id as u64))
+                            .collect(),
+                    ),
+                },
+            )
+            .placeholder("Ignored roles")
+            .min_values(0)
+            .max_values(25),
+        ));
+        components.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(
+                "ignored_users",
+                CreateSelectMenuKind::User {
+                    default_users: Some(
+                        ignores
+                            .ignored_users
+                            .iter()
+                            .map(|id| UserId::new(*id as u64))
+                            .collect(),
+                    ),
+                },
+            )
+            .placeholder("Ignored users")
+            .min_values(0)
+            .max_values(25),
+        ));
+        components.push(CreateActionRow::Buttons(vec![CreateButton::new("back")
+            .label("Back")
+            .style(ButtonStyle::Secondary)]));
+
+        Response::new()
+            .embed(
+                CreateEmbed::new()
+                    .title(LOGGING_TITLE)
+                    .description(format!(
+                        "Select what {} logs should ignore. A channel also covers the threads in it, and a category the channels in it. Clearing a menu removes everything from that list.",
+                        label.to_lowercase()
+                    ))
+                    .color(EMBED_COLOR),
+            )
+            .components(components)
+    }
+}
+
+#[async_trait::async_trait]
+impl ConfigStage for LoggingIgnoreLists {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let guild_id = ctx.guild.id.get() as i64;
+        let log_options = sqlx::query!(
+            "SELECT log_messages, log_voice, log_members, log_server FROM logging_configuration WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_one(&handler.main_database)
+        .await?;
+
+        let mut logs = vec![];
+        for (log_type, label) in IGNORABLE_LOGS {
+            let enabled = match log_type {
+                LogType::Message => log_options.log_messages,
+                LogType::Voice => log_options.log_voice,
+                LogType::Member => log_options.log_members,
+                LogType::Server => log_options.log_server,
+                LogType::Action => false,
+            };
+            if enabled {
+                logs.push((
+                    log_type,
+                    label,
+                    get_logging_ignores(handler, guild_id, &log_type).await,
+                ));
+            }
+        }
+        if logs.is_empty() {
+            return Ok(None);
+        }
+
+        let message = ctx
+            .reply_get_message(cmd, Self::generate_message(&logs))
+            .await?;
+        let Some(interaction) = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0))
+            .await
+        else {
+            return Err(timeout_error());
+        };
+        interaction
+            .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+            .await?;
+
+        let (log_type, label, ignores) =
+            match (interaction.data.custom_id.as_str(), &interaction.data.kind) {
+                ("done", _) => return Ok(None),
+                ("log_type", ComponentInteractionDataKind::StringSelect { values }) => {
+                    let Some(log) = values.first().and_then(|value| {
+                        logs.iter()
+                            .find(|(log_type, _, _)| log_type.to_string() == *value)
+                    }) else {
+                        return Err(invalid_option());
+                    };
+                    log
+                }
+                _ => return Err(invalid_option()),
+            };
+
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Self::generate_ignore_message(*log_type, label, ignores),
+            )
+            .await?;
+        let Some(interaction) = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0))
+            .await
+        else {
+            return Err(timeout_error());
+        };
+        interaction
+            .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+            .await?;
+
+        let log_type = log_type.to_string();
+        match (interaction.data.custom_id.as_str(), &interaction.data.kind) {
+            ("back", _) => {}
+            ("ignored_channels", ComponentInteractionDataKind::ChannelSelect { values }) => {
+                sqlx::query!(
+                    "INSERT INTO logging_ignores (guild_id, log_type, ignored_channels) VALUES ($1, $2, $3) ON CONFLICT (guild_id, log_type) DO UPDATE SET ignored_channels = $3",
+                    guild_id,
+                    log_type,
+                    &values.iter().map(|id| id.get() as i64).collect::<Vec<_>>()
+                )
+                .execute(&handler.main_database)
+                .await?;
+            }
+            ("ignored_roles", ComponentInteractionDataKind::RoleSelect { values }) => {
+                sqlx::query!(
+                    "INSERT INTO logging_ignores (guild_id, log_type, ignored_roles) VALUES ($1, $2, $3) ON CONFLICT (guild_id, log_type) DO UPDATE SET ignored_roles = $3",
+                    guild_id,
+                    log_type,
+                    &values.iter().map(|id| id.get() as i64).collect::<Vec<_>>()
+                )
+                .execute(&handler.main_database)
+                .await?;
+            }
+            ("ignored_users", ComponentInteractionDataKind::UserSelect { values }) => {
+                sqlx::query!(
+                    "INSERT INTO logging_ignores (guild_id, log_type, ignored_users) VALUES ($1, $2, $3) ON CONFLICT (guild_id, log_type) DO UPDATE SET ignored_users = $3",
+                    guild_id,
+                    log_type,
+                    &values.iter().map(|id| id.get() as i64).collect::<Vec<_>>()
+                )
+                .execute(&handler.main_database)
+                .await?;
+            }
+            _ => return Err(invalid_option()),
+        }
+        Ok(Some(0))
+    }
+}
+
+pub struct LoggingChannelMultipleServer;
+#[async_trait::async_trait]
+impl ConfigStage for LoggingChannelMultipleServer {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let is_logging = sqlx::query!(
+            "SELECT log_server FROM logging_configuration WHERE guild_id = $1",
+            cmd.guild_id.unwrap().get() as i64
+        )
+        .fetch_one(&handler.main_database)
+        .await?;
+
+        if !is_logging.log_server {
+            return Ok(None);
+        }
+
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(LOGGING_TITLE)
+                            .description(
+                                "Please enter the channel you would like to log server changes to.",
+                            )
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![
+                        CreateActionRow::SelectMenu(CreateSelectMenu::new(
+                            "log_server_channel",
+                            CreateSelectMenuKind::Channel {
+                                channel_types: Some(vec![
+                                    ChannelType::Text,
+                                    ChannelType::Forum,
+                                    ChannelType::PublicThread,
+                                    ChannelType::PrivateThread,
+                                ]),
+                                default_channels: None,
+                            },
+                        )),
+                        CreateActionRow::Buttons(vec![CreateButton::new("cancel")
+                            .label("Cancel")
+                            .style(ButtonStyle::Danger)]),
+                    ]),
+            )
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+        if let Some(interaction) = collector.await {
+            interaction
+                .create_response(
+                    &ctx.ctx.http,
+                    serenity::builder::CreateInteractionResponse::Acknowledge,
+                )
+                .await?;
+            match interaction.data.custom_id.as_str() {
+                "log_server_channel" => {
+                    if let ComponentInteractionDataKind::ChannelSelect { values } =
+                        interaction.data.kind
+                    {
+                        let channel = values.first().unwrap();
+                        sqlx::query!(
+                            "UPDATE logging_configuration SET log_server_channel = $1 WHERE guild_id = $2",
+                            channel.get() as i64,
+                            cmd.guild_id.unwrap().get() as i64
+                        )
+                        .execute(&handler.main_database)
+                        .await?;
+                        return Ok(None);
+                    }
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid option",
+                            Some("Please select a valid option.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    });
+                }
+                "cancel" => {
+                    return Ok(None);
+                }
+                _ => {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid option",
+                            Some("Please select a valid option.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    })
+                }
+            }
+        }
+        Err(ConfigError {
+            error: ResponseError::Execution(
+                "Time out",
+                Some("We didn't get a response in time. Please try again.".to_string()),
+            ),
+            stages_to_skip: Some(100),
+        })
+    }
+}
+
+pub struct LoggingChannelMultipleMembers;
+#[async_trait::async_trait]
+impl ConfigStage for LoggingChannelMultipleMembers {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let is_logging = sqlx::query!(
+            "SELECT log_members FROM logging_configuration WHERE guild_id = $1",
+            cmd.guild_id.unwrap().get() as i64
+        )
+        .fetch_one(&handler.main_database)
+        .await?;
+
+        if !is_logging.log_members {
+            return Ok(None);
+        }
+
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(LOGGING_TITLE)
+                            .description(
+                                "Please enter the channel you would like to log member events to.",
+                            )
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![
+                        CreateActionRow::SelectMenu(CreateSelectMenu::new(
+                            "log_member_channel",
+                            CreateSelectMenuKind::Channel {
+                                channel_types: Some(vec![
+                                    ChannelType::Text,
+                                    ChannelType::Forum,
+                                    ChannelType::PublicThread,
+                                    ChannelType::PrivateThread,
+                                ]),
+                                default_channels: None,
+                            },
+                        )),
+                        CreateActionRow::Buttons(vec![CreateButton::new("cancel")
+                            .label("Cancel")
+                            .style(ButtonStyle::Danger)]),
+                    ]),
+            )
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+        if let Some(interaction) = collector.await {
+            interaction
+                .create_response(
+                    &ctx.ctx.http,
+                    serenity::builder::CreateInteractionResponse::Acknowledge,
+                )
+                .await?;
+            match interaction.data.custom_id.as_str() {
+                "log_member_channel" => {
+                    if let ComponentInteractionDataKind::ChannelSelect { values } =
+                        interaction.data.kind
+                    {
+                        let channel = values.first().unwrap();
+                        sqlx::query!(
+                            "UPDATE logging_configuration SET log_member_channel = $1 WHERE guild_id = $2",
+                            channel.get() as i64,
+                            cmd.guild_id.unwrap().get() as i64
+                        )
+                        .execute(&handler.main_database)
+                        .await?;
+                        return Ok(None);
+                    }
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid option",
+                            Some("Please select a valid option.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    });
+                }
+                "cancel" => {
+                    return Ok(Some(2));
+                }
+                _ => {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid option",
+                            Some("Please select a valid option.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    })
+                }
+            }
+        }
+        Err(ConfigError {
+            error: ResponseError::Execution(
+                "Time out",
+                Some("We didn't get a response in time. Please try again.".to_string()),
+            ),
+            stages_to_skip: Some(100),
+        })
+    }
+}
 
 pub struct LoggingChannelMultipleVoice;
 #[async_trait::async_trait]
@@ -100,7 +610,7 @@ impl ConfigStage for LoggingChannelMultipleVoice {
                     });
                 }
                 "cancel" => {
-                    return Ok(None);
+                    return Ok(Some(3));
                 }
                 _ => {
                     return Err(ConfigError {
@@ -210,7 +720,7 @@ impl ConfigStage for LoggingChannelMultipleMessages {
                     });
                 }
                 "cancel" => {
-                    return Ok(Some(2));
+                    return Ok(Some(4));
                 }
                 _ => {
                     return Err(ConfigError {
@@ -327,7 +837,7 @@ impl ConfigStage for LoggingChannelMultipleActions {
                     });
                 }
                 "cancel" => {
-                    return Ok(Some(3));
+                    return Ok(Some(5));
                 }
                 _ => {
                     return Err(ConfigError {
@@ -407,13 +917,13 @@ impl ConfigStage for LoggingChannelSingle {
                     {
                         let channel = values.first().unwrap();
                         sqlx::query!(
-                            "UPDATE logging_configuration SET log_channel = $1, log_action_channel = null, log_message_channel = null, log_voice_channel = null WHERE guild_id = $2",
+                            "UPDATE logging_configuration SET log_channel = $1, log_action_channel = null, log_message_channel = null, log_voice_channel = null, log_member_channel = null, log_server_channel = null WHERE guild_id = $2",
                             channel.get() as i64,
                             cmd.guild_id.unwrap().get() as i64
                         )
                         .execute(&handler.main_database)
                         .await?;
-                        return Ok(Some(4));
+                        return Ok(Some(6));
                     }
                     return Err(ConfigError {
                         error: ResponseError::Execution(
@@ -424,7 +934,7 @@ impl ConfigStage for LoggingChannelSingle {
                     });
                 }
                 "cancel" => {
-                    return Ok(Some(4));
+                    return Ok(Some(6));
                 }
                 _ => {
                     return Err(ConfigError {
@@ -457,13 +967,18 @@ impl ConfigStage for LoggingChannelEnter {
         cmd: &CommandInteraction,
     ) -> Result<Option<usize>, ConfigError> {
         let log_options = sqlx::query!(
-            "SELECT log_actions, log_messages, log_voice FROM logging_configuration WHERE guild_id = $1"
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server FROM logging_configuration WHERE guild_id = $1"
             , cmd.guild_id.unwrap().get() as i64
         )
         .fetch_one(&handler.main_database)
         .await?;
-        if !log_options.log_actions && !log_options.log_messages && !log_options.log_voice {
-            return Ok(Some(4));
+        if !log_options.log_actions
+            && !log_options.log_messages
+            && !log_options.log_voice
+            && !log_options.log_members
+            && !log_options.log_server
+        {
+            return Ok(Some(8));
         }
 
         let message = ctx
@@ -528,6 +1043,168 @@ impl ConfigStage for LoggingChannelEnter {
     }
 }
 
+pub struct LoggingLogServer;
+#[async_trait::async_trait]
+impl ConfigStage for LoggingLogServer {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(LOGGING_TITLE)
+                            .description("Would you like to log changes to channels, roles, emojis, stickers, invites and server settings?")
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![CreateActionRow::Buttons(vec![
+                        CreateButton::new("yes")
+                            .label("Yes")
+                            .style(ButtonStyle::Success),
+                        CreateButton::new("no")
+                            .label("No")
+                            .style(ButtonStyle::Secondary),
+                    ])]),
+            )
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+        if let Some(interaction) = collector.await {
+            interaction
+                .create_response(
+                    &ctx.ctx.http,
+                    serenity::builder::CreateInteractionResponse::Acknowledge,
+                )
+                .await?;
+            match interaction.data.custom_id.as_str() {
+                "yes" => {
+                    sqlx::query!(
+                        "UPDATE logging_configuration SET log_server = true WHERE guild_id = $1",
+                        cmd.guild_id.unwrap().get() as i64
+                    )
+                    .execute(&handler.main_database)
+                    .await?;
+                    return Ok(None);
+                }
+                "no" => {
+                    sqlx::query!(
+                        "UPDATE logging_configuration SET log_server = false WHERE guild_id = $1",
+                        cmd.guild_id.unwrap().get() as i64
+                    )
+                    .execute(&handler.main_database)
+                    .await?;
+                    return Ok(None);
+                }
+                _ => {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid option",
+                            Some("Please select a valid option.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    })
+                }
+            }
+        }
+        Err(ConfigError {
+            error: ResponseError::Execution(
+                "Time out",
+                Some("We didn't get a response in time. Please try again.".to_string()),
+            ),
+            stages_to_skip: Some(100),
+        })
+    }
+}
+
+pub struct LoggingLogMembers;
+#[async_trait::async_trait]
+impl ConfigStage for LoggingLogMembers {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(LOGGING_TITLE)
+                            .description("Would you like to log member joins, leaves, nickname and role changes, and bans made outside of Reaper?")
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![CreateActionRow::Buttons(vec![
+                        CreateButton::new("yes")
+                            .label("Yes")
+                            .style(ButtonStyle::Success),
+                        CreateButton::new("no")
+                            .label("No")
+                            .style(ButtonStyle::Secondary),
+                    ])]),
+            )
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+        if let Some(interaction) = collector.await {
+            interaction
+                .create_response(
+                    &ctx.ctx.http,
+                    serenity::builder::CreateInteractionResponse::Acknowledge,
+                )
+                .await?;
+            match interaction.data.custom_id.as_str() {
+                "yes" => {
+                    sqlx::query!(
+                        "UPDATE logging_configuration SET log_members = true WHERE guild_id = $1",
+                        cmd.guild_id.unwrap().get() as i64
+                    )
+                    .execute(&handler.main_database)
+                    .await?;
+                    return Ok(None);
+                }
+                "no" => {
+                    sqlx::query!(
+                        "UPDATE logging_configuration SET log_members = false WHERE guild_id = $1",
+                        cmd.guild_id.unwrap().get() as i64
+                    )
+                    .execute(&handler.main_database)
+                    .await?;
+                    return Ok(None);
+                }
+                _ => {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid option",
+                            Some("Please select a valid option.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    })
+                }
+            }
+        }
+        Err(ConfigError {
+            error: ResponseError::Execution(
+                "Time out",
+                Some("We didn't get a response in time. Please try again.".to_string()),
+            ),
+            stages_to_skip: Some(100),
+        })
+    }
+}
+
 pub struct LoggingLogVoice;
 #[async_trait::async_trait]
 impl ConfigStage for LoggingLogVoice {
@@ -817,7 +1494,7 @@ impl ConfigStage for LoggingEnter {
                     return Ok(None);
                 }
                 "no" => {
-                    return Ok(Some(9));
+                    return Ok(Some(14));
                 }
                 _ => {
                     return Err(ConfigError {
@@ -1,5 +1,5 @@
 use serenity::{
-    all::CommandInteraction,
+    all::{ActionRowComponent, CommandInteraction, ModalInteraction},
     builder::{CreateCommand, CreateEmbed},
 };
 
@@ -12,9 +12,12 @@ use crate::models::{
 
 const EMBED_COLOR: i32 = 0x5539cc;
 
+mod anti_raid;
+mod automod;
 mod logging;
 mod moderation;
 mod role_recovery;
+mod spam;
 
 pub struct ConfigError {
     pub error: ResponseError,
@@ -48,6 +51,40 @@ impl From<serenity::Error> for ConfigError {
     }
 }
 
+fn timeout_error() -> ConfigError {
+    ConfigError {
+        error: ResponseError::Execution(
+            "Time out",
+            Some("We didn't get a response in time. Please try again.".to_string()),
+        ),
+        stages_to_skip: Some(100),
+    }
+}
+
+fn invalid_option() -> ConfigError {
+    ConfigError {
+        error: ResponseError::Execution(
+            "Invalid option",
+            Some("Please select a valid option.".to_string()),
+        ),
+        stages_to_skip: None,
+    }
+}
+
+fn get_modal_values(interaction: &ModalInteraction) -> Vec<String> {
+    interaction
+        .data
+        .components
+        .iter()
+        .filter_map(|row| match row.components.first() {
+            Some(ActionRowComponent::InputText(text)) => {
+                Some(text.value.clone().unwrap_or_default().trim().to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 #[async_trait::async_trait]
 trait ConfigStage: Send + Sync {
     async fn execute(
@@ -87,19 +124,35 @@ impl Command for ConfigCommand {
 
         let stages: Vec<Box<dyn ConfigStage>> = vec![
             Box::new(moderation::ModerationEnter),
+            Box::new(moderation::ModerationMuteBackend),
             Box::new(moderation::ModerationMuteRole),
             Box::new(moderation::ModerationDefaultStrikeDuration),
+            Box::new(moderation::ModerationCustomActions),
             Box::new(moderation::ModerationEscalations),
+            Box::new(moderation::ModerationEscalationSettings),
+            Box::new(moderation::ModerationAppealChannel),
+            Box::new(moderation::ModerationRemovalRecovery),
             Box::new(logging::LoggingEnter),
             Box::new(logging::LoggingLogActions),
             Box::new(logging::LoggingLogMessages),
             Box::new(logging::LoggingLogVoice),
+            Box::new(logging::LoggingLogMembers),
+            Box::new(logging::LoggingLogServer),
             Box::new(logging::LoggingChannelEnter),
             Box::new(logging::LoggingChannelSingle),
             Box::new(logging::LoggingChannelMultipleActions),
             Box::new(logging::LoggingChannelMultipleMessages),
             Box::new(logging::LoggingChannelMultipleVoice),
+            Box::new(logging::LoggingChannelMultipleMembers),
+            Box::new(logging::LoggingChannelMultipleServer),
+            Box::new(logging::LoggingIgnoreLists),
             Box::new(role_recovery::RoleRecovery),
+            Box::new(anti_raid::AntiRaidEnter),
+            Box::new(anti_raid::AntiRaidThreshold),
+            Box::new(anti_raid::AntiRaidResponse),
+            Box::new(spam::SpamEnter),
+            Box::new(spam::SpamSettings),
+            Box::new(automod::AutoModMappings),
         ];
 
         let mut current_stage = 0;
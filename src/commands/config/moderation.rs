@@ -1,7 +1,7 @@
 use serenity::{
     all::{
-        ActionRowComponent, ButtonStyle, CommandInteraction, ComponentInteractionDataKind,
-        InputTextStyle, Permissions,
+        ActionRowComponent, ButtonStyle, ChannelType, CommandInteraction,
+        ComponentInteractionDataKind, InputTextStyle, Permissions,
     },
     builder::{
         CreateActionRow, CreateButton, CreateEmbed, CreateInputText, CreateInteractionResponse,
@@ -12,53 +12,880 @@ use serenity::{
 
 use crate::{
     common::duration::Duration,
+    database::postgres::actions::get_custom_actions,
     models::{
-        actions::{ActionEscalation, ActionType},
+        actions::{ActionEscalation, CustomAction, EscalationType},
         command::{CommandContext, CommandContextReply},
         handler::Handler,
         response::{Response, ResponseError},
     },
 };
 
-use super::{ConfigError, ConfigStage, EMBED_COLOR};
+use super::{invalid_option, timeout_error, ConfigError, ConfigStage, EMBED_COLOR};
 
 const MODERATION_TITLE: &str = "Configuration - Moderation";
+// Discord allows at most 25 options in a select menu
+const MAX_SELECT_OPTIONS: usize = 25;
+const MAX_CUSTOM_ACTIONS: usize = 20;
+
+pub struct ModerationRemovalRecovery;
+#[async_trait::async_trait]
+impl ConfigStage for ModerationRemovalRecovery {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let recovery_days = sqlx::query!(
+            "SELECT removal_recovery_days FROM moderation_configuration WHERE guild_id = $1",
+            ctx.guild.id.get() as i64
+        )
+        .fetch_one(&handler.main_database)
+        .await?
+        .removal_recovery_days;
+
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(MODERATION_TITLE)
+                            .description(format!(
+                                "Removed actions can be restored with `/restore` for a period of time before they are permanently deleted. How long should removed actions be kept?\nRemoved actions are currently kept for {recovery_days} days"
+                            ))
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![
+                        CreateActionRow::SelectMenu(
+                            CreateSelectMenu::new(
+                                "recovery_days",
+                                CreateSelectMenuKind::String {
+                                    options: [1, 7, 30, 90, 365]
+                                        .iter()
+                                        .map(|days| {
+                                            CreateSelectMenuOption::new(
+                                                format!("{days} days"),
+                                                days.to_string(),
+                                            )
+                                            .default_selection(*days == recovery_days)
+                                        })
+                                        .collect(),
+                                },
+                            )
+                            .placeholder("Select a recovery period"),
+                        ),
+                        CreateActionRow::Buttons(vec![CreateButton::new("skip")
+                            .label("Skip")
+                            .style(ButtonStyle::Secondary)]),
+                    ]),
+            )
+            .await?;
+
+        let Some(interaction) = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0))
+            .await
+        else {
+            return Err(timeout_error());
+        };
+        interaction
+            .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+            .await?;
+
+        match (interaction.data.custom_id.as_str(), &interaction.data.kind) {
+            ("skip", _) => Ok(None),
+            ("recovery_days", ComponentInteractionDataKind::StringSelect { values }) => {
+                let Some(days) = values.first().and_then(|value| value.parse::<i32>().ok()) else {
+                    return Err(invalid_option());
+                };
+                sqlx::query!(
+                    "UPDATE moderation_configuration SET removal_recovery_days = $1 WHERE guild_id = $2",
+                    days,
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+                Ok(None)
+            }
+            _ => Err(invalid_option()),
+        }
+    }
+}
+
+pub struct ModerationAppealChannel;
+#[async_trait::async_trait]
+impl ConfigStage for ModerationAppealChannel {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let appeal_channel = sqlx::query!(
+            "SELECT appeal_channel FROM moderation_configuration WHERE guild_id = $1",
+            ctx.guild.id.get() as i64
+        )
+        .fetch_one(&handler.main_database)
+        .await?
+        .appeal_channel;
+
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(MODERATION_TITLE)
+                            .description(format!(
+                                "You can select a channel for appeals. When set, users will be able to appeal their punishments from the DM Reaper sends them, and moderators can accept or deny them in this channel.\nThe current appeals channel is: {}",
+                                match appeal_channel {
+                                    Some(channel) => format!("<#{channel}>"),
+                                    None => "None".to_string(),
+                                }
+                            ))
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![
+                        CreateActionRow::SelectMenu(CreateSelectMenu::new(
+                            "appeal_channel",
+                            CreateSelectMenuKind::Channel {
+                                channel_types: Some(vec![
+                                    ChannelType::Text,
+                                    ChannelType::PublicThread,
+                                    ChannelType::PrivateThread,
+                                ]),
+                                default_channels: None,
+                            },
+                        )),
+                        CreateActionRow::Buttons(vec![
+                            CreateButton::new("disable")
+                                .label("Disable appeals")
+                                .style(ButtonStyle::Danger),
+                            CreateButton::new("skip")
+                                .label("Skip")
+                                .style(ButtonStyle::Secondary),
+                        ]),
+                    ]),
+            )
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+
+        let Some(interaction) = collector.await else {
+            return Err(ConfigError {
+                error: ResponseError::Execution(
+                    "Time out",
+                    Some("We didn't get a response in time. Please try again.".to_string()),
+                ),
+                stages_to_skip: Some(100),
+            });
+        };
+        interaction
+            .create_response(
+                &ctx.ctx.http,
+                serenity::builder::CreateInteractionResponse::Acknowledge,
+            )
+            .await?;
+
+        match interaction.data.custom_id.as_str() {
+            "skip" => Ok(None),
+            "disable" => {
+                sqlx::query!(
+                    "UPDATE moderation_configuration SET appeal_channel = NULL WHERE guild_id = $1",
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+                Ok(None)
+            }
+            "appeal_channel" => {
+                let ComponentInteractionDataKind::ChannelSelect { values } = interaction.data.kind
+                else {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid option",
+                            Some("Please select a valid option.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    });
+                };
+                let channel = values.first().unwrap();
+                sqlx::query!(
+                    "UPDATE moderation_configuration SET appeal_channel = $1 WHERE guild_id = $2",
+                    channel.get() as i64,
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+                Ok(None)
+            }
+            _ => Err(ConfigError {
+                error: ResponseError::Execution(
+                    "Invalid option",
+                    Some("Please select a valid option.".to_string()),
+                ),
+                stages_to_skip: None,
+            }),
+        }
+    }
+}
+
+pub struct ModerationEscalationSettings;
+#[async_trait::async_trait]
+impl ConfigStage for ModerationEscalationSettings {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let config = sqlx::query!(
+            "SELECT escalation_channel, strike_window FROM moderation_configuration WHERE guild_id = $1",
+            ctx.guild.id.get() as i64
+        )
+        .fetch_one(&handler.main_database)
+        .await?;
+
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(MODERATION_TITLE)
+                            .description(format!(
+                                "You can select a channel for moderator alerts from strike escalations, and limit escalations to strikes from the last few days.\nAlerts are currently sent to: {}\n{}",
+                                match config.escalation_channel {
+                                    Some(channel) => format!("<#{channel}>"),
+                                    None => "The action log channel".to_string(),
+                                },
+                                match config.strike_window {
+                                    Some(days) => format!("Only strikes from the last **{days}** days count towards escalations"),
+                                    None => "All active strikes count towards escalations".to_string(),
+                                }
+                            ))
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![
+                        CreateActionRow::SelectMenu(CreateSelectMenu::new(
+                            "escalation_channel",
+                            CreateSelectMenuKind::Channel {
+                                channel_types: Some(vec![
+                                    ChannelType::Text,
+                                    ChannelType::PublicThread,
+                                    ChannelType::PrivateThread,
+                                ]),
+                                default_channels: None,
+                            },
+                        )),
+                        CreateActionRow::Buttons(vec![
+                            CreateButton::new("strike_window")
+                                .label("Set strike window")
+                                .style(ButtonStyle::Primary),
+                            CreateButton::new("reset")
+                                .label("Reset")
+                                .style(ButtonStyle::Danger),
+                            CreateButton::new("skip")
+                                .label("Skip")
+                                .style(ButtonStyle::Secondary),
+                        ]),
+                    ]),
+            )
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+
+        let Some(interaction) = collector.await else {
+            return Err(ConfigError {
+                error: ResponseError::Execution(
+                    "Time out",
+                    Some("We didn't get a response in time. Please try again.".to_string()),
+                ),
+                stages_to_skip: Some(100),
+            });
+        };
+
+        if interaction.data.custom_id.as_str() != "strike_window" {
+            interaction
+                .create_response(
+                    &ctx.ctx.http,
+                    serenity::builder::CreateInteractionResponse::Acknowledge,
+                )
+                .await?;
+        }
+
+        match interaction.data.custom_id.as_str() {
+            "skip" => Ok(None),
+            "reset" => {
+                sqlx::query!(
+                    "UPDATE moderation_configuration SET escalation_channel = NULL, strike_window = NULL WHERE guild_id = $1",
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+                Ok(None)
+            }
+            "escalation_channel" => {
+                let ComponentInteractionDataKind::ChannelSelect { values } = interaction.data.kind
+                else {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid option",
+                            Some("Please select a valid option.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    });
+                };
+                let channel = values.first().unwrap();
+                sqlx::query!(
+                    "UPDATE moderation_configuration SET escalation_channel = $1 WHERE guild_id = $2",
+                    channel.get() as i64,
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+                Ok(None)
+            }
+            "strike_window" => {
+                interaction
+                    .create_response(
+                        &ctx.ctx.http,
+                        CreateInteractionResponse::Modal(
+                            CreateModal::new("strike_window_modal", "Strike Window").components(
+                                vec![CreateActionRow::InputText(
+                                    CreateInputText::new(
+                                        InputTextStyle::Short,
+                                        "Days (leave empty to count all strikes)",
+                                        "strike_window",
+                                    )
+                                    .placeholder("30")
+                                    .required(false),
+                                )],
+                            ),
+                        ),
+                    )
+                    .await?;
+
+                let modal_collector = message
+                    .await_modal_interaction(&ctx.ctx)
+                    .author_id(cmd.user.id)
+                    .timeout(std::time::Duration::new(60, 0));
+
+                let Some(interaction) = modal_collector.await else {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Time out",
+                            Some("We didn't get a response in time. Please try again.".to_string()),
+                        ),
+                        stages_to_skip: Some(100),
+                    });
+                };
+                interaction
+                    .create_response(
+                        &ctx.ctx.http,
+                        serenity::builder::CreateInteractionResponse::Acknowledge,
+                    )
+                    .await?;
+
+                let ActionRowComponent::InputText(text) =
+                    &interaction.data.components[0].components[0]
+                else {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid option",
+                            Some("Please select a valid option.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    });
+                };
+                let value = text.value.clone().unwrap_or_default();
+                let strike_window = if value.trim().is_empty() {
+                    None
+                } else {
+                    let Some(days) = value.trim().parse::<i32>().ok().filter(|days| *days > 0)
+                    else {
+                        return Err(ConfigError {
+                            error: ResponseError::Execution(
+                                "Invalid strike window",
+                                Some("Please enter a valid amount of days.".to_string()),
+                            ),
+                            stages_to_skip: None,
+                        });
+                    };
+                    Some(days)
+                };
+                sqlx::query!(
+                    "UPDATE moderation_configuration SET strike_window = $1 WHERE guild_id = $2",
+                    strike_window,
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+                Ok(None)
+            }
+            _ => Err(ConfigError {
+                error: ResponseError::Execution(
+                    "Invalid option",
+                    Some("Please select a valid option.".to_string()),
+                ),
+                stages_to_skip: None,
+            }),
+        }
+    }
+}
 
 pub struct ModerationEscalations;
 
-impl ModerationEscalations {
-    fn generate_message(escalations: &[ActionEscalation]) -> Response {
+impl ModerationEscalations {
+    fn describe_escalation(escalation: &ActionEscalation) -> String {
+        match escalation.action_type {
+            EscalationType::Kick => "**kick** the user".to_string(),
+            EscalationType::Warn => "**DM** the user a warning".to_string(),
+            EscalationType::Alert => "**alert** the moderators".to_string(),
+            _ => format!(
+                "**{}** the user {}",
+                escalation
+                    .custom_action
+                    .clone()
+                    .unwrap_or(escalation.action_type.to_string()),
+                match escalation.action_duration.as_ref() {
+                    Some(duration) => format!("for **{duration}**"),
+                    None => "**indefinitely**".to_string(),
+                }
+            ),
+        }
+    }
+
+    fn generate_message(
+        escalations: &[ActionEscalation],
+        custom_actions: &[CustomAction],
+    ) -> Response {
+        let mut components = Vec::with_capacity(1);
+        if escalations.len() < 15 {
+            let mut options = vec![
+                CreateSelectMenuOption::new("Add a mute escalation", "mute"),
+                CreateSelectMenuOption::new("Add a kick escalation", "kick"),
+                CreateSelectMenuOption::new("Add a ban escalation", "ban"),
+                CreateSelectMenuOption::new("Add a DM warning escalation", "warn"),
+                CreateSelectMenuOption::new("Add a moderator alert escalation", "alert"),
+            ];
+            let remaining = MAX_SELECT_OPTIONS - options.len();
+            options.extend(custom_actions.iter().take(remaining).map(|custom_action| {
+                CreateSelectMenuOption::new(
+                    format!("Add a {} escalation", custom_action.name),
+                    format!("custom:{}", custom_action.name),
+                )
+            }));
+            components.push(CreateActionRow::SelectMenu(CreateSelectMenu::new(
+                "add_escalation",
+                CreateSelectMenuKind::String { options },
+            )));
+        }
+        if !escalations.is_empty() {
+            components.push(CreateActionRow::SelectMenu(CreateSelectMenu::new(
+                "remove_escalation",
+                CreateSelectMenuKind::String {
+                    options: escalations
+                        .iter()
+                        .enumerate()
+                        .map(|(index, escalation)| {
+                            CreateSelectMenuOption::new(
+                                format!(
+                                    "Remove {} at {} points",
+                                    escalation
+                                        .custom_action
+                                        .clone()
+                                        .unwrap_or(escalation.action_type.to_string()),
+                                    escalation.points
+                                ),
+                                index.to_string(),
+                            )
+                        })
+                        .collect(),
+                },
+            )));
+        }
+        components.push(CreateActionRow::Buttons(vec![
+            CreateButton::new("done")
+                .label("Done")
+                .style(ButtonStyle::Success),
+            CreateButton::new("revert")
+                .label("Revert")
+                .style(ButtonStyle::Danger),
+        ]));
+
+        let mut tiers: Vec<(i64, Vec<String>)> = vec![];
+        for escalation in escalations {
+            let description = ModerationEscalations::describe_escalation(escalation);
+            match tiers
+                .iter_mut()
+                .find(|(points, _)| *points == escalation.points)
+            {
+                Some((_, descriptions)) => descriptions.push(description),
+                None => tiers.push((escalation.points, vec![description])),
+            }
+        }
+
+        Response::new()
+            .embed(
+                CreateEmbed::new()
+                    .title(MODERATION_TITLE)
+                    .description("You can now configure your strike escalations. These are actions that will happen when a user's active strikes reach a certain amount of points. Escalations with the same amount of points will all run together, and the highest amount reached will be used.")
+                    .color(EMBED_COLOR)
+                    .fields(tiers.into_iter().map(|(points, descriptions)| {
+                        (format!("At {points} points"), format!("Reaper will {}.", descriptions.join(", ")), false)
+                    })),
+            )
+            .components(components)
+    }
+
+    async fn save_escalations(
+        escalations: &Vec<ActionEscalation>,
+        handler: &Handler,
+        ctx: &CommandContext,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM strike_escalations WHERE guild_id = $1",
+            ctx.guild.id.get() as i64
+        )
+        .execute(&handler.main_database)
+        .await?;
+        for escalation in escalations {
+            sqlx::query!(
+                "INSERT INTO strike_escalations (guild_id, points, action_type, action_duration, custom_action, message) VALUES ($1, $2, $3, $4, $5, $6)",
+                ctx.guild.id.get() as i64,
+                i32::try_from(escalation.points).unwrap(),
+                escalation.action_type.to_string(),
+                escalation.action_duration,
+                escalation.custom_action,
+                escalation.message
+            )
+            .execute(&handler.main_database)
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ConfigStage for ModerationEscalations {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let mut escalations = sqlx::query_as!(
+            ActionEscalation,
+            "SELECT * FROM strike_escalations WHERE guild_id = $1 ORDER BY points",
+            ctx.guild.id.get() as i64
+        )
+        .fetch_all(&handler.main_database)
+        .await?;
+        let custom_actions = get_custom_actions(handler, ctx.guild.id.get() as i64).await;
+
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                ModerationEscalations::generate_message(&escalations, &custom_actions),
+            )
+            .await?;
+
+        let mut collector = message
+            .await_component_interactions(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60 * 5, 0))
+            .stream();
+
+        while let Some(interaction) = collector.next().await {
+            if interaction.data.custom_id.as_str() != "add_escalation" {
+                interaction
+                    .create_response(
+                        &ctx.ctx.http,
+                        serenity::builder::CreateInteractionResponse::Acknowledge,
+                    )
+                    .await?;
+            }
+            match interaction.data.custom_id.as_str() {
+                "add_escalation" => {
+                    let (action_type, custom_action) =
+                        if let ComponentInteractionDataKind::StringSelect { values } =
+                            &interaction.data.kind
+                        {
+                            let value = values.first().unwrap();
+                            match value.strip_prefix("custom:") {
+                                Some(name) => (EscalationType::Custom, Some(name.to_string())),
+                                None => (EscalationType::from(value.as_str()), None),
+                            }
+                        } else {
+                            ModerationEscalations::save_escalations(&escalations, handler, ctx)
+                                .await?;
+                            return Err(ConfigError {
+                                error: ResponseError::Execution(
+                                    "Invalid option",
+                                    Some("Please select a valid option.".to_string()),
+                                ),
+                                stages_to_skip: None,
+                            });
+                        };
+
+                    let mut modal_components = vec![CreateActionRow::InputText(
+                        CreateInputText::new(InputTextStyle::Short, "Points", "escalation_points")
+                            .placeholder("3")
+                            .required(true),
+                    )];
+                    match action_type {
+                        EscalationType::Mute | EscalationType::Ban | EscalationType::Custom => {
+                            modal_components.push(CreateActionRow::InputText(
+                                CreateInputText::new(
+                                    InputTextStyle::Short,
+                                    "Duration",
+                                    "escalation_duration",
+                                )
+                                .placeholder("30d")
+                                .required(action_type == EscalationType::Mute),
+                            ));
+                        }
+                        EscalationType::Warn | EscalationType::Alert => {
+                            modal_components.push(CreateActionRow::InputText(
+                                CreateInputText::new(
+                                    InputTextStyle::Paragraph,
+                                    if action_type == EscalationType::Warn {
+                                        "Warning message"
+                                    } else {
+                                        "Note for moderators"
+                                    },
+                                    "escalation_message",
+                                )
+                                .max_length(255)
+                                .required(false),
+                            ));
+                        }
+                        EscalationType::Kick => {}
+                    }
+                    interaction
+                        .create_response(
+                            &ctx.ctx.http,
+                            CreateInteractionResponse::Modal(
+                                CreateModal::new("add_escalation_modal", "Add Escalation")
+                                    .components(modal_components),
+                            ),
+                        )
+                        .await?;
+
+                    let modal_collector = message
+                        .await_modal_interaction(&ctx.ctx)
+                        .author_id(cmd.user.id)
+                        .timeout(std::time::Duration::new(60, 0));
+
+                    let Some(interaction) = modal_collector.await else {
+                        ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
+                        return Err(ConfigError {
+                            error: ResponseError::Execution(
+                                "Time out",
+                                Some(
+                                    "We didn't get a response in time. Please try again."
+                                        .to_string(),
+                                ),
+                            ),
+                            stages_to_skip: Some(100),
+                        });
+                    };
+                    interaction
+                        .create_response(
+                            &ctx.ctx.http,
+                            serenity::builder::CreateInteractionResponse::Acknowledge,
+                        )
+                        .await?;
+
+                    let inputs = interaction
+                        .data
+                        .components
+                        .iter()
+                        .filter_map(|row| match row.components.first() {
+                            Some(ActionRowComponent::InputText(text)) => Some((
+                                text.custom_id.as_str(),
+                                text.value.clone().unwrap_or_default(),
+                            )),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>();
+                    let get_input = |custom_id: &str| {
+                        inputs
+                            .iter()
+                            .find(|(id, _)| *id == custom_id)
+                            .map(|(_, value)| value.trim().to_string())
+                            .filter(|value| !value.is_empty())
+                    };
+
+                    let Some(points) = get_input("escalation_points")
+                        .and_then(|points| points.parse::<i64>().ok())
+                        .filter(|points| *points > 0)
+                    else {
+                        ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
+                        return Err(ConfigError {
+                            error: ResponseError::Execution(
+                                "Invalid points",
+                                Some("Please enter a valid amount of points above 0.".to_string()),
+                            ),
+                            stages_to_skip: None,
+                        });
+                    };
+                    if escalations.iter().any(|escalation| {
+                        escalation.points == points
+                            && escalation.action_type == action_type
+                            && escalation.custom_action == custom_action
+                    }) {
+                        ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
+                        return Err(ConfigError {
+                            error: ResponseError::Execution(
+                                "Duplicate escalation",
+                                Some(
+                                    "This escalation already exists at that amount of points."
+                                        .to_string(),
+                                ),
+                            ),
+                            stages_to_skip: None,
+                        });
+                    }
+
+                    let action_duration = get_input("escalation_duration");
+                    if let Some(duration) = &action_duration {
+                        if Duration::new(duration)
+                            .to_timestamp()
+                            .is_none_or(|duration| duration < time::OffsetDateTime::now_utc())
+                        {
+                            ModerationEscalations::save_escalations(&escalations, handler, ctx)
+                                .await?;
+                            return Err(ConfigError {
+                                error: ResponseError::Execution(
+                                    "Invalid duration",
+                                    Some("Please enter a valid duration.".to_string()),
+                                ),
+                                stages_to_skip: None,
+                            });
+                        }
+                    } else if action_type == EscalationType::Mute {
+                        ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
+                        return Err(ConfigError {
+                            error: ResponseError::Execution(
+                                "Invalid duration",
+                                Some("Please enter a valid duration.".to_string()),
+                            ),
+                            stages_to_skip: None,
+                        });
+                    }
+
+                    escalations.push(ActionEscalation {
+                        guild_id: ctx.guild.id.get() as i64,
+                        points,
+                        action_type,
+                        action_duration,
+                        custom_action,
+                        message: get_input("escalation_message"),
+                    });
+                    escalations.sort_by_key(|escalation| escalation.points);
+                }
+                "remove_escalation" => {
+                    if let ComponentInteractionDataKind::StringSelect { values } =
+                        &interaction.data.kind
+                    {
+                        let Ok(index) = values.first().unwrap().parse::<usize>() else {
+                            ModerationEscalations::save_escalations(&escalations, handler, ctx)
+                                .await?;
+                            return Err(ResponseError::Execution(
+                                "Invalid escalation",
+                                Some("Please select a valid escalation.".to_string()),
+                            )
+                            .into());
+                        };
+
+                        escalations.remove(index);
+                    } else {
+                        ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
+                        return Err(ConfigError {
+                            error: ResponseError::Execution(
+                                "Invalid option",
+                                Some("Please select a valid option.".to_string()),
+                            ),
+                            stages_to_skip: None,
+                        });
+                    }
+                }
+                "done" => {
+                    ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
+                    return Ok(None);
+                }
+                "revert" => {
+                    return Ok(Some(0));
+                }
+                _ => {
+                    ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid option",
+                            Some("Please select a valid option.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    });
+                }
+            }
+            ctx.reply(
+                cmd,
+                ModerationEscalations::generate_message(&escalations, &custom_actions),
+            )
+            .await?;
+        }
+
+        Ok(None)
+    }
+}
+
+pub struct ModerationCustomActions;
+
+impl ModerationCustomActions {
+    fn generate_message(custom_actions: &[CustomAction]) -> Response {
         let mut components = Vec::with_capacity(1);
-        if escalations.len() < 15 {
-            components.push(CreateActionRow::SelectMenu(CreateSelectMenu::new(
-                "add_escalation",
-                CreateSelectMenuKind::String {
-                    options: vec![
-                        CreateSelectMenuOption::new("Add a mute escalation", "mute"),
-                        CreateSelectMenuOption::new("Add a kick escalation", "kick"),
-                        CreateSelectMenuOption::new("Add a ban escalation", "ban"),
-                    ],
-                },
-            )));
+        if custom_actions.len() < MAX_CUSTOM_ACTIONS {
+            components.push(CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    "add_custom_action",
+                    CreateSelectMenuKind::Role {
+                        default_roles: None,
+                    },
+                )
+                .placeholder("Select a role to add a punishment"),
+            ));
         }
-        if !escalations.is_empty() {
-            components.push(CreateActionRow::SelectMenu(CreateSelectMenu::new(
-                "remove_escalation",
-                CreateSelectMenuKind::String {
-                    options: (0..escalations.len())
-                        .map(|index| {
-                            CreateSelectMenuOption::new(
-                                format!(
-                                    "Remove {}{} escalation",
-                                    index + 1,
-                                    ordinal::Ordinal(index + 1).suffix()
-                                ),
-                                index.to_string(),
-                            )
-                        })
-                        .collect(),
-                },
-            )));
+        if !custom_actions.is_empty() {
+            components.push(CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    "remove_custom_action",
+                    CreateSelectMenuKind::String {
+                        options: custom_actions
+                            .iter()
+                            .take(MAX_SELECT_OPTIONS)
+                            .enumerate()
+                            .map(|(index, custom_action)| {
+                                CreateSelectMenuOption::new(
+                                    format!("Remove {}", custom_action.name),
+                                    index.to_string(),
+                                )
+                            })
+                            .collect(),
+                    },
+                )
+                .placeholder("Select a punishment to remove"),
+            ));
         }
         components.push(CreateActionRow::Buttons(vec![
             CreateButton::new("done")
@@ -72,36 +899,32 @@ impl ModerationEscalations {
             .embed(
                 CreateEmbed::new()
                     .title(MODERATION_TITLE)
-                    .description("You can now configure your strike escalations. These are actions that will happen when a user reaches a certain amount of strikes.")
+                    .description("You can now configure custom punishments. These give a user a role, optionally for a duration, and can be issued with `/punish` or used as strike escalations.")
                     .color(EMBED_COLOR)
-                    .fields(escalations.iter().enumerate().map(|(index, escalation)| {
-                        (format!("{}{} escalation", index + 1, ordinal::Ordinal(index + 1).suffix()), format!("At **{}** strikes, Reaper will **{}** the user {}.", escalation.strike_count, escalation.action_type, match escalation.action_duration.as_ref() {
-                            Some(duration) => format!("for **{duration}**"),
-                            None => "**indefinitely**".to_string(),
-                        }), false)
+                    .fields(custom_actions.iter().map(|custom_action| {
+                        (custom_action.name.clone(), format!("Reaper will give the user <@&{}>", custom_action.role_id), false)
                     })),
             )
             .components(components)
     }
 
-    async fn save_escalations(
-        escalations: &Vec<ActionEscalation>,
+    async fn save_custom_actions(
+        custom_actions: &Vec<CustomAction>,
         handler: &Handler,
         ctx: &CommandContext,
     ) -> Result<(), sqlx::Error> {
         sqlx::query!(
-            "DELETE FROM strike_escalations WHERE guild_id = $1",
+            "DELETE FROM custom_actions WHERE guild_id = $1",
             ctx.guild.id.get() as i64
         )
         .execute(&handler.main_database)
         .await?;
-        for escalation in escalations {
+        for custom_action in custom_actions {
             sqlx::query!(
-                "INSERT INTO strike_escalations (guild_id, strike_count, action_type, action_duration) VALUES ($1, $2, $3, $4)",
+                "INSERT INTO custom_actions (guild_id, name, role_id) VALUES ($1, $2, $3)",
                 ctx.guild.id.get() as i64,
-                i32::try_from(escalation.strike_count).unwrap(),
-                escalation.action_type.to_string(),
-                escalation.action_duration
+                custom_action.name,
+                custom_action.role_id
             )
             .execute(&handler.main_database)
             .await?;
@@ -111,23 +934,20 @@ impl ModerationEscalations {
 }
 
 #[async_trait::async_trait]
-impl ConfigStage for ModerationEscalations {
+impl ConfigStage for ModerationCustomActions {
     async fn execute(
         &self,
         handler: &Handler,
         ctx: &CommandContext,
         cmd: &CommandInteraction,
     ) -> Result<Option<usize>, ConfigError> {
-        let mut escalations = sqlx::query_as!(
-            ActionEscalation,
-            "SELECT * FROM strike_escalations WHERE guild_id = $1",
-            ctx.guild.id.get() as i64
-        )
-        .fetch_all(&handler.main_database)
-        .await?;
+        let mut custom_actions = get_custom_actions(handler, ctx.guild.id.get() as i64).await;
 
         let message = ctx
-            .reply_get_message(cmd, ModerationEscalations::generate_message(&escalations))
+            .reply_get_message(
+                cmd,
+                ModerationCustomActions::generate_message(&custom_actions),
+            )
             .await?;
 
         let mut collector = message
@@ -137,7 +957,7 @@ impl ConfigStage for ModerationEscalations {
             .stream();
 
         while let Some(interaction) = collector.next().await {
-            if interaction.data.custom_id.as_str() != "add_escalation" {
+            if interaction.data.custom_id.as_str() != "add_custom_action" {
                 interaction
                     .create_response(
                         &ctx.ctx.http,
@@ -146,13 +966,12 @@ impl ConfigStage for ModerationEscalations {
                     .await?;
             }
             match interaction.data.custom_id.as_str() {
-                "add_escalation" => {
-                    let action_type = if let ComponentInteractionDataKind::StringSelect { values } =
+                "add_custom_action" => {
+                    let ComponentInteractionDataKind::RoleSelect { values } =
                         &interaction.data.kind
-                    {
-                        ActionType::from(values.first().unwrap().as_str())
-                    } else {
-                        ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
+                    else {
+                        ModerationCustomActions::save_custom_actions(&custom_actions, handler, ctx)
+                            .await?;
                         return Err(ConfigError {
                             error: ResponseError::Execution(
                                 "Invalid option",
@@ -161,33 +980,60 @@ impl ConfigStage for ModerationEscalations {
                             stages_to_skip: None,
                         });
                     };
-
-                    let mut modal_components = vec![CreateActionRow::InputText(
-                        CreateInputText::new(
-                            InputTextStyle::Short,
-                            "Strike Count",
-                            "escalation_strike_count",
+                    let Some(role) = values.first() else {
+                        ModerationCustomActions::save_custom_actions(&custom_actions, handler, ctx)
+                            .await?;
+                        return Err(ResponseError::Execution(
+                            "No role selected",
+                            Some("Please select a role.".to_string()),
                         )
-                        .placeholder("3")
-                        .required(true),
-                    )];
-                    if action_type != ActionType::Kick {
-                        modal_components.push(CreateActionRow::InputText(
-                            CreateInputText::new(
-                                InputTextStyle::Short,
-                                "Duration",
-                                "escalation_duration",
-                            )
-                            .placeholder("30d")
-                            .required(action_type == ActionType::Mute),
-                        ));
+                        .into());
+                    };
+
+                    let Some(guild_role) = ctx.guild.roles.get(role) else {
+                        ModerationCustomActions::save_custom_actions(&custom_actions, handler, ctx)
+                            .await?;
+                        return Err(ConfigError {
+                            error: ResponseError::Execution(
+                                "Invalid role",
+                                Some("Please select a valid role.".to_string()),
+                            ),
+                            stages_to_skip: None,
+                        });
+                    };
+                    let role_position =
+                        if guild_role.permissions.contains(Permissions::ADMINISTRATOR) {
+                            u16::MAX - 1
+                        } else {
+                            guild_role.position
+                        };
+                    if role_position >= ctx.highest_role {
+                        ModerationCustomActions::save_custom_actions(&custom_actions, handler, ctx)
+                            .await?;
+                        return Err(ConfigError {
+                            error: ResponseError::Execution(
+                                "Cannot set this role",
+                                Some("The role you have selected is higher than yours, please move it and try again.".to_string()),
+                            ),
+                            stages_to_skip: None,
+                        });
                     }
+
                     interaction
                         .create_response(
                             &ctx.ctx.http,
                             CreateInteractionResponse::Modal(
-                                CreateModal::new("add_escalation_modal", "Add Escalation")
-                                    .components(modal_components),
+                                CreateModal::new("add_custom_action_modal", "Add Punishment")
+                                    .components(vec![CreateActionRow::InputText(
+                                        CreateInputText::new(
+                                            InputTextStyle::Short,
+                                            "Name",
+                                            "custom_action_name",
+                                        )
+                                        .placeholder("media ban")
+                                        .max_length(32)
+                                        .required(true),
+                                    )]),
                             ),
                         )
                         .await?;
@@ -197,123 +1043,9 @@ impl ConfigStage for ModerationEscalations {
                         .author_id(cmd.user.id)
                         .timeout(std::time::Duration::new(60, 0));
 
-                    if let Some(interaction) = modal_collector.await {
-                        interaction
-                            .create_response(
-                                &ctx.ctx.http,
-                                serenity::builder::CreateInteractionResponse::Acknowledge,
-                            )
+                    let Some(interaction) = modal_collector.await else {
+                        ModerationCustomActions::save_custom_actions(&custom_actions, handler, ctx)
                             .await?;
-
-                        let strike_count = if let ActionRowComponent::InputText(text) =
-                            &interaction.data.components[0].components[0]
-                        {
-                            let Ok(strike_count) = text.value.as_ref().unwrap().parse::<i64>()
-                            else {
-                                ModerationEscalations::save_escalations(&escalations, handler, ctx)
-                                    .await?;
-                                return Err(ResponseError::Execution(
-                                    "Invalid strike count",
-                                    Some("Please enter a valid strike count.".to_string()),
-                                )
-                                .into());
-                            };
-                            if strike_count > 0 {
-                                if escalations
-                                    .iter()
-                                    .any(|escalation| escalation.strike_count == strike_count)
-                                {
-                                    ModerationEscalations::save_escalations(
-                                        &escalations,
-                                        handler,
-                                        ctx,
-                                    )
-                                    .await?;
-                                    return Err(ConfigError {
-                                        error: ResponseError::Execution(
-                                            "Invalid strike count",
-                                            Some(
-                                                "You cannot enter a duplicate strike count."
-                                                    .to_string(),
-                                            ),
-                                        ),
-                                        stages_to_skip: None,
-                                    });
-                                }
-                                strike_count
-                            } else {
-                                ModerationEscalations::save_escalations(&escalations, handler, ctx)
-                                    .await?;
-                                return Err(ConfigError {
-                                    error: ResponseError::Execution(
-                                        "Invalid strike count",
-                                        Some(
-                                            "You cannot enter a strike count below 0.".to_string(),
-                                        ),
-                                    ),
-                                    stages_to_skip: None,
-                                });
-                            }
-                        } else {
-                            ModerationEscalations::save_escalations(&escalations, handler, ctx)
-                                .await?;
-                            return Err(ConfigError {
-                                error: ResponseError::Execution(
-                                    "Invalid option",
-                                    Some("Please select a valid option.".to_string()),
-                                ),
-                                stages_to_skip: None,
-                            });
-                        };
-
-                        let action_duration = if action_type == ActionType::Kick {
-                            None
-                        } else if let ActionRowComponent::InputText(text) =
-                            &interaction.data.components[1].components[0]
-                        {
-                            let value = text.value.clone().unwrap();
-                            if value.is_empty() {
-                                None
-                            } else {
-                                let duration =
-                                    Duration::new(value.as_str()).to_timestamp().unwrap();
-                                if duration < time::OffsetDateTime::now_utc() {
-                                    ModerationEscalations::save_escalations(
-                                        &escalations,
-                                        handler,
-                                        ctx,
-                                    )
-                                    .await?;
-                                    return Err(ConfigError {
-                                        error: ResponseError::Execution(
-                                            "Invalid duration",
-                                            Some("Please enter a valid duration.".to_string()),
-                                        ),
-                                        stages_to_skip: None,
-                                    });
-                                }
-                                Some(value)
-                            }
-                        } else {
-                            ModerationEscalations::save_escalations(&escalations, handler, ctx)
-                                .await?;
-                            return Err(ConfigError {
-                                error: ResponseError::Execution(
-                                    "Invalid option",
-                                    Some("Please select a valid option.".to_string()),
-                                ),
-                                stages_to_skip: None,
-                            });
-                        };
-
-                        escalations.push(ActionEscalation {
-                            guild_id: ctx.guild.id.get() as i64,
-                            strike_count,
-                            action_type,
-                            action_duration,
-                        });
-                    } else {
-                        ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
                         return Err(ConfigError {
                             error: ResponseError::Execution(
                                 "Time out",
@@ -324,25 +1056,92 @@ impl ConfigStage for ModerationEscalations {
                             ),
                             stages_to_skip: Some(100),
                         });
+                    };
+                    interaction
+                        .create_response(
+                            &ctx.ctx.http,
+                            serenity::builder::CreateInteractionResponse::Acknowledge,
+                        )
+                        .await?;
+
+                    let name = if let ActionRowComponent::InputText(text) =
+                        &interaction.data.components[0].components[0]
+                    {
+                        text.value.clone().unwrap_or_default().trim().to_lowercase()
+                    } else {
+                        String::new()
+                    };
+                    if name.is_empty()
+                        || custom_actions
+                            .iter()
+                            .any(|custom_action| custom_action.name == name)
+                    {
+                        ModerationCustomActions::save_custom_actions(&custom_actions, handler, ctx)
+                            .await?;
+                        return Err(ConfigError {
+                            error: ResponseError::Execution(
+                                "Invalid name",
+                                Some("Please enter a name that is not already in use.".to_string()),
+                            ),
+                            stages_to_skip: None,
+                        });
                     }
+
+                    custom_actions.push(CustomAction {
+                        name,
+                        role_id: role.get() as i64,
+                    });
                 }
-                "remove_escalation" => {
+                "remove_custom_action" => {
                     if let ComponentInteractionDataKind::StringSelect { values } =
                         &interaction.data.kind
                     {
                         let Ok(index) = values.first().unwrap().parse::<usize>() else {
-                            ModerationEscalations::save_escalations(&escalations, handler, ctx)
-                                .await?;
+                            ModerationCustomActions::save_custom_actions(
+                                &custom_actions,
+                                handler,
+                                ctx,
+                            )
+                            .await?;
                             return Err(ResponseError::Execution(
-                                "Invalid escalation",
-                                Some("Please select a valid escalation.".to_string()),
+                                "Invalid punishment",
+                                Some("Please select a valid punishment.".to_string()),
                             )
                             .into());
                         };
 
-                        escalations.remove(index);
+                        // Active actions find their role by the punishment name when they are lifted
+                        if let Some(custom_action) = custom_actions.get(index) {
+                            let active = sqlx::query!(
+                                "SELECT COUNT(*) AS \"count!\" FROM actions WHERE guild_id = $1 AND action_type = 'custom' AND active = true AND LOWER(custom_action) = LOWER($2)",
+                                ctx.guild.id.get() as i64,
+                                custom_action.name
+                            )
+                            .fetch_one(&handler.main_database)
+                            .await?
+                            .count;
+                            if active > 0 {
+                                let name = custom_action.name.clone();
+                                ModerationCustomActions::save_custom_actions(
+                                    &custom_actions,
+                                    handler,
+                                    ctx,
+                                )
+                                .await?;
+                                return Err(ResponseError::Execution(
+                                    "Punishment still in use",
+                                    Some(format!(
+                                        "**{name}** is still active on {active} users. Expire those actions before removing it."
+                                    )),
+                                )
+                                .into());
+                            }
+                        }
+
+                        custom_actions.remove(index);
                     } else {
-                        ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
+                        ModerationCustomActions::save_custom_actions(&custom_actions, handler, ctx)
+                            .await?;
                         return Err(ConfigError {
                             error: ResponseError::Execution(
                                 "Invalid option",
@@ -353,14 +1152,16 @@ impl ConfigStage for ModerationEscalations {
                     }
                 }
                 "done" => {
-                    ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
+                    ModerationCustomActions::save_custom_actions(&custom_actions, handler, ctx)
+                        .await?;
                     return Ok(None);
                 }
                 "revert" => {
                     return Ok(Some(0));
                 }
                 _ => {
-                    ModerationEscalations::save_escalations(&escalations, handler, ctx).await?;
+                    ModerationCustomActions::save_custom_actions(&custom_actions, handler, ctx)
+                        .await?;
                     return Err(ConfigError {
                         error: ResponseError::Execution(
                             "Invalid option",
@@ -370,8 +1171,11 @@ impl ConfigStage for ModerationEscalations {
                     });
                 }
             }
-            ctx.reply(cmd, ModerationEscalations::generate_message(&escalations))
-                .await?;
+            ctx.reply(
+                cmd,
+                ModerationCustomActions::generate_message(&custom_actions),
+            )
+            .await?;
         }
 
         Ok(None)
@@ -681,6 +1485,99 @@ impl ConfigStage for ModerationMuteRole {
     }
 }
 
+pub struct ModerationMuteBackend;
+#[async_trait::async_trait]
+impl ConfigStage for ModerationMuteBackend {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let use_timeouts = sqlx::query!(
+            "SELECT use_timeouts FROM moderation_configuration WHERE guild_id = $1",
+            ctx.guild.id.get() as i64
+        )
+        .fetch_one(&handler.main_database)
+        .await?
+        .use_timeouts;
+
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(MODERATION_TITLE)
+                            .description(format!("How would you like Reaper to mute users?\nReaper can either add a mute role, or use Discord's built-in timeouts. Mutes longer than 28 days will be renewed automatically when using timeouts.\nReaper is currently using: **{}**", if use_timeouts {
+                                "Timeouts"
+                            } else {
+                                "Mute role"
+                            }))
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![CreateActionRow::Buttons(vec![
+                        CreateButton::new("role")
+                            .label("Mute role")
+                            .style(ButtonStyle::Primary),
+                        CreateButton::new("timeout")
+                            .label("Timeouts")
+                            .style(ButtonStyle::Primary),
+                    ])]),
+            )
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+        if let Some(interaction) = collector.await {
+            interaction
+                .create_response(
+                    &ctx.ctx.http,
+                    serenity::builder::CreateInteractionResponse::Acknowledge,
+                )
+                .await?;
+            match interaction.data.custom_id.as_str() {
+                "role" => {
+                    sqlx::query!(
+                        "UPDATE moderation_configuration SET use_timeouts = false WHERE guild_id = $1",
+                        ctx.guild.id.get() as i64
+                    )
+                    .execute(&handler.main_database)
+                    .await?;
+                    return Ok(None);
+                }
+                "timeout" => {
+                    sqlx::query!(
+                        "UPDATE moderation_configuration SET use_timeouts = true WHERE guild_id = $1",
+                        ctx.guild.id.get() as i64
+                    )
+                    .execute(&handler.main_database)
+                    .await?;
+                    return Ok(Some(2));
+                }
+                _ => {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid option",
+                            Some("Please select a valid option.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    })
+                }
+            }
+        }
+        Err(ConfigError {
+            error: ResponseError::Execution(
+                "Time out",
+                Some("We didn't get a response in time. Please try again.".to_string()),
+            ),
+            stages_to_skip: Some(100),
+        })
+    }
+}
+
 pub struct ModerationEnter;
 #[async_trait::async_trait]
 impl ConfigStage for ModerationEnter {
@@ -727,7 +1624,7 @@ impl ConfigStage for ModerationEnter {
                     return Ok(None);
                 }
                 "no" => {
-                    return Ok(Some(4));
+                    return Ok(Some(9));
                 }
                 _ => {
                     return Err(ConfigError {
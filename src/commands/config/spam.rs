@@ -0,0 +1,392 @@
+use serenity::{
+    all::{ButtonStyle, CommandInteraction, ComponentInteractionDataKind, InputTextStyle},
+    builder::{
+        CreateActionRow, CreateButton, CreateEmbed, CreateInputText, CreateInteractionResponse,
+        CreateModal, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+    },
+};
+
+use crate::{
+    common::duration::Duration,
+    database::postgres::guild::get_spam_config,
+    models::{
+        command::{CommandContext, CommandContextReply},
+        config::SpamConfig,
+        handler::Handler,
+        response::{Response, ResponseError},
+    },
+};
+
+use super::{
+    get_modal_values, invalid_option, timeout_error, ConfigError, ConfigStage, EMBED_COLOR,
+};
+
+const SPAM_TITLE: &str = "Configuration - Spam Filter";
+
+fn describe_limit(limit: Option<i32>) -> String {
+    limit.map_or("Disabled".to_string(), |limit| limit.to_string())
+}
+
+fn limit_input(label: &str, id: &str, value: Option<i32>) -> CreateActionRow {
+    let input = CreateInputText::new(InputTextStyle::Short, label, id)
+        .placeholder("Leave empty to disable")
+        .required(false);
+    CreateActionRow::InputText(match value {
+        Some(value) => input.value(value.to_string()),
+        None => input,
+    })
+}
+
+fn parse_limit(value: Option<&String>) -> Result<Option<i32>, ()> {
+    match value.map(String::as_str) {
+        None | Some("") => Ok(None),
+        Some(value) => match value.parse::<i32>() {
+            Ok(limit) if limit > 0 => Ok(Some(limit)),
+            _ => Err(()),
+        },
+    }
+}
+
+fn invalid_limit() -> ConfigError {
+    ConfigError {
+        error: ResponseError::Execution(
+            "Invalid limit",
+            Some("Limits must be positive numbers.".to_string()),
+        ),
+        stages_to_skip: None,
+    }
+}
+
+pub struct SpamEnter;
+#[async_trait::async_trait]
+impl ConfigStage for SpamEnter {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let message = ctx
+            .reply_get_message(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(SPAM_TITLE)
+                            .description("Would you like to enable the spam filter?\nReaper will delete spam and punish users who send messages too quickly, repeat the same message, mass mention or send walls of newlines or emojis.")
+                            .color(EMBED_COLOR),
+                    )
+                    .components(vec![CreateActionRow::Buttons(vec![
+                        CreateButton::new("yes")
+                            .label("Yes")
+                            .style(ButtonStyle::Success),
+                        CreateButton::new("no")
+                            .label("No")
+                            .style(ButtonStyle::Secondary),
+                    ])]),
+            )
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+        let Some(interaction) = collector.await else {
+            return Err(timeout_error());
+        };
+        interaction
+            .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+            .await?;
+
+        let enabled = match interaction.data.custom_id.as_str() {
+            "yes" => true,
+            "no" => false,
+            _ => return Err(invalid_option()),
+        };
+        sqlx::query!(
+            "UPDATE spam_configuration SET enabled = $1 WHERE guild_id = $2",
+            enabled,
+            ctx.guild.id.get() as i64
+        )
+        .execute(&handler.main_database)
+        .await?;
+
+        if enabled {
+            Ok(None)
+        } else {
+            Ok(Some(2))
+        }
+    }
+}
+
+pub struct SpamSettings;
+
+impl SpamSettings {
+    fn generate_message(config: &SpamConfig) -> Response {
+        Response::new()
+            .embed(
+                CreateEmbed::new()
+                    .title(SPAM_TITLE)
+                    .description("Configure the spam filter limits and what happens to users who break them. Any limit left empty is not checked.")
+                    .field(
+                        "Message rate",
+                        format!(
+                            "{} messages in {} seconds",
+                            describe_limit(config.message_limit),
+                            config.message_window
+                        ),
+                        true,
+                    )
+                    .field(
+                        "Identical messages",
+                        describe_limit(config.duplicate_limit),
+                        true,
+                    )
+                    .field("Mentions", describe_limit(config.mention_limit), true)
+                    .field("Newlines", describe_limit(config.newline_limit), true)
+                    .field("Emojis", describe_limit(config.emoji_limit), true)
+                    .field(
+                        "Punishment",
+                        match config.punishment.as_str() {
+                            "mute" => format!(
+                                "Mute for {}",
+                                config.mute_duration.as_deref().unwrap_or("10m")
+                            ),
+                            "none" => "Delete only".to_string(),
+                            _ => "Strike".to_string(),
+                        },
+                        true,
+                    )
+                    .field("Reason", config.reason.clone(), false)
+                    .color(EMBED_COLOR),
+            )
+            .components(vec![
+                CreateActionRow::SelectMenu(
+                    CreateSelectMenu::new(
+                        "punishment",
+                        CreateSelectMenuKind::String {
+                            options: vec![
+                                CreateSelectMenuOption::new("Strike the user", "strike"),
+                                CreateSelectMenuOption::new("Mute the user", "mute"),
+                                CreateSelectMenuOption::new("Only delete the messages", "none"),
+                            ],
+                        },
+                    )
+                    .placeholder("Select a punishment"),
+                ),
+                CreateActionRow::Buttons(vec![
+                    CreateButton::new("rate_limits")
+                        .label("Rate limits")
+                        .style(ButtonStyle::Primary),
+                    CreateButton::new("content_limits")
+                        .label("Content limits")
+                        .style(ButtonStyle::Primary),
+                    CreateButton::new("reason")
+                        .label("Reason")
+                        .style(ButtonStyle::Primary),
+                    CreateButton::new("done")
+                        .label("Done")
+                        .style(ButtonStyle::Success),
+                ]),
+            ])
+    }
+}
+
+#[async_trait::async_trait]
+impl ConfigStage for SpamSettings {
+    async fn execute(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> Result<Option<usize>, ConfigError> {
+        let Some(config) = get_spam_config(handler, ctx.guild.id.get() as i64).await else {
+            return Err(invalid_option());
+        };
+
+        let message = ctx
+            .reply_get_message(cmd, Self::generate_message(&config))
+            .await?;
+
+        let collector = message
+            .await_component_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0));
+        let Some(interaction) = collector.await else {
+            return Err(timeout_error());
+        };
+
+        let modal = match interaction.data.custom_id.as_str() {
+            "rate_limits" => Some(
+                CreateModal::new("rate_limits_modal", "Rate Limits").components(vec![
+                    limit_input("Messages", "message_limit", config.message_limit),
+                    CreateActionRow::InputText(
+                        CreateInputText::new(
+                            InputTextStyle::Short,
+                            "Window (seconds)",
+                            "message_window",
+                        )
+                        .value(config.message_window.to_string()),
+                    ),
+                    limit_input(
+                        "Identical messages",
+                        "duplicate_limit",
+                        config.duplicate_limit,
+                    ),
+                ]),
+            ),
+            "content_limits" => Some(
+                CreateModal::new("content_limits_modal", "Content Limits").components(vec![
+                    limit_input("Mentions", "mention_limit", config.mention_limit),
+                    limit_input("Newlines", "newline_limit", config.newline_limit),
+                    limit_input("Emojis", "emoji_limit", config.emoji_limit),
+                ]),
+            ),
+            "reason" => Some(
+                CreateModal::new("reason_modal", "Punishment").components(vec![
+                    CreateActionRow::InputText(
+                        CreateInputText::new(InputTextStyle::Short, "Reason", "reason")
+                            .max_length(200)
+                            .value(config.reason.clone()),
+                    ),
+                    CreateActionRow::InputText(
+                        CreateInputText::new(
+                            InputTextStyle::Short,
+                            "Mute duration",
+                            "mute_duration",
+                        )
+                        .placeholder("10m")
+                        .value(config.mute_duration.clone().unwrap_or_default())
+                        .required(false),
+                    ),
+                ]),
+            ),
+            _ => None,
+        };
+
+        let Some(modal) = modal else {
+            interaction
+                .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+                .await?;
+            return match interaction.data.custom_id.as_str() {
+                "done" => Ok(None),
+                "punishment" => {
+                    let ComponentInteractionDataKind::StringSelect { values } =
+                        &interaction.data.kind
+                    else {
+                        return Err(invalid_option());
+                    };
+                    let Some(punishment) = values
+                        .first()
+                        .filter(|value| ["strike", "mute", "none"].contains(&value.as_str()))
+                    else {
+                        return Err(invalid_option());
+                    };
+                    sqlx::query!(
+                        "UPDATE spam_configuration SET punishment = $1 WHERE guild_id = $2",
+                        punishment,
+                        ctx.guild.id.get() as i64
+                    )
+                    .execute(&handler.main_database)
+                    .await?;
+                    Ok(Some(0))
+                }
+                _ => Err(invalid_option()),
+            };
+        };
+
+        interaction
+            .create_response(&ctx.ctx.http, CreateInteractionResponse::Modal(modal))
+            .await?;
+        let Some(modal_interaction) = message
+            .await_modal_interaction(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(std::time::Duration::new(60, 0))
+            .await
+        else {
+            return Err(timeout_error());
+        };
+        modal_interaction
+            .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+            .await?;
+        let values = get_modal_values(&modal_interaction);
+
+        match interaction.data.custom_id.as_str() {
+            "rate_limits" => {
+                let Some(window) = values
+                    .get(1)
+                    .and_then(|value| value.parse::<i32>().ok())
+                    .filter(|value| (1..=300).contains(value))
+                else {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid window",
+                            Some("The window must be between 1 and 300 seconds.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    });
+                };
+                let (Ok(message_limit), Ok(duplicate_limit)) =
+                    (parse_limit(values.first()), parse_limit(values.get(2)))
+                else {
+                    return Err(invalid_limit());
+                };
+                sqlx::query!(
+                    "UPDATE spam_configuration SET message_limit = $1, message_window = $2, duplicate_limit = $3 WHERE guild_id = $4",
+                    message_limit,
+                    window,
+                    duplicate_limit,
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+            }
+            "content_limits" => {
+                let (Ok(mention_limit), Ok(newline_limit), Ok(emoji_limit)) = (
+                    parse_limit(values.first()),
+                    parse_limit(values.get(1)),
+                    parse_limit(values.get(2)),
+                ) else {
+                    return Err(invalid_limit());
+                };
+                sqlx::query!(
+                    "UPDATE spam_configuration SET mention_limit = $1, newline_limit = $2, emoji_limit = $3 WHERE guild_id = $4",
+                    mention_limit,
+                    newline_limit,
+                    emoji_limit,
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+            }
+            _ => {
+                let Some(reason) = values.first().filter(|reason| !reason.is_empty()) else {
+                    return Err(invalid_option());
+                };
+                let mute_duration = values.get(1).filter(|duration| !duration.is_empty());
+                if mute_duration.is_some_and(|duration| {
+                    Duration::new(duration)
+                        .to_timestamp()
+                        .is_none_or(|duration| duration <= time::OffsetDateTime::now_utc())
+                }) {
+                    return Err(ConfigError {
+                        error: ResponseError::Execution(
+                            "Invalid duration",
+                            Some("Please enter a valid mute duration, like `10m`.".to_string()),
+                        ),
+                        stages_to_skip: None,
+                    });
+                }
+                sqlx::query!(
+                    "UPDATE spam_configuration SET reason = $1, mute_duration = $2 WHERE guild_id = $3",
+                    reason,
+                    mute_duration,
+                    ctx.guild.id.get() as i64
+                )
+                .execute(&handler.main_database)
+                .await?;
+            }
+        }
+        Ok(Some(0))
+    }
+}
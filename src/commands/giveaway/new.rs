@@ -7,7 +7,7 @@ use tracing::{debug, error};
 
 use crate::{
     commands::giveaway::interaction::new_giveaway_entry_handler,
-    common::{duration::Duration, options::Options},
+    common::{duration::Duration, options::Options, scheduler::ScheduledTask},
     models::{
         command::{CommandContext, CommandContextReply},
         giveaway::Giveaway,
@@ -128,6 +128,10 @@ pub async fn new(
         error!("Failed to insert giveaway into database: {:?}", err);
         error!("Giveaway {:?} will not persist on restart", giveaway.id);
     };
+    handler.scheduler.schedule(
+        giveaway.duration,
+        ScheduledTask::GiveawayCleanup(giveaway.id),
+    );
 
     tokio::spawn(new_giveaway_entry_handler(
         handler.clone(),
@@ -15,6 +15,7 @@ use crate::{
         logging::{get_log_channel, LogType},
         options::Options,
     },
+    events::appeal::get_appeal_components,
     models::{
         actions::{Action, ActionDatabaseInsert, ActionType},
         command::{Command, CommandContext, CommandContextReply},
@@ -58,7 +59,7 @@ impl Handler {
             start.elapsed()
         );
 
-        let action = Action::new(
+        let mut action = Action::new(
             ActionType::Ban,
             user_id,
             moderator_id,
@@ -87,6 +88,8 @@ impl Handler {
             ),
         ];
 
+        let appeal_components = get_appeal_components(self, guild_id, &action.get_id()).await;
+
         if sqlx::query!("SELECT active FROM global_kills WHERE feature = 'commands.dm'")
             .fetch_one(&self.main_database)
             .await
@@ -106,18 +109,22 @@ impl Handler {
                 if dm_channel
                     .send_message(
                         &ctx.ctx,
-                        CreateMessage::new().embed(
-                            CreateEmbed::new()
-                                .title("Banned!")
-                                .description(match GuildId::new(guild_id as u64).name(&ctx.ctx) {
-                                    Some(guild_name) => {
-                                        format!("You've been banned from {guild_name}")
-                                    }
-                                    None => "A server has banned you".to_string(),
-                                })
-                                .fields(fields.clone())
-                                .color(0xf54029),
-                        ),
+                        CreateMessage::new()
+                            .embed(
+                                CreateEmbed::new()
+                                    .title("Banned!")
+                                    .description(
+                                        match GuildId::new(guild_id as u64).name(&ctx.ctx) {
+                                            Some(guild_name) => {
+                                                format!("You've been banned from {guild_name}")
+                                            }
+                                            None => "A server has banned you".to_string(),
+                                        },
+                                    )
+                                    .fields(fields.clone())
+                                    .color(0xf54029),
+                            )
+                            .components(appeal_components),
                     )
                     .await
                     .is_ok()
@@ -150,20 +157,16 @@ impl Handler {
 
         if let Ok(config) = sqlx::query_as!(
             LoggingConfig,
-            "SELECT log_actions, log_messages, log_voice, log_channel, log_action_channel, log_message_channel, log_voice_channel FROM logging_configuration WHERE guild_id = $1",
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
             guild_id
         )
         .fetch_one(&self.main_database)
         .await {
             if let Some(channel) = get_log_channel(self, &config, &LogType::Action).await {
-                if let Err(err) = ChannelId::new(channel as u64)
-                    .send_message(
-                        &ctx.ctx,
-                        CreateMessage::new()
-                            .embed(CreateEmbed::new().title("User banned").description(format!("<@{}> has been banned", action.user_id)).fields(fields).footer(CreateEmbedFooter::new(format!("User {} banned | UUID: {}", action.user_id, action.get_id()))).color(0xf54029))
-                ).await {
-                    error!("Failed to send ban log message: {}", err);
-                }
+                self.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title("User banned").description(format!("<@{}> has been banned", action.user_id)).fields(fields).footer(CreateEmbedFooter::new(format!("User {} banned | {} | UUID: {}", action.user_id, action.case(), action.get_id()))).color(0xf54029),
+                );
             }
         }
 
@@ -309,7 +312,8 @@ impl Command for BanCommand {
                         true,
                     )
                     .footer(CreateEmbedFooter::new(format!(
-                        "UUID: {} | Total execution time: {:?}",
+                        "{} | UUID: {} | Total execution time: {:?}",
+                        action.action.case(),
                         action.action.get_id(),
                         start.elapsed()
                     )))
@@ -1,6 +1,6 @@
 use serenity::{
     all::{ChannelId, CommandInteraction, CommandOptionType},
-    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateMessage},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter},
 };
 use std::time::Instant;
 use tracing::error;
@@ -10,8 +10,11 @@ use crate::{
         duration::Duration,
         logging::{get_log_channel, LogType},
         options::Options,
+        scheduler::ScheduledTask,
     },
+    database::postgres::actions::{insert_action_revision, resolve_action_id},
     models::{
+        action_history::RevisionField,
         command::{Command, CommandContext, CommandContextReply},
         config::LoggingConfig,
         handler::Handler,
@@ -44,7 +47,7 @@ impl Command for DurationCommand {
                 CreateCommandOption::new(
                     CommandOptionType::String,
                     "uuid",
-                    "The UUID of the action",
+                    "The UUID or case number of the action",
                 )
                 .required(false),
             )
@@ -80,7 +83,7 @@ impl Command for DurationCommand {
         };
 
         let id = match options.get_string("uuid").into_owned() {
-            Some(id) => id,
+            Some(id) => resolve_action_id(handler, cmd.guild_id.unwrap().get() as i64, id).await,
             None => match sqlx::query!(
                 "SELECT id FROM actions WHERE moderator_id = $1 ORDER BY created_at DESC",
                 cmd.user.id.get() as i64
@@ -101,6 +104,39 @@ impl Command for DurationCommand {
 
         let expiry = Duration::new(duration.as_str()).to_timestamp().unwrap();
 
+        let guild_id = cmd.guild_id.unwrap().get() as i64;
+        let (old_expiry, case_number) = match sqlx::query!(
+            "SELECT expiry, case_number FROM actions WHERE id = $1 AND guild_id = $2",
+            id,
+            guild_id
+        )
+        .fetch_optional(&handler.main_database)
+        .await
+        {
+            Ok(Some(record)) => (
+                record
+                    .expiry
+                    .map(|expiry| expiry.assume_utc().unix_timestamp()),
+                record.case_number,
+            ),
+            Ok(None) => {
+                return Err(ResponseError::Execution(
+                    "Action not found",
+                    Some("No action with this ID exists in this server.".to_string()),
+                ))
+            }
+            Err(err) => {
+                error!("Could not fetch action, failed with error: {:?}", err);
+                return Err(ResponseError::Execution(
+                    "Could not update action duration",
+                    Some(
+                        "The action duration could not be updated. Please try again later."
+                            .to_string(),
+                    ),
+                ));
+            }
+        };
+
         if let Err(err) = sqlx::query!(
             "UPDATE actions SET expiry = $1 WHERE id = $2",
             time::PrimitiveDateTime::new(expiry.date(), expiry.time()),
@@ -120,6 +156,19 @@ impl Command for DurationCommand {
                 ),
             ));
         };
+        handler
+            .scheduler
+            .schedule(expiry, ScheduledTask::ActionExpiry(id.clone()));
+        insert_action_revision(
+            handler,
+            guild_id,
+            &id,
+            RevisionField::Expiry,
+            old_expiry.map(|expiry| expiry.to_string()),
+            Some(expiry.unix_timestamp().to_string()),
+            cmd.user.id.get() as i64,
+        )
+        .await;
 
         let reply = ctx.reply(
             cmd,
@@ -127,7 +176,7 @@ impl Command for DurationCommand {
                 CreateEmbed::new()
                     .title("Duration updated")
                     .description(format!(
-                        "The duration of action `{id}` has been updated to <t:{}:F>",
+                        "The duration of case #{case_number} has been updated to <t:{}:F>",
                         expiry.unix_timestamp()
                     ))
                     .color(0x0abfd6)
@@ -138,26 +187,21 @@ impl Command for DurationCommand {
             ),
         );
 
-        let log = if let Ok(config) = sqlx::query_as!(
+        if let Ok(config) = sqlx::query_as!(
             LoggingConfig,
-            "SELECT log_actions, log_messages, log_voice, log_channel, log_action_channel, log_message_channel, log_voice_channel FROM logging_configuration WHERE guild_id = $1",
-            cmd.guild_id.unwrap().get() as i64
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
+            guild_id
         )
         .fetch_one(&handler.main_database)
         .await {
-            get_log_channel(handler, &config, &LogType::Action).await.map(|channel| ChannelId::new(channel as u64)
-                .send_message(
-                    &ctx.ctx,
-                    CreateMessage::new()
-                        .embed(CreateEmbed::new().title("Duration updated").description(format!("The duration of action `{id}` will now expire on <t:{}:F>", expiry.unix_timestamp())).footer(CreateEmbedFooter::new(format!("Duration updated | UUID: {id}"))).color(0x0abfd6))
-                ))
-        } else {
-            None
-        };
-
-        match log {
-            Some(log_future) => tokio::join!(log_future, reply).1,
-            None => reply.await,
+            if let Some(channel) = get_log_channel(handler, &config, &LogType::Action).await {
+                handler.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title("Duration updated").description(format!("The duration of action `{id}` will now expire on <t:{}:F>", expiry.unix_timestamp())).field("Previous expiry", old_expiry.map_or("Never".to_string(), |expiry| format!("<t:{expiry}:F>")), false).field("Moderator", format!("<@{}>", cmd.user.id), true).footer(CreateEmbedFooter::new(format!("Duration updated | Case #{case_number} | UUID: {id}"))).color(0x0abfd6),
+                );
+            }
         }
+
+        reply.await
     }
 }
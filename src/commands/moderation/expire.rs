@@ -1,6 +1,6 @@
 use serenity::{
-    all::{ChannelId, CommandInteraction, CommandOptionType},
-    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateMessage},
+    all::{ChannelId, CommandInteraction, CommandOptionType, Context},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter},
 };
 use std::time::Instant;
 use tracing::error;
@@ -9,8 +9,13 @@ use crate::{
     common::{
         logging::{get_log_channel, LogType},
         options::Options,
+        scheduler::ScheduledTask,
     },
+    database::postgres::actions::{insert_action_revision, resolve_action_id},
+    events::expire::lift_action,
     models::{
+        action_history::RevisionField,
+        actions::DatabaseAction,
         command::{Command, CommandContext, CommandContextReply},
         config::LoggingConfig,
         handler::Handler,
@@ -19,6 +24,87 @@ use crate::{
     },
 };
 
+impl Handler {
+    pub async fn expire_action(
+        &self,
+        ctx: &Context,
+        guild_id: i64,
+        id: &str,
+        changed_by: i64,
+    ) -> Result<i32, ResponseError> {
+        let action = match sqlx::query_as!(
+            DatabaseAction,
+            "SELECT * FROM actions WHERE id = $1 AND guild_id = $2",
+            id,
+            guild_id
+        )
+        .fetch_optional(&self.main_database)
+        .await
+        {
+            Ok(Some(action)) => action,
+            Ok(None) => {
+                return Err(ResponseError::Execution(
+                    "Action not found",
+                    Some("No action with this ID exists in this server.".to_string()),
+                ))
+            }
+            Err(err) => {
+                error!("Could not fetch action, failed with error: {:?}", err);
+                return Err(ResponseError::Execution(
+                    "Could not expire action",
+                    Some("The action could not be expired. Please try again later.".to_string()),
+                ));
+            }
+        };
+
+        if action.active {
+            lift_action(self, ctx, &action).await;
+        }
+
+        if let Err(err) = sqlx::query!("UPDATE actions SET active = false WHERE id = $1", id)
+            .execute(&self.main_database)
+            .await
+        {
+            error!("Could not expire action, failed with error: {:?}", err);
+            return Err(ResponseError::Execution(
+                "Could not expire action",
+                Some("The action could not be expired. Please try again later.".to_string()),
+            ));
+        };
+        self.scheduler
+            .unschedule(&ScheduledTask::ActionExpiry(id.to_string()));
+        if action.active {
+            insert_action_revision(
+                self,
+                guild_id,
+                id,
+                RevisionField::Active,
+                Some(true.to_string()),
+                Some(false.to_string()),
+                changed_by,
+            )
+            .await;
+        }
+
+        if let Ok(config) = sqlx::query_as!(
+            LoggingConfig,
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_one(&self.main_database)
+        .await {
+            if let Some(channel) = get_log_channel(self, &config, &LogType::Action).await {
+                self.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title("Action expired").description(format!("Case #{} has been manually expired", action.case_number)).field("Moderator", format!("<@{changed_by}>"), true).footer(CreateEmbedFooter::new(format!("Action expired | Case #{} | UUID: {id}", action.case_number))).color(0x2e4045),
+                );
+            }
+        }
+
+        Ok(action.case_number)
+    }
+}
+
 pub struct ExpireCommand;
 
 #[async_trait::async_trait]
@@ -35,7 +121,7 @@ impl Command for ExpireCommand {
                 CreateCommandOption::new(
                     CommandOptionType::String,
                     "uuid",
-                    "The UUID of the action",
+                    "The UUID or case number of the action",
                 )
                 .required(true),
             )
@@ -66,54 +152,30 @@ impl Command for ExpireCommand {
                 Some("A UUID must be provided before continuing!".to_string()),
             ));
         };
+        let id = resolve_action_id(handler, ctx.guild.id.get() as i64, id).await;
 
-        if let Err(err) = sqlx::query!("UPDATE actions SET active = false WHERE id = $1", id)
-            .execute(&handler.main_database)
-            .await
-        {
-            error!("Could not expire action, failed with error: {:?}", err);
-            return Err(ResponseError::Execution(
-                "Could not expire action",
-                Some("The action could not be expired. Please try again later.".to_string()),
-            ));
-        };
+        let case_number = handler
+            .expire_action(
+                &ctx.ctx,
+                ctx.guild.id.get() as i64,
+                &id,
+                cmd.user.id.get() as i64,
+            )
+            .await?;
 
-        let reply = ctx.reply(
+        ctx.reply(
             cmd,
             Response::new().embed(
                 CreateEmbed::new()
                     .title("Action expired")
-                    .description(format!(
-                        "The action with ID `{id}` has been manually expired"
-                    ))
+                    .description(format!("Case #{case_number} has been manually expired"))
                     .color(0x2e4045)
                     .footer(CreateEmbedFooter::new(format!(
                         "Total execution time: {:?}",
                         start.elapsed()
                     ))),
             ),
-        );
-
-        let log = if let Ok(config) = sqlx::query_as!(
-            LoggingConfig,
-            "SELECT log_actions, log_messages, log_voice, log_channel, log_action_channel, log_message_channel, log_voice_channel FROM logging_configuration WHERE guild_id = $1",
-            cmd.guild_id.unwrap().get() as i64
         )
-        .fetch_one(&handler.main_database)
-        .await {
-            get_log_channel(handler, &config, &LogType::Action).await.map(|channel| ChannelId::new(channel as u64)
-                .send_message(
-                    &ctx.ctx,
-                    CreateMessage::new()
-                        .embed(CreateEmbed::new().title("Action expired").description(format!("The action with ID `{id}` has been manually expired")).footer(CreateEmbedFooter::new(format!("Action expired | UUID: {id}"))).color(0x2e4045))
-            ))
-        } else {
-            None
-        };
-
-        match log {
-            Some(log_future) => tokio::join!(log_future, reply).1,
-            None => reply.await,
-        }
+        .await
     }
 }
@@ -0,0 +1,289 @@
+use serenity::{
+    all::{CommandInteraction, CommandOptionType},
+    builder::{
+        CreateAttachment, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter,
+    },
+    futures::StreamExt,
+};
+use std::time::Instant;
+use tracing::error;
+
+use crate::{
+    common::options::{invalid_date, Options},
+    models::{
+        actions::{Action, DatabaseAction},
+        command::{Command, CommandContext, CommandContextReply},
+        handler::Handler,
+        permissions::Permission,
+        response::{Response, ResponseError, ResponseResult},
+    },
+};
+
+const EXPORT_COLOR: u32 = 0x0abfd6;
+const MAX_EXPORT_SIZE: usize = 8 * 1024 * 1024;
+const CSV_HEADER: &str =
+    "case,id,type,user_id,moderator_id,reason,active,points,created_at,expiry,source\n";
+
+fn format_timestamp(timestamp: time::OffsetDateTime) -> String {
+    format!(
+        "{}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        timestamp.year(),
+        u8::from(timestamp.month()),
+        timestamp.day(),
+        timestamp.hour(),
+        timestamp.minute(),
+        timestamp.second()
+    )
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_row(action: &Action) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{}\n",
+        action.case_number,
+        action.get_id(),
+        csv_field(&action.type_name()),
+        action.user_id,
+        action.moderator_id,
+        csv_field(&action.reason),
+        action.active,
+        action
+            .points
+            .map(|points| points.to_string())
+            .unwrap_or_default(),
+        format_timestamp(action.created_at),
+        action.expiry.map(format_timestamp).unwrap_or_default(),
+        csv_field(action.import_source.as_deref().unwrap_or_default())
+    )
+}
+
+fn json_row(action: &Action) -> String {
+    serde_json::json!({
+        "case": action.case_number,
+        "id": action.get_id(),
+        "type": action.type_name(),
+        "user_id": action.user_id.to_string(),
+        "moderator_id": action.moderator_id.to_string(),
+        "reason": action.reason,
+        "active": action.active,
+        "points": action.points,
+        "created_at": format_timestamp(action.created_at),
+        "expiry": action.expiry.map(format_timestamp),
+        "source": action.import_source,
+    })
+    .to_string()
+}
+
+pub struct ExportCommand;
+
+#[async_trait::async_trait]
+impl Command for ExportCommand {
+    fn name(&self) -> &'static str {
+        "export"
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new("export")
+            .dm_permission(false)
+            .description("Export moderation data")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "actions",
+                    "Export this server's moderation actions",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "format",
+                        "The format of the export",
+                    )
+                    .add_string_choice("CSV", "csv")
+                    .add_string_choice("JSON", "json")
+                    .required(true),
+                )
+                .add_sub_option(CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "user",
+                    "Only export actions against this user",
+                ))
+                .add_sub_option(CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "moderator",
+                    "Only export actions issued by this moderator",
+                ))
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "type",
+                        "Only export actions of this type",
+                    )
+                    .add_string_choice("Strike", "strike")
+                    .add_string_choice("Mute", "mute")
+                    .add_string_choice("Kick", "kick")
+                    .add_string_choice("Ban", "ban")
+                    .add_string_choice("Custom", "custom"),
+                )
+                .add_sub_option(CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "active",
+                    "Only export active or only export expired actions",
+                ))
+                .add_sub_option(CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "after",
+                    "Only export actions issued on or after this date (YYYY-MM-DD)",
+                ))
+                .add_sub_option(CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "before",
+                    "Only export actions issued before this date (YYYY-MM-DD)",
+                )),
+            )
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        let start = Instant::now();
+
+        if !ctx.user_permissions.contains(&Permission::ModerationExport) {
+            return Err(ResponseError::Execution(
+                "You do not have permission to do this!",
+                Some(format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationExport)),
+            ));
+        }
+
+        let options = Options {
+            options: cmd.data.options(),
+        };
+
+        let json = options.get_string("format").as_deref() == Some("json");
+        let user = options
+            .get_user("user")
+            .as_ref()
+            .as_ref()
+            .map(|user| user.id.get() as i64);
+        let moderator = options
+            .get_user("moderator")
+            .as_ref()
+            .as_ref()
+            .map(|moderator| moderator.id.get() as i64);
+        let action_type = options.get_string("type").into_owned();
+        let active = options.get_boolean("active");
+        let after = options
+            .get_date("after")
+            .map_err(|value| invalid_date(&value))?;
+        let before = options
+            .get_date("before")
+            .map_err(|value| invalid_date(&value))?;
+
+        ctx.reply(
+            cmd,
+            Response::new()
+                .embed(
+                    CreateEmbed::new()
+                        .title("Exporting actions")
+                        .description("Your export is being prepared, this may take a moment")
+                        .color(EXPORT_COLOR),
+                )
+                .ephemeral(true),
+        )
+        .await?;
+
+        let mut actions = sqlx::query_as!(
+            DatabaseAction,
+            "SELECT * FROM actions WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR user_id = $2) AND ($3::BIGINT IS NULL OR moderator_id = $3) AND ($4::VARCHAR IS NULL OR action_type = $4) AND ($5::BOOLEAN IS NULL OR active = $5) AND ($6::TIMESTAMP IS NULL OR created_at >= $6) AND ($7::TIMESTAMP IS NULL OR created_at < $7) ORDER BY case_number",
+            ctx.guild.id.get() as i64,
+            user,
+            moderator,
+            action_type,
+            active,
+            after,
+            before
+        )
+        .fetch(&handler.main_database);
+
+        let mut export = if json {
+            String::from("[")
+        } else {
+            String::from(CSV_HEADER)
+        };
+        let mut count = 0;
+        let mut truncated = false;
+        while let Some(action) = actions.next().await {
+            let action = match action {
+                Ok(action) => Action::from(action),
+                Err(err) => {
+                    error!("Could not export actions, failed with error: {:?}", err);
+                    return Err(ResponseError::Execution(
+                        "Could not export actions",
+                        Some(
+                            "The export could not be created. Please try again later.".to_string(),
+                        ),
+                    ));
+                }
+            };
+            let row = if json {
+                format!(
+                    "{}\n  {}",
+                    if count == 0 { "" } else { "," },
+                    json_row(&action)
+                )
+            } else {
+                csv_row(&action)
+            };
+            if export.len() + row.len() > MAX_EXPORT_SIZE {
+                truncated = true;
+                break;
+            }
+            export.push_str(&row);
+            count += 1;
+        }
+        drop(actions);
+        if json {
+            export.push_str("\n]\n");
+        }
+
+        let mut embed = CreateEmbed::new()
+            .title("Export complete")
+            .description(format!("Exported **{count}** actions"))
+            .color(EXPORT_COLOR)
+            .footer(CreateEmbedFooter::new(format!(
+                "Total execution time: {:?}",
+                start.elapsed()
+            )));
+        if truncated {
+            embed = embed.field(
+                "Truncated",
+                "The export was too large to attach in full. Use filters to export the remaining actions.",
+                false,
+            );
+        }
+
+        ctx.reply(
+            cmd,
+            Response::new()
+                .embed(embed)
+                .attachment(CreateAttachment::bytes(
+                    export.into_bytes(),
+                    format!(
+                        "actions-{}.{}",
+                        ctx.guild.id,
+                        if json { "json" } else { "csv" }
+                    ),
+                )),
+        )
+        .await
+    }
+}
@@ -0,0 +1,469 @@
+use serenity::{
+    all::{CommandInteraction, CommandOptionType},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter},
+};
+use std::{fmt::Write, time::Instant};
+use tracing::error;
+
+use crate::{
+    common::{duration::Duration, options::Options},
+    database::postgres::filters::{get_compiled_filter_rules, get_filter_rules},
+    models::{
+        command::{Command, CommandContext, CommandContextReply},
+        filter::{CompiledFilterRule, FilterAction, FilterPatternType, FilterRule},
+        handler::Handler,
+        permissions::Permission,
+        response::{Response, ResponseError, ResponseResult},
+    },
+};
+
+const FILTER_COLOR: u32 = 0xe67e22;
+const MAX_RULES: usize = 50;
+
+fn name_option(description: &str) -> CreateCommandOption {
+    CreateCommandOption::new(CommandOptionType::String, "name", description)
+        .max_length(32)
+        .required(true)
+}
+
+fn describe_rule(rule: &FilterRule) -> String {
+    let mut description = format!(
+        "`{}` ({})\n**Action:** {}",
+        rule.pattern.replace('`', "'"),
+        rule.pattern_type,
+        rule.action
+    );
+    if let Some(duration) = &rule.duration {
+        write!(&mut description, " for {duration}").unwrap();
+    }
+    if !rule.exempt_roles.is_empty() || !rule.exempt_channels.is_empty() {
+        let exemptions = rule
+            .exempt_roles
+            .iter()
+            .map(|role| format!("<@&{role}>"))
+            .chain(
+                rule.exempt_channels
+                    .iter()
+                    .map(|channel| format!("<#{channel}>")),
+            )
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(&mut description, "\n**Exempt:** {exemptions}").unwrap();
+    }
+    description
+}
+
+pub struct FilterCommand;
+
+#[async_trait::async_trait]
+impl Command for FilterCommand {
+    fn name(&self) -> &'static str {
+        "filter"
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new("filter")
+            .dm_permission(false)
+            .description("Manage the word and pattern filter")
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "add", "Add a filter rule")
+                    .add_sub_option(name_option("The name of the rule"))
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::String,
+                            "pattern",
+                            "The word, glob or regex to match",
+                        )
+                        .max_length(500)
+                        .required(true),
+                    )
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::String,
+                            "type",
+                            "How the pattern is matched",
+                        )
+                        .add_string_choice("Literal", "literal")
+                        .add_string_choice("Glob", "glob")
+                        .add_string_choice("Regex", "regex")
+                        .required(true),
+                    )
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::String,
+                            "action",
+                            "What happens to messages that match",
+                        )
+                        .add_string_choice("Delete", "delete")
+                        .add_string_choice("Strike", "strike")
+                        .add_string_choice("Mute", "mute")
+                        .add_string_choice("Kick", "kick")
+                        .add_string_choice("Ban", "ban")
+                        .required(true),
+                    )
+                    .add_sub_option(CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "duration",
+                        "The duration of the strike, mute or ban",
+                    )),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "remove",
+                    "Remove a filter rule",
+                )
+                .add_sub_option(name_option("The name of the rule")),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "list",
+                "List the filter rules",
+            ))
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "exempt",
+                    "Toggle an exemption from a filter rule",
+                )
+                .add_sub_option(name_option("The name of the rule"))
+                .add_sub_option(CreateCommandOption::new(
+                    CommandOptionType::Role,
+                    "role",
+                    "The role to toggle",
+                ))
+                .add_sub_option(CreateCommandOption::new(
+                    CommandOptionType::Channel,
+                    "channel",
+                    "The channel to toggle",
+                )),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "test",
+                    "Test which filter rules match a message",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::String, "text", "The text to test")
+                        .max_length(2000)
+                        .required(true),
+                ),
+            )
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        let start = Instant::now();
+
+        if !ctx.user_permissions.contains(&Permission::ModerationFilter) {
+            return Err(ResponseError::Execution(
+                "You do not have permission to do this!",
+                Some(format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationFilter)),
+            ));
+        }
+
+        let options = Options {
+            options: cmd.data.options(),
+        };
+        let guild_id = cmd.guild_id.unwrap().get() as i64;
+
+        let Some(subcommand) = cmd.data.options.first() else {
+            return Err(ResponseError::Execution(
+                "Invalid command",
+                Some("You must specify a subcommand to use this command!".to_string()),
+            ));
+        };
+
+        let name = options.get_string("name").into_owned();
+        let embed = match subcommand.name.as_str() {
+            "add" => {
+                let (Some(name), Some(pattern), Some(pattern_type), Some(action)) = (
+                    name,
+                    options.get_string("pattern").into_owned(),
+                    options.get_string("type").into_owned(),
+                    options.get_string("action").into_owned(),
+                ) else {
+                    return Err(ResponseError::Execution(
+                        "Missing options!",
+                        Some(
+                            "A name, pattern, type and action must be provided before continuing!"
+                                .to_string(),
+                        ),
+                    ));
+                };
+                let pattern_type = FilterPatternType::from(pattern_type);
+                let action = FilterAction::from(action);
+
+                if let Err(err) = FilterRule::compile(&pattern, pattern_type) {
+                    return Err(ResponseError::Execution(
+                        "Invalid pattern!",
+                        Some(format!("The pattern could not be compiled: ```{err}```")),
+                    ));
+                }
+
+                let duration = options.get_string("duration").into_owned();
+                if let Some(duration) = &duration {
+                    if action == FilterAction::Delete || action == FilterAction::Kick {
+                        return Err(ResponseError::Execution(
+                            "Invalid duration!",
+                            Some(format!(
+                                "A duration cannot be used with the {action} action."
+                            )),
+                        ));
+                    }
+                    if Duration::new(duration)
+                        .to_timestamp()
+                        .is_none_or(|expiry| expiry <= time::OffsetDateTime::now_utc())
+                    {
+                        return Err(ResponseError::Execution(
+                            "Invalid duration!",
+                            Some("Please enter a valid duration, like `10m`.".to_string()),
+                        ));
+                    }
+                } else if action == FilterAction::Mute {
+                    return Err(ResponseError::Execution(
+                        "No duration provided!",
+                        Some("A duration must be provided for mute rules.".to_string()),
+                    ));
+                }
+
+                if get_filter_rules(handler, guild_id).await.len() >= MAX_RULES {
+                    return Err(ResponseError::Execution(
+                        "Too many rules!",
+                        Some(format!(
+                            "A server can have at most {MAX_RULES} filter rules."
+                        )),
+                    ));
+                }
+
+                match sqlx::query!(
+                    "INSERT INTO filter_rules (guild_id, name, pattern, pattern_type, action, duration) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (guild_id, name) DO NOTHING",
+                    guild_id,
+                    name,
+                    pattern,
+                    pattern_type.to_string(),
+                    action.to_string(),
+                    duration
+                )
+                .execute(&handler.main_database)
+                .await
+                {
+                    Ok(result) if result.rows_affected() == 0 => {
+                        return Err(ResponseError::Execution(
+                            "Rule already exists!",
+                            Some(format!("A filter rule named `{name}` already exists.")),
+                        ));
+                    }
+                    Ok(_) => handler.filter_cache.invalidate(guild_id),
+                    Err(err) => {
+                        error!("Could not add filter rule, failed with error: {:?}", err);
+                        return Err(ResponseError::Execution(
+                            "Could not add rule",
+                            Some("The rule could not be added. Please try again later.".to_string()),
+                        ));
+                    }
+                }
+
+                CreateEmbed::new()
+                    .title("Filter rule added")
+                    .description(format!("The filter rule `{name}` has been added"))
+            }
+            "remove" => {
+                let Some(name) = name else {
+                    return Err(ResponseError::Execution(
+                        "No name provided!",
+                        Some(
+                            "The name of the rule must be provided before continuing!".to_string(),
+                        ),
+                    ));
+                };
+
+                match sqlx::query!(
+                    "DELETE FROM filter_rules WHERE guild_id = $1 AND name = $2",
+                    guild_id,
+                    name
+                )
+                .execute(&handler.main_database)
+                .await
+                {
+                    Ok(result) if result.rows_affected() == 0 => {
+                        return Err(ResponseError::Execution(
+                            "Rule not found!",
+                            Some(format!(
+                                "No filter rule named `{name}` exists in this server."
+                            )),
+                        ));
+                    }
+                    Ok(_) => handler.filter_cache.invalidate(guild_id),
+                    Err(err) => {
+                        error!("Could not remove filter rule, failed with error: {:?}", err);
+                        return Err(ResponseError::Execution(
+                            "Could not remove rule",
+                            Some(
+                                "The rule could not be removed. Please try again later."
+                                    .to_string(),
+                            ),
+                        ));
+                    }
+                }
+
+                CreateEmbed::new()
+                    .title("Filter rule removed")
+                    .description(format!("The filter rule `{name}` has been removed"))
+            }
+            "list" => {
+                let rules = get_filter_rules(handler, guild_id).await;
+                let mut embed = CreateEmbed::new().title("Filter rules");
+                if rules.is_empty() {
+                    embed = embed.description("No filter rules have been added");
+                }
+                let mut length = 0;
+                for rule in rules.iter().take(25) {
+                    let value = describe_rule(rule);
+                    length += value.len() + rule.name.len();
+                    if length > 5000 {
+                        embed = embed.description(format!(
+                            "Some of the {} rules could not be shown",
+                            rules.len()
+                        ));
+                        break;
+                    }
+                    embed = embed.field(rule.name.clone(), value, false);
+                }
+                embed
+            }
+            "exempt" => {
+                let Some(name) = name else {
+                    return Err(ResponseError::Execution(
+                        "No name provided!",
+                        Some(
+                            "The name of the rule must be provided before continuing!".to_string(),
+                        ),
+                    ));
+                };
+                let role = options.get_role("role").into_owned();
+                let channel = options.get_channel("channel").into_owned();
+                if role.is_none() && channel.is_none() {
+                    return Err(ResponseError::Execution(
+                        "Nothing to exempt!",
+                        Some("A role or channel must be provided before continuing!".to_string()),
+                    ));
+                }
+
+                let Some(filter_rule) = get_filter_rules(handler, guild_id)
+                    .await
+                    .into_iter()
+                    .find(|rule| rule.name == name)
+                else {
+                    return Err(ResponseError::Execution(
+                        "Rule not found!",
+                        Some(format!(
+                            "No filter rule named `{name}` exists in this server."
+                        )),
+                    ));
+                };
+
+                let mut exempt_roles = filter_rule.exempt_roles;
+                let mut exempt_channels = filter_rule.exempt_channels;
+                let mut changes = vec![];
+                if let Some(role) = role {
+                    let id = role.id.get() as i64;
+                    if let Some(index) = exempt_roles.iter().position(|role| *role == id) {
+                        exempt_roles.remove(index);
+                        changes.push(format!("<@&{id}> is no longer exempt"));
+                    } else {
+                        exempt_roles.push(id);
+                        changes.push(format!("<@&{id}> is now exempt"));
+                    }
+                }
+                if let Some(channel) = channel {
+                    let id = channel.id.get() as i64;
+                    if let Some(index) = exempt_channels.iter().position(|channel| *channel == id) {
+                        exempt_channels.remove(index);
+                        changes.push(format!("<#{id}> is no longer exempt"));
+                    } else {
+                        exempt_channels.push(id);
+                        changes.push(format!("<#{id}> is now exempt"));
+                    }
+                }
+
+                if let Err(err) = sqlx::query!(
+                    "UPDATE filter_rules SET exempt_roles = $1, exempt_channels = $2 WHERE id = $3",
+                    &exempt_roles,
+                    &exempt_channels,
+                    filter_rule.id
+                )
+                .execute(&handler.main_database)
+                .await
+                {
+                    error!("Could not update filter rule, failed with error: {:?}", err);
+                    return Err(ResponseError::Execution(
+                        "Could not update rule",
+                        Some("The rule could not be updated. Please try again later.".to_string()),
+                    ));
+                }
+                handler.filter_cache.invalidate(guild_id);
+
+                CreateEmbed::new()
+                    .title("Filter rule updated")
+                    .description(format!("`{name}`\n{}", changes.join("\n")))
+            }
+            "test" => {
+                let Some(text) = options.get_string("text").into_owned() else {
+                    return Err(ResponseError::Execution(
+                        "No text provided!",
+                        Some("The text to test must be provided before continuing!".to_string()),
+                    ));
+                };
+
+                let matches = get_compiled_filter_rules(handler, guild_id)
+                    .await
+                    .iter()
+                    .filter_map(|CompiledFilterRule { rule, regex }| {
+                        let found = regex.find(&text)?;
+                        Some(format!(
+                            "`{}` matched `{}` ({})",
+                            rule.name,
+                            found.as_str().replace('`', "'"),
+                            rule.action
+                        ))
+                    })
+                    .collect::<Vec<_>>();
+
+                CreateEmbed::new()
+                    .title("Filter test")
+                    .description(if matches.is_empty() {
+                        "No filter rules matched".to_string()
+                    } else {
+                        matches.join("\n")
+                    })
+            }
+            _ => {
+                return Err(ResponseError::Execution(
+                    "Invalid command",
+                    Some("You must specify a subcommand to use this command!".to_string()),
+                ))
+            }
+        };
+
+        ctx.reply(
+            cmd,
+            Response::new()
+                .embed(
+                    embed
+                        .color(FILTER_COLOR)
+                        .footer(CreateEmbedFooter::new(format!(
+                            "Total execution time: {:?}",
+                            start.elapsed()
+                        ))),
+                )
+                .ephemeral(true),
+        )
+        .await
+    }
+}
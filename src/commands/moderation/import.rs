@@ -0,0 +1,535 @@
+use std::{collections::HashMap, fmt::Write, time::Instant};
+
+use serenity::{
+    all::{ButtonStyle, CommandInteraction, CommandOptionType},
+    builder::{
+        CreateActionRow, CreateButton, CreateCommand, CreateCommandOption, CreateEmbed,
+        CreateEmbedFooter, CreateInteractionResponse,
+    },
+};
+use tracing::error;
+
+use crate::{
+    common::options::Options,
+    database::postgres::actions::get_custom_actions,
+    models::{
+        command::{Command, CommandContext, CommandContextReply},
+        handler::Handler,
+        import::{parse_file, ImportFormat, MAX_IMPORT_ROWS},
+        permissions::Permission,
+        response::{Response, ResponseError, ResponseResult},
+    },
+};
+
+const IMPORT_COLOR: u32 = 0x0abfd6;
+const MAX_IMPORT_SIZE: u32 = 8 * 1024 * 1024;
+const PROGRESS_INTERVAL: usize = 250;
+
+async fn confirm(
+    ctx: &CommandContext,
+    cmd: &CommandInteraction,
+    embed: CreateEmbed,
+    label: String,
+    enabled: bool,
+) -> Result<bool, ResponseError> {
+    let message = ctx
+        .reply_get_message(
+            cmd,
+            Response::new()
+                .embed(embed)
+                .components(vec![CreateActionRow::Buttons(vec![
+                    CreateButton::new("confirm")
+                        .label(label)
+                        .style(ButtonStyle::Danger)
+                        .disabled(!enabled),
+                    CreateButton::new("cancel")
+                        .label("Cancel")
+                        .style(ButtonStyle::Secondary),
+                ])])
+                .ephemeral(true),
+        )
+        .await?;
+
+    let Some(interaction) = message
+        .await_component_interaction(&ctx.ctx)
+        .author_id(cmd.user.id)
+        .timeout(std::time::Duration::new(120, 0))
+        .await
+    else {
+        return Ok(false);
+    };
+    interaction
+        .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+        .await
+        .map_err(ResponseError::Serenity)?;
+    Ok(interaction.data.custom_id == "confirm")
+}
+
+async fn cancelled(ctx: &CommandContext, cmd: &CommandInteraction, title: &str) -> ResponseResult {
+    ctx.reply(
+        cmd,
+        Response::new()
+            .embed(
+                CreateEmbed::new()
+                    .title(title)
+                    .description("No actions were changed")
+                    .color(IMPORT_COLOR),
+            )
+            .components(vec![]),
+    )
+    .await
+}
+
+async fn import_file(
+    handler: &Handler,
+    ctx: &CommandContext,
+    cmd: &CommandInteraction,
+    options: &Options<'_>,
+) -> ResponseResult {
+    let start = Instant::now();
+    let guild_id = ctx.guild.id.get() as i64;
+
+    let Some(attachment) = options.get_attachment("file").into_owned() else {
+        return Err(ResponseError::Execution(
+            "No file provided!",
+            Some("Please attach a CSV or JSON file to import.".to_string()),
+        ));
+    };
+    if attachment.size > MAX_IMPORT_SIZE {
+        return Err(ResponseError::Execution(
+            "File too large!",
+            Some(
+                "Imports are limited to 8 MB, please split the file into smaller parts."
+                    .to_string(),
+            ),
+        ));
+    }
+    let format = ImportFormat::from(
+        options
+            .get_string("format")
+            .into_owned()
+            .unwrap_or("reaper".to_string()),
+    );
+    let source = match options.get_string("source").into_owned() {
+        Some(source) => source.trim().to_string(),
+        None => format!(
+            "{format}-{}",
+            time::OffsetDateTime::now_utc().unix_timestamp()
+        ),
+    };
+    if source.is_empty() || source.len() > 32 {
+        return Err(ResponseError::Execution(
+            "Invalid source tag!",
+            Some("The source tag must be between 1 and 32 characters long.".to_string()),
+        ));
+    }
+    if sqlx::query!(
+        r#"SELECT (EXISTS (SELECT 1 FROM actions WHERE guild_id = $1 AND import_source = $2) OR EXISTS (SELECT 1 FROM removed_actions WHERE guild_id = $1 AND import_source = $2)) AS "exists!""#,
+        guild_id,
+        source
+    )
+    .fetch_one(&handler.main_database)
+    .await
+    .is_ok_and(|record| record.exists)
+    {
+        return Err(ResponseError::Execution(
+            "Source tag already in use!",
+            Some(format!("Actions have already been imported with the source tag `{source}`. Please choose a different tag or roll back the previous import first.")),
+        ));
+    }
+
+    let content = attachment
+        .download()
+        .await
+        .map_err(ResponseError::Serenity)?;
+    let Ok(content) = String::from_utf8(content) else {
+        return Err(ResponseError::Execution(
+            "Invalid file!",
+            Some("The file must be UTF-8 encoded CSV or JSON.".to_string()),
+        ));
+    };
+    let json = attachment.filename.to_lowercase().ends_with(".json")
+        || content.trim_start().starts_with(['[', '{']);
+    let rows = parse_file(&content, json)
+        .map_err(|err| ResponseError::Execution("Invalid file!", Some(err)))?;
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(ResponseError::Execution(
+            "Too many actions!",
+            Some(format!(
+                "Imports are limited to {MAX_IMPORT_ROWS} actions, please split the file into smaller parts."
+            )),
+        ));
+    }
+
+    let custom_actions = get_custom_actions(handler, guild_id)
+        .await
+        .into_iter()
+        .map(|custom_action| custom_action.name)
+        .collect::<Vec<_>>();
+    let mut actions = vec![];
+    let mut skipped = 0;
+    let mut errors = vec![];
+    for (index, row) in rows.iter().enumerate() {
+        match format.parse_row(row, &custom_actions) {
+            Ok(Some(action)) => actions.push(action),
+            Ok(None) => skipped += 1,
+            Err(err) => errors.push(format!("Entry {}: {err}", index + 1)),
+        }
+    }
+    actions.sort_by_key(|action| action.created_at);
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for action in &actions {
+        *counts
+            .entry(
+                action
+                    .custom_action
+                    .clone()
+                    .unwrap_or(action.typ.to_string()),
+            )
+            .or_default() += 1;
+    }
+    let mut counts = counts.into_iter().collect::<Vec<_>>();
+    counts.sort();
+    let mut breakdown = String::new();
+    for (typ, count) in counts {
+        writeln!(breakdown, "{typ}: **{count}**").unwrap();
+    }
+
+    let mut preview = CreateEmbed::new()
+        .title("Import preview")
+        .description(format!(
+            "**{}** actions will be imported with the source tag `{source}`. Nothing has been imported yet.",
+            actions.len()
+        ))
+        .color(IMPORT_COLOR);
+    if !breakdown.is_empty() {
+        preview = preview.field("Actions", breakdown, true);
+    }
+    if let (Some(first), Some(last)) = (actions.first(), actions.last()) {
+        preview = preview.field(
+            "Date range",
+            format!(
+                "<t:{}:d> - <t:{}:d>",
+                first.created_at.unix_timestamp(),
+                last.created_at.unix_timestamp()
+            ),
+            true,
+        );
+    }
+    if skipped > 0 {
+        preview = preview.field(
+            "Skipped",
+            format!("**{skipped}** entries do not map to a Reaper action and will be skipped"),
+            false,
+        );
+    }
+    if !errors.is_empty() {
+        let mut invalid = format!(
+            "**{}** entries are invalid and will not be imported",
+            errors.len()
+        );
+        for err in errors.iter().take(10) {
+            write!(invalid, "\n{err}").unwrap();
+        }
+        if errors.len() > 10 {
+            write!(invalid, "\n...and {} more", errors.len() - 10).unwrap();
+        }
+        preview = preview.field(
+            "Invalid",
+            invalid.chars().take(1024).collect::<String>(),
+            false,
+        );
+    }
+
+    if !confirm(
+        ctx,
+        cmd,
+        preview,
+        format!("Import {} actions", actions.len()),
+        !actions.is_empty(),
+    )
+    .await?
+    {
+        return cancelled(ctx, cmd, "Import cancelled").await;
+    }
+
+    let total = actions.len();
+    let mut imported = 0;
+    let mut failed = 0;
+    for (index, action) in actions.into_iter().enumerate() {
+        if action
+            .into_action(guild_id, &source)
+            .insert(handler)
+            .await
+            .is_ok()
+        {
+            imported += 1;
+        } else {
+            failed += 1;
+        }
+        if (index + 1) % PROGRESS_INTERVAL == 0 && index + 1 < total {
+            ctx.reply(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title("Import in progress")
+                            .description(format!("Imported {}/{total} actions", index + 1))
+                            .color(IMPORT_COLOR),
+                    )
+                    .components(vec![]),
+            )
+            .await?;
+        }
+    }
+
+    ctx.reply(
+        cmd,
+        Response::new()
+            .embed(
+                CreateEmbed::new()
+                    .title("Import complete")
+                    .description(format!(
+                        "**{imported}** actions were imported and **{failed}** failed. Use `/import rollback source:{source}` to undo this import."
+                    ))
+                    .color(IMPORT_COLOR)
+                    .footer(CreateEmbedFooter::new(format!(
+                        "Total execution time: {:?}",
+                        start.elapsed()
+                    ))),
+            )
+            .components(vec![]),
+    )
+    .await
+}
+
+async fn rollback(
+    handler: &Handler,
+    ctx: &CommandContext,
+    cmd: &CommandInteraction,
+    options: &Options<'_>,
+) -> ResponseResult {
+    let guild_id = ctx.guild.id.get() as i64;
+    let Some(source) = options.get_string("source").into_owned() else {
+        return Err(ResponseError::Execution(
+            "No source tag provided!",
+            Some("Please provide the source tag of the import to roll back.".to_string()),
+        ));
+    };
+
+    let count = match sqlx::query!(
+        r#"SELECT COUNT(*) AS "count!" FROM actions WHERE guild_id = $1 AND import_source = $2"#,
+        guild_id,
+        source
+    )
+    .fetch_one(&handler.main_database)
+    .await
+    {
+        Ok(record) => record.count,
+        Err(err) => {
+            error!(
+                "Could not count imported actions, failed with error: {:?}",
+                err
+            );
+            return Err(ResponseError::Execution(
+                "Could not roll back import",
+                Some("The import could not be rolled back. Please try again later.".to_string()),
+            ));
+        }
+    };
+    if count == 0 {
+        return Err(ResponseError::Execution(
+            "Import not found",
+            Some(format!(
+                "No actions were imported into this server with the source tag `{source}`."
+            )),
+        ));
+    }
+
+    if !confirm(
+        ctx,
+        cmd,
+        CreateEmbed::new()
+            .title("Confirm rollback")
+            .description(format!(
+                "You are about to permanently delete **{count}** actions imported with the source tag `{source}`. This cannot be undone."
+            ))
+            .color(IMPORT_COLOR),
+        "Roll back".to_string(),
+        true,
+    )
+    .await?
+    {
+        return cancelled(ctx, cmd, "Rollback cancelled").await;
+    }
+
+    let deleted = match sqlx::query!(
+        "WITH deleted AS (DELETE FROM actions WHERE guild_id = $1 AND import_source = $2 RETURNING id), removed AS (DELETE FROM removed_actions WHERE guild_id = $1 AND import_source = $2 RETURNING id), history AS (DELETE FROM action_history WHERE action_id IN (SELECT id FROM deleted UNION ALL SELECT id FROM removed)), appeals AS (DELETE FROM appeals WHERE action_id IN (SELECT id FROM deleted UNION ALL SELECT id FROM removed)) SELECT COUNT(*) AS count FROM deleted",
+        guild_id,
+        source
+    )
+    .fetch_one(&handler.main_database)
+    .await
+    {
+        Ok(record) => record.count.unwrap_or_default(),
+        Err(err) => {
+            error!("Could not roll back import, failed with error: {:?}", err);
+            return Err(ResponseError::Execution(
+                "Could not roll back import",
+                Some("The import could not be rolled back. Please try again later.".to_string()),
+            ));
+        }
+    };
+
+    ctx.reply(
+        cmd,
+        Response::new()
+            .embed(
+                CreateEmbed::new()
+                    .title("Import rolled back")
+                    .description(format!(
+                        "**{deleted}** actions imported with the source tag `{source}` were deleted"
+                    ))
+                    .color(IMPORT_COLOR),
+            )
+            .components(vec![]),
+    )
+    .await
+}
+
+async fn list(handler: &Handler, ctx: &CommandContext, cmd: &CommandInteraction) -> ResponseResult {
+    let sources = match sqlx::query!(
+        r#"SELECT import_source AS "import_source!", COUNT(*) AS "count!", MIN(created_at) AS "oldest!", MAX(created_at) AS "newest!" FROM actions WHERE guild_id = $1 AND import_source IS NOT NULL GROUP BY import_source ORDER BY import_source"#,
+        ctx.guild.id.get() as i64
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(sources) => sources,
+        Err(err) => {
+            error!("Could not list imports, failed with error: {:?}", err);
+            return Err(ResponseError::Execution(
+                "Could not list imports",
+                Some("The imports could not be listed. Please try again later.".to_string()),
+            ));
+        }
+    };
+
+    let mut embed = CreateEmbed::new().title("Imports").color(IMPORT_COLOR);
+    if sources.is_empty() {
+        embed = embed.description("No actions have been imported into this server");
+    }
+    for source in sources.into_iter().take(25) {
+        embed = embed.field(
+            format!("`{}`", source.import_source),
+            format!(
+                "**{}** actions from <t:{}:d> to <t:{}:d>",
+                source.count,
+                source.oldest.assume_utc().unix_timestamp(),
+                source.newest.assume_utc().unix_timestamp()
+            ),
+            false,
+        );
+    }
+
+    ctx.reply(cmd, Response::new().embed(embed).ephemeral(true))
+        .await
+}
+
+pub struct ImportCommand;
+
+#[async_trait::async_trait]
+impl Command for ImportCommand {
+    fn name(&self) -> &'static str {
+        "import"
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new("import")
+            .dm_permission(false)
+            .description("Import moderation history from other bots")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "file",
+                    "Import actions from a CSV or JSON file",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Attachment,
+                        "file",
+                        "The CSV or JSON file to import",
+                    )
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "format",
+                        "The format of the file",
+                    )
+                    .add_string_choice("Reaper export", "reaper")
+                    .add_string_choice("Zeppelin", "zeppelin")
+                    .add_string_choice("Other bots", "generic")
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "source",
+                        "A tag to mark the imported actions with, used to roll the import back",
+                    )
+                    .max_length(32),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "rollback",
+                    "Delete all actions from a previous import",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "source",
+                        "The source tag of the import",
+                    )
+                    .required(true),
+                ),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "list",
+                "List previous imports",
+            ))
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        if !ctx.user_permissions.contains(&Permission::ModerationImport) {
+            return Err(ResponseError::Execution(
+                "You do not have permission to do this!",
+                Some(format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationImport)),
+            ));
+        }
+
+        let options = Options {
+            options: cmd.data.options(),
+        };
+
+        match cmd.data.options.first().map(|option| option.name.as_str()) {
+            Some("file") => import_file(handler, ctx, cmd, &options).await,
+            Some("rollback") => rollback(handler, ctx, cmd, &options).await,
+            Some("list") => list(handler, ctx, cmd).await,
+            _ => Err(ResponseError::Execution(
+                "Invalid command",
+                Some("You must specify a subcommand to use this command!".to_string()),
+            )),
+        }
+    }
+}
@@ -45,7 +45,7 @@ impl Handler {
             start.elapsed()
         );
 
-        let action = Action::new(
+        let mut action = Action::new(
             ActionType::Kick,
             user_id,
             moderator_id,
@@ -129,20 +129,16 @@ impl Handler {
 
         if let Ok(config) = sqlx::query_as!(
             LoggingConfig,
-            "SELECT log_actions, log_messages, log_voice, log_channel, log_action_channel, log_message_channel, log_voice_channel FROM logging_configuration WHERE guild_id = $1",
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
             guild_id
         )
         .fetch_one(&self.main_database)
         .await {
             if let Some(channel) = get_log_channel(self, &config, &LogType::Action).await {
-                if let Err(err) = ChannelId::new(channel as u64)
-                    .send_message(
-                        &ctx.ctx,
-                        CreateMessage::new()
-                            .embed(CreateEmbed::new().title("User kicked").description(format!("<@{}> has been kicked", action.user_id)).fields(fields).footer(CreateEmbedFooter::new(format!("User {} kicked | UUID: {}", action.user_id, action.get_id()))).color(0x000080))
-                ).await {
-                    error!("Failed to send kick log message: {}", err);
-                }
+                self.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title("User kicked").description(format!("<@{}> has been kicked", action.user_id)).fields(fields).footer(CreateEmbedFooter::new(format!("User {} kicked | {} | UUID: {}", action.user_id, action.case(), action.get_id()))).color(0x000080),
+                );
             }
         }
 
@@ -262,7 +258,8 @@ impl Command for KickCommand {
                     .field("Reason", action.action.reason.to_string(), true)
                     .field("Moderator", format!("<@{}>", cmd.user.id.get()), true)
                     .footer(CreateEmbedFooter::new(format!(
-                        "UUID: {} | Total execution time: {:?}",
+                        "{} | UUID: {} | Total execution time: {:?}",
+                        action.action.case(),
                         action.action.get_id(),
                         start.elapsed()
                     )))
@@ -0,0 +1,105 @@
+use serenity::{
+    all::{CommandInteraction, CommandOptionType},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter},
+};
+use std::time::Instant;
+use tracing::error;
+
+use crate::models::{
+    command::{Command, CommandContext, CommandContextReply},
+    handler::Handler,
+    permissions::Permission,
+    response::{Response, ResponseError, ResponseResult},
+};
+
+pub struct LockdownCommand;
+
+#[async_trait::async_trait]
+impl Command for LockdownCommand {
+    fn name(&self) -> &'static str {
+        "lockdown"
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new("lockdown")
+            .dm_permission(false)
+            .description("Manage the anti-raid lockdown")
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "end",
+                "End the current lockdown and unlock its channels",
+            ))
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        let start = Instant::now();
+
+        if !ctx
+            .user_permissions
+            .contains(&Permission::ModerationLockdown)
+        {
+            return Err(ResponseError::Execution(
+                "You do not have permission to do this!",
+                Some(format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationLockdown)),
+            ));
+        }
+
+        let Some(subcommand) = cmd.data.options.first() else {
+            return Err(ResponseError::Execution(
+                "Invalid command",
+                Some("You must specify a subcommand to use this command!".to_string()),
+            ));
+        };
+
+        match subcommand.name.as_str() {
+            "end" => {
+                let ended = match handler.end_lockdown(&ctx.ctx, ctx.guild.id).await {
+                    Ok(ended) => ended,
+                    Err(err) => {
+                        error!(
+                            "Failed to end lockdown in guild {}: {:?}",
+                            ctx.guild.id, err
+                        );
+                        return Err(ResponseError::Execution(
+                            "Could not end lockdown",
+                            Some(
+                                "The lockdown could not be ended. Please try again later."
+                                    .to_string(),
+                            ),
+                        ));
+                    }
+                };
+                if !ended {
+                    return Err(ResponseError::Execution(
+                        "No lockdown active!",
+                        Some("This server is not in lockdown.".to_string()),
+                    ));
+                }
+
+                ctx.reply(
+                    cmd,
+                    Response::new().embed(
+                        CreateEmbed::new()
+                            .title("Lockdown ended")
+                            .description(format!("Ended by <@{}>", cmd.user.id))
+                            .color(0xf54029)
+                            .footer(CreateEmbedFooter::new(format!(
+                                "Total execution time: {:?}",
+                                start.elapsed()
+                            ))),
+                    ),
+                )
+                .await
+            }
+            _ => Err(ResponseError::Execution(
+                "Invalid command",
+                Some("You must specify a subcommand to use this command!".to_string()),
+            )),
+        }
+    }
+}
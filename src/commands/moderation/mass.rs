@@ -0,0 +1,495 @@
+use std::{collections::HashSet, fmt::Write, sync::LazyLock, time::Instant};
+
+use inflections::Inflect;
+use regex::Regex;
+use serenity::{
+    all::{ButtonStyle, ChannelId, CommandInteraction, CommandOptionType, UserId},
+    builder::{
+        CreateActionRow, CreateButton, CreateCommand, CreateCommandOption, CreateEmbed,
+        CreateEmbedFooter, CreateInteractionResponse,
+    },
+    futures::{future::join_all, StreamExt},
+};
+use tracing::error;
+
+use crate::{
+    common::{
+        duration::Duration,
+        logging::{get_log_channel, LogType},
+        options::Options,
+    },
+    models::{
+        command::{Command, CommandContext, CommandContextReply},
+        config::LoggingConfig,
+        handler::Handler,
+        highest_role::get_highest_role_by_id,
+        permissions::Permission,
+        response::{Response, ResponseError, ResponseResult},
+    },
+};
+
+const MAX_TARGETS: usize = 250;
+const BATCH_SIZE: usize = 5;
+
+static USER_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d{17,20}").unwrap());
+
+#[derive(Copy, Clone, PartialEq)]
+enum MassAction {
+    Ban,
+    Kick,
+}
+
+impl MassAction {
+    const fn verb(self) -> &'static str {
+        match self {
+            MassAction::Ban => "ban",
+            MassAction::Kick => "kick",
+        }
+    }
+
+    const fn title(self) -> &'static str {
+        match self {
+            MassAction::Ban => "Ban",
+            MassAction::Kick => "Kick",
+        }
+    }
+
+    const fn past(self) -> &'static str {
+        match self {
+            MassAction::Ban => "banned",
+            MassAction::Kick => "kicked",
+        }
+    }
+
+    const fn permission(self) -> Permission {
+        match self {
+            MassAction::Ban => Permission::ModerationBan,
+            MassAction::Kick => Permission::ModerationKick,
+        }
+    }
+
+    const fn color(self) -> u32 {
+        match self {
+            MassAction::Ban => 0xf54029,
+            MassAction::Kick => 0x000080,
+        }
+    }
+}
+
+enum MassResult {
+    Success(UserId),
+    Skipped(UserId),
+    Failed(UserId),
+}
+
+fn register_mass_command(typ: MassAction) -> CreateCommand {
+    let command = CreateCommand::new(format!("mass{}", typ.verb()))
+        .dm_permission(false)
+        .description(format!(
+            "{} many users from the server at once",
+            typ.title()
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "reason",
+                format!("The reason for the {}", typ.verb()),
+            )
+            .required(true),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "users",
+            format!("The IDs or mentions of the users to {}", typ.verb()),
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "joined",
+                format!("{} users who joined in the last N minutes", typ.title()),
+            )
+            .min_int_value(1)
+            .max_int_value(1440),
+        );
+
+    match typ {
+        MassAction::Ban => command.add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "duration",
+            "The duration of the bans",
+        )),
+        MassAction::Kick => command,
+    }
+}
+
+async fn get_targets(
+    ctx: &CommandContext,
+    cmd: &CommandInteraction,
+    options: &Options<'_>,
+) -> Result<Vec<UserId>, ResponseError> {
+    let users = options.get_string("users").into_owned();
+    let joined = options.get_integer("joined");
+    if users.is_none() && joined.is_none() {
+        return Err(ResponseError::Execution(
+            "No users provided!",
+            Some("Please provide a list of users, a join window, or both.".to_string()),
+        ));
+    }
+
+    let mut targets = Vec::new();
+    if let Some(users) = users {
+        for capture in USER_ID_REGEX.find_iter(&users) {
+            if let Ok(id) = capture.as_str().parse::<u64>() {
+                targets.push(UserId::new(id));
+            }
+        }
+    }
+    if let Some(minutes) = joined {
+        let since = time::OffsetDateTime::now_utc() - time::Duration::minutes(minutes);
+        let mut members = ctx.guild.id.members_iter(&ctx.ctx.http).boxed();
+        while let Some(member) = members.next().await {
+            let member = member.map_err(ResponseError::Serenity)?;
+            if member.user.bot {
+                continue;
+            }
+            if member
+                .joined_at
+                .is_some_and(|joined_at| joined_at.unix_timestamp() >= since.unix_timestamp())
+            {
+                targets.push(member.user.id);
+            }
+        }
+    }
+
+    let bot_id = ctx.ctx.cache.current_user().id;
+    let mut seen = HashSet::new();
+    targets
+        .retain(|user_id| *user_id != cmd.user.id && *user_id != bot_id && seen.insert(*user_id));
+
+    if targets.is_empty() {
+        return Err(ResponseError::Execution(
+            "No users found!",
+            Some("None of the provided filters matched any users.".to_string()),
+        ));
+    }
+    if targets.len() > MAX_TARGETS {
+        return Err(ResponseError::Execution(
+            "Too many users!",
+            Some(format!(
+                "{} users matched, but at most {MAX_TARGETS} can be handled at once. Please narrow your filters.",
+                targets.len()
+            )),
+        ));
+    }
+
+    Ok(targets)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn moderate_user(
+    handler: &Handler,
+    ctx: &CommandContext,
+    cmd: &CommandInteraction,
+    typ: MassAction,
+    user_id: UserId,
+    reason: &str,
+    duration: Option<&Duration>,
+    bot_highest_role: u16,
+) -> MassResult {
+    let target_highest_role = get_highest_role_by_id(ctx, user_id).await;
+    if ctx.highest_role <= target_highest_role || bot_highest_role <= target_highest_role {
+        return MassResult::Skipped(user_id);
+    }
+
+    let result = match typ {
+        MassAction::Ban => {
+            handler
+                .ban_user(
+                    ctx,
+                    ctx.guild.id.get() as i64,
+                    user_id.get() as i64,
+                    reason.to_string(),
+                    Some(cmd.user.id.get() as i64),
+                    duration.cloned(),
+                )
+                .await
+        }
+        MassAction::Kick => {
+            handler
+                .kick_user(
+                    ctx,
+                    ctx.guild.id.get() as i64,
+                    user_id.get() as i64,
+                    reason.to_string(),
+                    Some(cmd.user.id.get() as i64),
+                )
+                .await
+        }
+    };
+
+    match result {
+        Ok(_) => MassResult::Success(user_id),
+        Err(err) => {
+            error!(
+                "Failed to {} user {} during mass {}: {:?}",
+                typ.verb(),
+                user_id,
+                typ.verb(),
+                err
+            );
+            MassResult::Failed(user_id)
+        }
+    }
+}
+
+fn user_list(users: &[UserId]) -> String {
+    let mut list = String::new();
+    for (index, user_id) in users.iter().enumerate() {
+        let mention = format!("<@{user_id}> ");
+        if list.len() + mention.len() > 1000 {
+            write!(&mut list, "and {} more", users.len() - index).unwrap();
+            break;
+        }
+        list.push_str(&mention);
+    }
+    list
+}
+
+async fn mass_moderate(
+    handler: &Handler,
+    ctx: &CommandContext,
+    cmd: &CommandInteraction,
+    typ: MassAction,
+) -> ResponseResult {
+    let start = Instant::now();
+
+    if !ctx.user_permissions.contains(&typ.permission()) {
+        return Err(ResponseError::Execution(
+            "You do not have permission to do this!",
+            Some(format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", typ.permission())),
+        ));
+    }
+
+    let options = Options {
+        options: cmd.data.options(),
+    };
+
+    let Some(reason) = options.get_string("reason").into_owned() else {
+        return Err(ResponseError::Execution(
+            "No reason provided!",
+            Some(format!("Please provide a reason for the {}.", typ.verb())),
+        ));
+    };
+    let duration = match typ {
+        MassAction::Ban => Some(
+            match options
+                .get_string("duration")
+                .into_owned()
+                .as_deref()
+                .map(Duration::new)
+            {
+                Some(duration) => duration,
+                None => Duration::permanent(),
+            },
+        ),
+        MassAction::Kick => None,
+    };
+
+    let targets = get_targets(ctx, cmd, &options).await?;
+
+    let message = ctx
+        .reply_get_message(
+            cmd,
+            Response::new()
+                .embed(
+                    CreateEmbed::new()
+                        .title(format!("Confirm mass {}", typ.verb()))
+                        .description(format!(
+                            "You are about to {} **{}** users. This cannot be undone in bulk.",
+                            typ.verb(),
+                            targets.len()
+                        ))
+                        .field("Reason", reason.clone(), true)
+                        .field("Users", user_list(&targets), false)
+                        .color(typ.color()),
+                )
+                .components(vec![CreateActionRow::Buttons(vec![
+                    CreateButton::new("confirm")
+                        .label(format!("Mass {}", typ.verb()))
+                        .style(ButtonStyle::Danger),
+                    CreateButton::new("cancel")
+                        .label("Cancel")
+                        .style(ButtonStyle::Secondary),
+                ])]),
+        )
+        .await?;
+
+    let interaction = message
+        .await_component_interaction(&ctx.ctx)
+        .author_id(cmd.user.id)
+        .timeout(std::time::Duration::new(60, 0))
+        .await;
+
+    let confirmed = match interaction {
+        Some(interaction) => {
+            interaction
+                .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+                .await
+                .map_err(ResponseError::Serenity)?;
+            interaction.data.custom_id == "confirm"
+        }
+        None => false,
+    };
+    if !confirmed {
+        return ctx
+            .reply(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(format!("Mass {} cancelled", typ.verb()))
+                            .description("No users were affected")
+                            .color(typ.color()),
+                    )
+                    .components(vec![]),
+            )
+            .await;
+    }
+
+    let bot_id = ctx.ctx.cache.current_user().id;
+    let bot_highest_role = get_highest_role_by_id(ctx, bot_id).await;
+
+    let mut succeeded = Vec::new();
+    let mut skipped = Vec::new();
+    let mut failed = Vec::new();
+    for (batch, chunk) in targets.chunks(BATCH_SIZE).enumerate() {
+        let results = join_all(chunk.iter().map(|user_id| {
+            moderate_user(
+                handler,
+                ctx,
+                cmd,
+                typ,
+                *user_id,
+                &reason,
+                duration.as_ref(),
+                bot_highest_role,
+            )
+        }))
+        .await;
+        for result in results {
+            match result {
+                MassResult::Success(user_id) => succeeded.push(user_id),
+                MassResult::Skipped(user_id) => skipped.push(user_id),
+                MassResult::Failed(user_id) => failed.push(user_id),
+            }
+        }
+
+        let processed = (batch + 1) * BATCH_SIZE;
+        if processed < targets.len() {
+            ctx.reply(
+                cmd,
+                Response::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(format!("Mass {} in progress", typ.verb()))
+                            .description(format!("Processed {processed}/{} users", targets.len()))
+                            .color(typ.color()),
+                    )
+                    .components(vec![]),
+            )
+            .await?;
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+    }
+
+    let mut summary = CreateEmbed::new()
+        .title(format!("Mass {}", typ.verb()))
+        .description(format!(
+            "**{}** users were {}, **{}** were skipped and **{}** failed",
+            succeeded.len(),
+            typ.past(),
+            skipped.len(),
+            failed.len()
+        ))
+        .field("Moderator", format!("<@{}>", cmd.user.id.get()), true)
+        .field("Reason", reason, true)
+        .color(typ.color());
+    if !succeeded.is_empty() {
+        summary = summary.field(typ.past().to_title_case(), user_list(&succeeded), false);
+    }
+    if !skipped.is_empty() {
+        summary = summary.field("Skipped (role hierarchy)", user_list(&skipped), false);
+    }
+    if !failed.is_empty() {
+        summary = summary.field("Failed", user_list(&failed), false);
+    }
+
+    if let Ok(config) = sqlx::query_as!(
+        LoggingConfig,
+        "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
+        ctx.guild.id.get() as i64
+    )
+    .fetch_one(&handler.main_database)
+    .await {
+        if let Some(channel) = get_log_channel(handler, &config, &LogType::Action).await {
+            handler
+                .log_dispatcher
+                .dispatch(ChannelId::new(channel as u64), summary.clone());
+        }
+    }
+
+    ctx.reply(
+        cmd,
+        Response::new()
+            .embed(summary.footer(CreateEmbedFooter::new(format!(
+                "Total execution time: {:?}",
+                start.elapsed()
+            ))))
+            .components(vec![]),
+    )
+    .await
+}
+
+pub struct MassBanCommand;
+
+#[async_trait::async_trait]
+impl Command for MassBanCommand {
+    fn name(&self) -> &'static str {
+        "massban"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_mass_command(MassAction::Ban)
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        mass_moderate(handler, ctx, cmd, MassAction::Ban).await
+    }
+}
+
+pub struct MassKickCommand;
+
+#[async_trait::async_trait]
+impl Command for MassKickCommand {
+    fn name(&self) -> &'static str {
+        "masskick"
+    }
+
+    fn register(&self) -> CreateCommand {
+        register_mass_command(MassAction::Kick)
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        mass_moderate(handler, ctx, cmd, MassAction::Kick).await
+    }
+}
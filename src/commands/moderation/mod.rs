@@ -3,11 +3,22 @@ use crate::models::command::Command;
 pub mod ban;
 pub mod duration;
 pub mod expire;
+pub mod export;
+pub mod filter;
+pub mod import;
 pub mod kick;
+pub mod lockdown;
+pub mod mass;
+pub mod modstats;
 pub mod mute;
+pub mod note;
+pub mod punish;
 pub mod reason;
 pub mod remove;
+pub mod restore;
+pub mod schedule;
 pub mod search;
+pub mod standing;
 pub mod strike;
 pub mod unban;
 pub mod unmute;
@@ -17,11 +28,23 @@ pub fn get_moderation_commands() -> Vec<Box<dyn Command>> {
         Box::new(ban::BanCommand),
         Box::new(duration::DurationCommand),
         Box::new(expire::ExpireCommand),
+        Box::new(export::ExportCommand),
+        Box::new(filter::FilterCommand),
+        Box::new(import::ImportCommand),
         Box::new(kick::KickCommand),
+        Box::new(lockdown::LockdownCommand),
+        Box::new(mass::MassBanCommand),
+        Box::new(mass::MassKickCommand),
+        Box::new(modstats::ModStatsCommand),
         Box::new(mute::MuteCommand),
+        Box::new(note::NoteCommand),
+        Box::new(punish::PunishCommand),
         Box::new(reason::ReasonCommand),
         Box::new(remove::RemoveCommand),
+        Box::new(restore::RestoreCommand),
+        Box::new(schedule::ScheduleCommand),
         Box::new(search::SearchCommand),
+        Box::new(standing::StandingCommand),
         Box::new(strike::StrikeCommand),
         Box::new(unban::UnbanCommand),
         Box::new(unmute::UnmuteCommand),
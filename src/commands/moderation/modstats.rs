@@ -0,0 +1,371 @@
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use inflections::Inflect;
+use serenity::{
+    all::{ButtonStyle, CommandInteraction, CommandOptionType, ReactionType},
+    builder::{
+        CreateActionRow, CreateButton, CreateCommand, CreateCommandOption, CreateEmbed,
+        CreateEmbedFooter, CreateInteractionResponse,
+    },
+    futures::StreamExt,
+};
+use tracing::error;
+
+use crate::{
+    common::options::Options,
+    database::postgres::stats::{
+        get_activity_counts, get_moderator_action_counts, get_top_reasons,
+    },
+    models::{
+        command::{Command, CommandContext, CommandContextReply},
+        handler::Handler,
+        permissions::Permission,
+        response::{Response, ResponseError, ResponseResult},
+        stats::{ActivityCount, ModeratorActionCount, ReasonCount},
+    },
+};
+
+const STATS_COLOR: u32 = 0x2e4045;
+const LEADERBOARD_PAGE_SIZE: usize = 10;
+const MAX_ACTIVITY_ROWS: usize = 25;
+
+#[derive(Default)]
+struct ModeratorSummary {
+    total: i64,
+    removed: i64,
+    expired_early: i64,
+    types: HashMap<String, i64>,
+}
+
+impl ModeratorSummary {
+    fn add(&mut self, count: &ModeratorActionCount) {
+        self.total += count.total;
+        self.removed += count.removed;
+        self.expired_early += count.expired_early;
+        *self.types.entry(count.action_type.clone()).or_default() += count.total;
+    }
+
+    fn share(&self, count: i64) -> String {
+        if self.total == 0 {
+            return "0".to_string();
+        }
+        format!("{count} ({}%)", count * 100 / self.total)
+    }
+
+    fn breakdown(&self) -> String {
+        let mut types = self.types.iter().collect::<Vec<_>>();
+        types.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        types
+            .iter()
+            .map(|(typ, count)| format!("{typ} {count}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn overview_embed(
+    title: &str,
+    summary: &ModeratorSummary,
+    activity: &[ActivityCount],
+    period_days: Option<i64>,
+) -> CreateEmbed {
+    let days = period_days
+        .or_else(|| {
+            activity.first().map(|first| {
+                (time::OffsetDateTime::now_utc() - first.period.assume_utc()).whole_days() + 1
+            })
+        })
+        .unwrap_or(1)
+        .max(1);
+
+    let mut types = summary.types.iter().collect::<Vec<_>>();
+    types.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+    CreateEmbed::new()
+        .title(title)
+        .field("Actions", summary.total.to_string(), true)
+        .field(
+            "Per day",
+            format!(
+                "{}.{:02}",
+                summary.total / days,
+                summary.total * 100 / days % 100
+            ),
+            true,
+        )
+        .field("\u{200b}", "\u{200b}", true)
+        .fields(
+            types
+                .into_iter()
+                .map(|(typ, count)| (typ.to_title_case(), count.to_string(), true)),
+        )
+        .field("Removed", summary.share(summary.removed), true)
+        .field("Expired early", summary.share(summary.expired_early), true)
+        .color(STATS_COLOR)
+}
+
+fn activity_embed(title: &str, activity: &[ActivityCount], weekly: bool) -> CreateEmbed {
+    let max = activity.iter().map(|count| count.total).max().unwrap_or(1);
+    let mut description = String::new();
+    let skipped = activity.len().saturating_sub(MAX_ACTIVITY_ROWS);
+    for count in activity.iter().skip(skipped) {
+        writeln!(
+            description,
+            "`{}-{:02}-{:02}` {} **{}**",
+            count.period.year(),
+            u8::from(count.period.month()),
+            count.period.day(),
+            "▇".repeat(usize::try_from(count.total * 15 / max).unwrap_or(0).max(1)),
+            count.total
+        )
+        .unwrap();
+    }
+    if skipped > 0 {
+        write!(description, "*{skipped} earlier periods not shown*").unwrap();
+    }
+    if description.is_empty() {
+        description = "No actions in this period".to_string();
+    }
+
+    CreateEmbed::new()
+        .title(format!(
+            "{title} - {}",
+            if weekly {
+                "Actions per week"
+            } else {
+                "Actions per day"
+            }
+        ))
+        .description(description)
+        .color(STATS_COLOR)
+}
+
+fn reasons_embed(title: &str, reasons: &[ReasonCount]) -> CreateEmbed {
+    let mut description = String::new();
+    for (index, reason) in reasons.iter().enumerate() {
+        writeln!(
+            description,
+            "**{}.** {} - **{}**",
+            index + 1,
+            reason.reason.chars().take(100).collect::<String>(),
+            reason.total
+        )
+        .unwrap();
+    }
+    if description.is_empty() {
+        description = "No actions in this period".to_string();
+    }
+
+    CreateEmbed::new()
+        .title(format!("{title} - Top reasons"))
+        .description(description)
+        .color(STATS_COLOR)
+}
+
+fn leaderboard_embeds(title: &str, moderators: &[(i64, ModeratorSummary)]) -> Vec<CreateEmbed> {
+    moderators
+        .chunks(LEADERBOARD_PAGE_SIZE)
+        .enumerate()
+        .map(|(page, chunk)| {
+            let mut description = String::new();
+            for (index, (moderator_id, summary)) in chunk.iter().enumerate() {
+                writeln!(
+                    description,
+                    "**{}.** <@{moderator_id}> - **{}** actions\n> {} | Removed {} | Expired early {}",
+                    page * LEADERBOARD_PAGE_SIZE + index + 1,
+                    summary.total,
+                    summary.breakdown(),
+                    summary.removed,
+                    summary.expired_early
+                )
+                .unwrap();
+            }
+            CreateEmbed::new()
+                .title(format!("{title} - Leaderboard"))
+                .description(description)
+                .color(STATS_COLOR)
+        })
+        .collect()
+}
+
+fn generate_stats_response(pages: &[CreateEmbed], page: usize, time: &Instant) -> Response {
+    Response::new()
+        .embed(
+            pages[page - 1]
+                .clone()
+                .footer(CreateEmbedFooter::new(format!(
+                    "Page {}/{} | Total execution time: {:?}",
+                    page,
+                    pages.len(),
+                    time.elapsed()
+                ))),
+        )
+        .components(vec![CreateActionRow::Buttons(vec![
+            CreateButton::new("previous")
+                .style(ButtonStyle::Primary)
+                .emoji(ReactionType::Unicode("◀".to_string()))
+                .disabled(page == 1),
+            CreateButton::new("next")
+                .style(ButtonStyle::Primary)
+                .emoji(ReactionType::Unicode("▶".to_string()))
+                .disabled(page == pages.len()),
+            CreateButton::new("close")
+                .emoji('✅')
+                .style(ButtonStyle::Success),
+        ])])
+}
+
+pub struct ModStatsCommand;
+
+#[async_trait::async_trait]
+impl Command for ModStatsCommand {
+    fn name(&self) -> &'static str {
+        "modstats"
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new("modstats")
+            .dm_permission(false)
+            .description("View moderator activity statistics")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "moderator",
+                    "The moderator to view, leave empty to rank all moderators",
+                )
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "period",
+                    "The period to view statistics for",
+                )
+                .add_string_choice("7 days", "7")
+                .add_string_choice("30 days", "30")
+                .add_string_choice("90 days", "90")
+                .add_string_choice("1 year", "365")
+                .add_string_choice("All time", "all")
+                .required(false),
+            )
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        let mut start = Instant::now();
+
+        if !ctx.user_permissions.contains(&Permission::ModerationStats) {
+            return Err(ResponseError::Execution(
+                "You do not have permission to do this!",
+                Some(format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationStats)),
+            ));
+        }
+
+        let options = Options {
+            options: cmd.data.options(),
+        };
+
+        let moderator = options.get_user("moderator").into_owned();
+        let period_days = match options.get_string("period").into_owned().as_deref() {
+            Some("all") => None,
+            Some(days) => Some(days.parse::<i64>().unwrap_or(30)),
+            None => Some(30),
+        };
+        let since = period_days.map(|days| {
+            let since = time::OffsetDateTime::now_utc() - time::Duration::days(days);
+            time::PrimitiveDateTime::new(since.date(), since.time())
+        });
+        let weekly = period_days.is_none_or(|days| days > 31);
+        let guild_id = ctx.guild.id.get() as i64;
+        let moderator_id = moderator
+            .as_ref()
+            .map(|moderator| moderator.id.get() as i64);
+
+        let (counts, activity, reasons) = tokio::join!(
+            get_moderator_action_counts(handler, guild_id, moderator_id, since),
+            get_activity_counts(
+                handler,
+                guild_id,
+                moderator_id,
+                since,
+                if weekly { "week" } else { "day" }
+            ),
+            get_top_reasons(handler, guild_id, moderator_id, since)
+        );
+
+        let title = format!(
+            "{} statistics ({})",
+            moderator
+                .as_ref()
+                .map_or(ctx.guild.name.clone(), |moderator| moderator.name.clone()),
+            match period_days {
+                Some(days) => format!("last {days} days"),
+                None => "all time".to_string(),
+            }
+        );
+
+        let mut summary = ModeratorSummary::default();
+        let mut moderators: HashMap<i64, ModeratorSummary> = HashMap::new();
+        for count in &counts {
+            summary.add(count);
+            moderators.entry(count.moderator_id).or_default().add(count);
+        }
+        let mut moderators = moderators.into_iter().collect::<Vec<_>>();
+        moderators.sort_by(|a, b| b.1.total.cmp(&a.1.total).then(a.0.cmp(&b.0)));
+
+        let mut pages = vec![overview_embed(&title, &summary, &activity, period_days)];
+        if moderator.is_none() && !moderators.is_empty() {
+            pages.extend(leaderboard_embeds(&title, &moderators));
+        }
+        pages.push(activity_embed(&title, &activity, weekly));
+        pages.push(reasons_embed(&title, &reasons));
+
+        let mut page = 1;
+        let message = ctx
+            .reply_get_message(cmd, generate_stats_response(&pages, page, &start))
+            .await?;
+
+        let mut interaction_stream = message
+            .await_component_interactions(&ctx.ctx)
+            .author_id(cmd.user.id)
+            .timeout(Duration::new(60 * 15, 0))
+            .stream();
+        while let Some(interaction) = interaction_stream.next().await {
+            start = Instant::now();
+            match interaction.data.custom_id.as_str() {
+                "previous" if page > 1 => page -= 1,
+                "next" if page < pages.len() => page += 1,
+                "close" => {
+                    if let Err(err) = cmd.delete_response(&ctx.ctx.http).await {
+                        error!(
+                            "Failed to delete command interaction response with error: {:?}",
+                            err
+                        );
+                    }
+                    break;
+                }
+                _ => continue,
+            }
+            ctx.reply(cmd, generate_stats_response(&pages, page, &start))
+                .await?;
+            if let Err(err) = interaction
+                .create_response(&ctx.ctx.http, CreateInteractionResponse::Acknowledge)
+                .await
+            {
+                error!(
+                    "Failed to acknowledge interaction response with error: {:?}",
+                    err
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -14,8 +14,10 @@ use crate::{
         duration::Duration,
         logging::{get_log_channel, LogType},
         options::Options,
+        timeout::apply_timeout,
     },
     database::postgres::guild::get_moderation_config,
+    events::appeal::get_appeal_components,
     models::{
         actions::{Action, ActionDatabaseInsert, ActionType},
         command::{Command, CommandContext, CommandContextReply},
@@ -40,9 +42,10 @@ impl Handler {
         let start = Instant::now();
 
         let mute_role = match get_moderation_config(self, guild_id).await {
-            Some(config) => match config.mute_role {
-                Some(id) => RoleId::new(id as u64),
-                None => {
+            Some(config) => match (config.use_timeouts, config.mute_role) {
+                (true, _) => None,
+                (false, Some(id)) => Some(RoleId::new(id as u64)),
+                (false, None) => {
                     return Err(ResponseError::Execution(
                         "Could not find mute role!",
                         Some(
@@ -73,7 +76,7 @@ impl Handler {
             start.elapsed()
         );
 
-        let action = Action::new(
+        let mut action = Action::new(
             ActionType::Mute,
             user_id,
             moderator_id,
@@ -102,26 +105,22 @@ impl Handler {
             dm_notified: AtomicBool::new(false),
         };
 
-        let log_message = match sqlx::query_as!(
+        if let Ok(config) = sqlx::query_as!(
             LoggingConfig,
-            "SELECT log_actions, log_messages, log_voice, log_channel, log_action_channel, log_message_channel, log_voice_channel FROM logging_configuration WHERE guild_id = $1",
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
             guild_id
         )
         .fetch_one(&self.main_database)
         .await {
-            Ok(config) => {
-                get_log_channel(self, &config, &LogType::Action).await
-                    .map(|channel| {
-                        ChannelId::new(channel as u64)
-                            .send_message(
-                                &ctx.ctx,
-                                CreateMessage::new()
-                                    .embed(CreateEmbed::new().title("Mute issued").description(format!("<@{}> has been muted", action.user_id)).fields(fields.clone()).footer(CreateEmbedFooter::new(format!("User {} muted | UUID: {}", action.user_id, action.get_id()))).color(0x2e4045))
-                            )
-                    })
-            },
-            Err(_) => None
-        };
+            if let Some(channel) = get_log_channel(self, &config, &LogType::Action).await {
+                self.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title("Mute issued").description(format!("<@{}> has been muted", action.user_id)).fields(fields.clone()).footer(CreateEmbedFooter::new(format!("User {} muted | {} | UUID: {}", action.user_id, action.case(), action.get_id()))).color(0x2e4045),
+                );
+            }
+        }
+
+        let appeal_components = get_appeal_components(self, guild_id, &action.get_id()).await;
 
         let dm_channel =
             if sqlx::query!("SELECT active FROM global_kills WHERE feature = 'commands.dm'")
@@ -141,51 +140,67 @@ impl Handler {
                 None
             };
 
-        let mute_role_future = ctx.ctx.http.add_member_role(
-            GuildId::new(guild_id as u64),
-            UserId::new(user_id as u64),
-            mute_role,
-            Some(&action.reason),
-        );
-
-        if let Some(Ok(channel)) = match (log_message, dm_channel) {
-            (Some(log_future), Some(dm_channel)) => {
-                Some(tokio::join!(log_future, mute_role_future, dm_channel).2)
-            }
-            (None, Some(dm_channel)) => Some(tokio::join!(mute_role_future, dm_channel).1),
-            (Some(log_future), None) => {
-                let _ = tokio::join!(mute_role_future, log_future);
-                None
-            }
-            (None, None) => {
-                let _ = mute_role_future.await;
-                None
+        let mute_future = async {
+            match (mute_role, action.expiry) {
+                (Some(mute_role), _) => {
+                    ctx.ctx
+                        .http
+                        .add_member_role(
+                            GuildId::new(guild_id as u64),
+                            UserId::new(user_id as u64),
+                            mute_role,
+                            Some(&action.reason),
+                        )
+                        .await
+                }
+                (None, expiry) => {
+                    apply_timeout(
+                        &ctx.ctx,
+                        GuildId::new(guild_id as u64),
+                        UserId::new(user_id as u64),
+                        expiry,
+                        &action.reason,
+                    )
+                    .await
+                }
             }
-        } {
+        };
+
+        let dm_channel = if let Some(dm_channel) = dm_channel {
+            Some(tokio::join!(mute_future, dm_channel).1)
+        } else {
+            let _ = mute_future.await;
+            None
+        };
+        if let Some(Ok(channel)) = dm_channel {
             if channel
                 .send_message(
                     &ctx.ctx,
-                    CreateMessage::new().embed(
-                        CreateEmbed::new()
-                            .title("Muted!")
-                            .description(match GuildId::new(guild_id as u64).name(&ctx.ctx) {
-                                Some(guild_name) => {
-                                    format!("You've been muted in {guild_name}")
-                                }
-                                None => "A server has muted you".to_string(),
-                            })
-                            .fields(fields)
-                            .footer(CreateEmbedFooter::new(format!(
-                                "If you wish to appeal, please refer to the following action ID: {}",
-                                action.get_id()
-                            )))
-                            .color(0x2e4045),
-                    ),
+                    CreateMessage::new()
+                        .embed(
+                            CreateEmbed::new()
+                                .title("Muted!")
+                                .description(match GuildId::new(guild_id as u64).name(&ctx.ctx) {
+                                    Some(guild_name) => {
+                                        format!("You've been muted in {guild_name}")
+                                    }
+                                    None => "A server has muted you".to_string(),
+                                })
+                                .fields(fields)
+                                .footer(CreateEmbedFooter::new(format!(
+                                    "If you wish to appeal, please refer to {} (action ID: {})",
+                                    action.case(),
+                                    action.get_id()
+                                )))
+                                .color(0x2e4045),
+                        )
+                        .components(appeal_components),
                 )
                 .await
-                .is_ok() {
-                    action_insert.dm_notified.store(true, Ordering::Relaxed);
-                }
+                .is_ok()
+            {
+                action_insert.dm_notified.store(true, Ordering::Relaxed);
+            }
         };
 
         debug!("Completed mute action in {:?}", start.elapsed());
@@ -323,7 +338,8 @@ impl Command for MuteCommand {
                         true,
                     )
                     .footer(CreateEmbedFooter::new(format!(
-                        "UUID: {} | Total execution time: {:?}",
+                        "{} | UUID: {} | Total execution time: {:?}",
+                        action.action.case(),
                         action.action.get_id(),
                         start.elapsed()
                     )))
@@ -0,0 +1,282 @@
+use serenity::{
+    all::{CommandInteraction, CommandOptionType, User},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter},
+};
+use std::time::Instant;
+use tracing::error;
+
+use crate::{
+    common::options::Options,
+    database::postgres::notes::get_user_notes,
+    models::{
+        command::{Command, CommandContext, CommandContextReply},
+        handler::Handler,
+        note::Note,
+        permissions::Permission,
+        response::{Response, ResponseError, ResponseResult},
+    },
+};
+
+const NOTE_COLOR: u32 = 0x95a5a6;
+
+pub fn notes_embed(user: &User, notes: &[Note]) -> CreateEmbed {
+    let embed = CreateEmbed::new()
+        .title(format!("{}'s notes", user.name))
+        .color(NOTE_COLOR);
+    if notes.is_empty() {
+        return embed.description(format!("<@{}>\nNo notes found", user.id.get()));
+    }
+    let mut embed = embed.description(format!("<@{}>", user.id.get()));
+
+    let mut length = 0;
+    let mut shown = 0;
+    for note in notes.iter().take(25) {
+        let value = format!(
+            "{}\n- <@{}> <t:{}:R>",
+            note.content,
+            note.author_id,
+            note.created_at.assume_utc().unix_timestamp()
+        );
+        length += value.len() + 16;
+        if length > 5000 {
+            break;
+        }
+        embed = embed.field(format!("Note #{}", note.id), value, false);
+        shown += 1;
+    }
+    if shown < notes.len() {
+        embed = embed.footer(CreateEmbedFooter::new(format!(
+            "Showing the {shown} most recent of {} notes",
+            notes.len()
+        )));
+    }
+    embed
+}
+
+pub struct NoteCommand;
+
+#[async_trait::async_trait]
+impl Command for NoteCommand {
+    fn name(&self) -> &'static str {
+        "note"
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new("note")
+            .dm_permission(false)
+            .description("Manage private moderator notes on users")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "add",
+                    "Add a note to a user",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::User,
+                        "user",
+                        "The user to add a note to",
+                    )
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "content",
+                        "The content of the note",
+                    )
+                    .max_length(1000)
+                    .required(true),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "list",
+                    "List the notes on a user",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::User,
+                        "user",
+                        "The user to list the notes of",
+                    )
+                    .required(true),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "remove", "Remove a note")
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::Integer,
+                            "id",
+                            "The ID of the note",
+                        )
+                        .min_int_value(1)
+                        .required(true),
+                    ),
+            )
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        let start = Instant::now();
+
+        if !ctx.user_permissions.contains(&Permission::ModerationNotes) {
+            return Err(ResponseError::Execution(
+                "You do not have permission to do this!",
+                Some(format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationNotes)),
+            ));
+        }
+
+        let options = Options {
+            options: cmd.data.options(),
+        };
+        let guild_id = cmd.guild_id.unwrap().get() as i64;
+
+        let Some(subcommand) = cmd.data.options.first() else {
+            return Err(ResponseError::Execution(
+                "Invalid command",
+                Some("You must specify a subcommand to use this command!".to_string()),
+            ));
+        };
+
+        match subcommand.name.as_str() {
+            "add" => {
+                let Some(user) = options.get_user("user").into_owned() else {
+                    return Err(ResponseError::Execution(
+                        "No user provided!",
+                        Some("A user must be provided before continuing!".to_string()),
+                    ));
+                };
+                let Some(content) = options.get_string("content").into_owned() else {
+                    return Err(ResponseError::Execution(
+                        "No content provided!",
+                        Some(
+                            "The content of the note must be provided before continuing!"
+                                .to_string(),
+                        ),
+                    ));
+                };
+
+                let id = match sqlx::query!(
+                    "INSERT INTO notes (guild_id, user_id, author_id, content) VALUES ($1, $2, $3, $4) RETURNING id",
+                    guild_id,
+                    user.id.get() as i64,
+                    cmd.user.id.get() as i64,
+                    content
+                )
+                .fetch_one(&handler.main_database)
+                .await
+                {
+                    Ok(row) => row.id,
+                    Err(err) => {
+                        error!("Could not add note, failed with error: {:?}", err);
+                        return Err(ResponseError::Execution(
+                            "Could not add note",
+                            Some("The note could not be added. Please try again later.".to_string()),
+                        ));
+                    }
+                };
+
+                ctx.reply(
+                    cmd,
+                    Response::new()
+                        .embed(
+                            CreateEmbed::new()
+                                .title("Note added")
+                                .description(format!(
+                                    "Note #{id} has been added to <@{}>",
+                                    user.id.get()
+                                ))
+                                .field("Content", content, false)
+                                .color(NOTE_COLOR)
+                                .footer(CreateEmbedFooter::new(format!(
+                                    "Total execution time: {:?}",
+                                    start.elapsed()
+                                ))),
+                        )
+                        .ephemeral(true),
+                )
+                .await
+            }
+            "list" => {
+                let Some(user) = options.get_user("user").into_owned() else {
+                    return Err(ResponseError::Execution(
+                        "No user provided!",
+                        Some("A user must be provided before continuing!".to_string()),
+                    ));
+                };
+
+                let notes = get_user_notes(handler, guild_id, user.id.get() as i64).await;
+                ctx.reply(
+                    cmd,
+                    Response::new()
+                        .embed(notes_embed(&user, &notes))
+                        .ephemeral(true),
+                )
+                .await
+            }
+            "remove" => {
+                let Some(id) = options.get_integer("id") else {
+                    return Err(ResponseError::Execution(
+                        "No ID provided!",
+                        Some("The ID of the note must be provided before continuing!".to_string()),
+                    ));
+                };
+
+                match sqlx::query!(
+                    "DELETE FROM notes WHERE id = $1 AND guild_id = $2",
+                    i32::try_from(id).unwrap_or_default(),
+                    guild_id
+                )
+                .execute(&handler.main_database)
+                .await
+                {
+                    Ok(result) if result.rows_affected() == 0 => {
+                        return Err(ResponseError::Execution(
+                            "Note not found!",
+                            Some(format!("No note with the ID `{id}` exists in this server.")),
+                        ));
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        error!("Could not remove note, failed with error: {:?}", err);
+                        return Err(ResponseError::Execution(
+                            "Could not remove note",
+                            Some(
+                                "The note could not be removed. Please try again later."
+                                    .to_string(),
+                            ),
+                        ));
+                    }
+                }
+
+                ctx.reply(
+                    cmd,
+                    Response::new()
+                        .embed(
+                            CreateEmbed::new()
+                                .title("Note removed")
+                                .description(format!("Note #{id} has been removed"))
+                                .color(NOTE_COLOR)
+                                .footer(CreateEmbedFooter::new(format!(
+                                    "Total execution time: {:?}",
+                                    start.elapsed()
+                                ))),
+                        )
+                        .ephemeral(true),
+                )
+                .await
+            }
+            _ => Err(ResponseError::Execution(
+                "Invalid command",
+                Some("You must specify a subcommand to use this command!".to_string()),
+            )),
+        }
+    }
+}
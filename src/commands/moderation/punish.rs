@@ -0,0 +1,362 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
+
+use inflections::Inflect;
+use serenity::{
+    all::{ChannelId, CommandInteraction, CommandOptionType, GuildId, RoleId, UserId},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateMessage},
+};
+use tracing::{debug, error};
+
+use crate::{
+    common::{
+        duration::Duration,
+        logging::{get_log_channel, LogType},
+        options::Options,
+    },
+    database::postgres::actions::{get_custom_action, get_custom_actions},
+    events::appeal::get_appeal_components,
+    models::{
+        actions::{Action, ActionDatabaseInsert, ActionType, CustomAction},
+        command::{Command, CommandContext, CommandContextReply},
+        config::LoggingConfig,
+        handler::Handler,
+        highest_role::get_highest_role,
+        permissions::Permission,
+        response::{Response, ResponseError, ResponseResult},
+    },
+};
+
+impl Handler {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn punish_user(
+        &self,
+        ctx: &CommandContext,
+        guild_id: i64,
+        user_id: i64,
+        custom_action: &CustomAction,
+        reason: String,
+        moderator_id: Option<i64>,
+        duration: Option<Duration>,
+    ) -> Result<ActionDatabaseInsert, ResponseError> {
+        let start = Instant::now();
+
+        let duration = if let Some(duration) = duration {
+            if duration.permanent {
+                None
+            } else {
+                Some(duration)
+            }
+        } else {
+            None
+        };
+
+        let moderator_id = match moderator_id {
+            Some(mod_id) => mod_id,
+            None => ctx.ctx.cache.current_user().id.get() as i64,
+        };
+
+        debug!(
+            "Gathered all required data to punish in {:?}",
+            start.elapsed()
+        );
+
+        let mut action = Action::new(
+            ActionType::Custom,
+            user_id,
+            moderator_id,
+            guild_id,
+            reason,
+            duration,
+        );
+        action.custom_type = Some(custom_action.name.clone());
+
+        if let Err(err) = ctx
+            .ctx
+            .http
+            .add_member_role(
+                GuildId::new(guild_id as u64),
+                UserId::new(user_id as u64),
+                RoleId::new(custom_action.role_id as u64),
+                Some(&action.reason),
+            )
+            .await
+        {
+            error!("Failed to add punishment role: {}", err);
+            return Err(ResponseError::Serenity(err));
+        }
+
+        action.insert(self).await?;
+
+        let fields = vec![
+            ("Moderator", format!("<@{}>", action.moderator_id), true),
+            ("Reason", action.reason.clone(), true),
+            (
+                "Expires",
+                match action.expiry {
+                    Some(expiry) => format!("<t:{}:F>", expiry.unix_timestamp()),
+                    None => "Never".to_string(),
+                },
+                true,
+            ),
+        ];
+
+        let action_insert = ActionDatabaseInsert {
+            action: action.clone(),
+            dm_notified: AtomicBool::new(false),
+        };
+
+        if let Ok(config) = sqlx::query_as!(
+            LoggingConfig,
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_one(&self.main_database)
+        .await {
+            if let Some(channel) = get_log_channel(self, &config, &LogType::Action).await {
+                self.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title(format!("{} issued", custom_action.name.to_title_case())).description(format!("<@{}> has been given a {}", action.user_id, custom_action.name)).fields(fields.clone()).footer(CreateEmbedFooter::new(format!("User {} punished | {} | UUID: {}", action.user_id, action.case(), action.get_id()))).color(0x8e44ad),
+                );
+            }
+        }
+
+        let appeal_components = get_appeal_components(self, guild_id, &action.get_id()).await;
+
+        let dm_channel =
+            if sqlx::query!("SELECT active FROM global_kills WHERE feature = 'commands.dm'")
+                .fetch_one(&self.main_database)
+                .await
+                .unwrap()
+                .active
+                && ctx
+                    .ctx
+                    .http
+                    .get_member(GuildId::new(guild_id as u64), UserId::new(user_id as u64))
+                    .await
+                    .is_ok()
+            {
+                Some(UserId::new(user_id as u64).create_dm_channel(&ctx.ctx.http))
+            } else {
+                None
+            };
+
+        if let Some(Ok(channel)) = match dm_channel {
+            Some(dm_channel) => Some(dm_channel.await),
+            None => None,
+        } {
+            if channel
+                .send_message(
+                    &ctx.ctx,
+                    CreateMessage::new()
+                        .embed(
+                            CreateEmbed::new()
+                                .title(format!("{} received", custom_action.name.to_title_case()))
+                                .description(match GuildId::new(guild_id as u64).name(&ctx.ctx) {
+                                    Some(guild_name) => {
+                                        format!(
+                                            "You've been given a {} in {guild_name}",
+                                            custom_action.name
+                                        )
+                                    }
+                                    None => {
+                                        format!("A server has given you a {}", custom_action.name)
+                                    }
+                                })
+                                .fields(fields)
+                                .footer(CreateEmbedFooter::new(format!(
+                                    "If you wish to appeal, please refer to {} (action ID: {})",
+                                    action.case(),
+                                    action.get_id()
+                                )))
+                                .color(0x8e44ad),
+                        )
+                        .components(appeal_components),
+                )
+                .await
+                .is_ok()
+            {
+                action_insert.dm_notified.store(true, Ordering::Relaxed);
+            }
+        }
+
+        debug!("Completed punish action in {:?}", start.elapsed());
+
+        Ok(action_insert)
+    }
+}
+
+pub struct PunishCommand;
+
+#[async_trait::async_trait]
+impl Command for PunishCommand {
+    fn name(&self) -> &'static str {
+        "punish"
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new("punish")
+            .dm_permission(false)
+            .description("Give a user one of this server's custom punishments")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "type",
+                    "The name of the punishment",
+                )
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::User, "user", "The user to punish")
+                    .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "reason",
+                    "The reason for the punishment",
+                )
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "duration",
+                    "The duration of the punishment",
+                )
+                .required(false),
+            )
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        let start = Instant::now();
+
+        if !ctx.user_permissions.contains(&Permission::ModerationPunish) {
+            return Err(ResponseError::Execution(
+                "You do not have permission to do this!",
+                Some(format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationPunish)),
+            ));
+        }
+
+        let options = Options {
+            options: cmd.data.options(),
+        };
+
+        let Some(typ) = options.get_string("type").into_owned() else {
+            return Err(ResponseError::Execution(
+                "No punishment provided!",
+                Some("Please provide the name of the punishment to give.".to_string()),
+            ));
+        };
+        let Some(custom_action) = get_custom_action(handler, ctx.guild.id.get() as i64, &typ).await
+        else {
+            let custom_actions = get_custom_actions(handler, ctx.guild.id.get() as i64).await;
+            return Err(ResponseError::Execution(
+                "Invalid punishment!",
+                Some(if custom_actions.is_empty() {
+                    "This server has not configured any custom punishments. Please contact your server administrator to configure them.".to_string()
+                } else {
+                    format!(
+                        "The available punishments are: {}",
+                        custom_actions
+                            .iter()
+                            .map(|custom_action| format!("`{}`", custom_action.name))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                }),
+            ));
+        };
+
+        let Some(user) = options.get_user("user").into_owned() else {
+            return Err(ResponseError::Execution("No member found!", Some("The user option either was not provided, or this command was not ran in a guild. Both of these should not occur, if they do, please contact a developer.".to_string())));
+        };
+        if user == cmd.user {
+            return Err(ResponseError::Execution(
+                "You cannot punish yourself!",
+                Some("You cannot punish yourself, that would be silly.".to_string()),
+            ));
+        }
+        let Some(reason) = options.get_string("reason").into_owned() else {
+            return Err(ResponseError::Execution(
+                "No reason provided!",
+                Some("Please provide a reason for the punishment.".to_string()),
+            ));
+        };
+        let duration = match options
+            .get_string("duration")
+            .into_owned()
+            .as_deref()
+            .map(Duration::new)
+        {
+            Some(duration) => duration,
+            None => Duration::permanent(),
+        };
+
+        let target_user_highest_role = get_highest_role(ctx, &user).await;
+        if ctx.highest_role <= target_user_highest_role {
+            return Err(ResponseError::Execution(
+                "You cannot punish this user!",
+                Some(
+                    "You cannot punish a user with a role equal to or higher than yours."
+                        .to_string(),
+                ),
+            ));
+        }
+
+        let action = handler
+            .punish_user(
+                ctx,
+                ctx.guild.id.get() as i64,
+                user.id.get() as i64,
+                &custom_action,
+                reason,
+                Some(cmd.user.id.get() as i64),
+                Some(duration),
+            )
+            .await?;
+
+        ctx.reply(
+            cmd,
+            Response::new().embed(
+                CreateEmbed::new()
+                    .title(format!("{} issued", custom_action.name.to_title_case()))
+                    .description(if action.dm_notified.load(Ordering::Relaxed) {
+                        format!("<@{}> was given a {}", user.id.get(), custom_action.name)
+                    } else {
+                        format!(
+                            "<@{}> was given a {}\n*<@{}> could not be notified*",
+                            user.id.get(),
+                            custom_action.name,
+                            user.id.get()
+                        )
+                    })
+                    .field("Reason", action.action.reason.clone(), true)
+                    .field("Moderator", format!("<@{}>", cmd.user.id.get()), true)
+                    .field(
+                        "Expires",
+                        match action.action.expiry {
+                            Some(expiry) => format!("<t:{}:F>", expiry.unix_timestamp()),
+                            None => "Never".to_string(),
+                        },
+                        true,
+                    )
+                    .footer(CreateEmbedFooter::new(format!(
+                        "{} | UUID: {} | Total execution time: {:?}",
+                        action.action.case(),
+                        action.action.get_id(),
+                        start.elapsed()
+                    )))
+                    .color(0x8e44ad),
+            ),
+        )
+        .await
+    }
+}
@@ -1,6 +1,6 @@
 use serenity::{
     all::{ChannelId, CommandInteraction, CommandOptionType},
-    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateMessage},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter},
 };
 use std::time::Instant;
 use tracing::error;
@@ -10,7 +10,9 @@ use crate::{
         logging::{get_log_channel, LogType},
         options::Options,
     },
+    database::postgres::actions::{insert_action_revision, resolve_action_id},
     models::{
+        action_history::RevisionField,
         command::{Command, CommandContext, CommandContextReply},
         config::LoggingConfig,
         handler::Handler,
@@ -35,7 +37,7 @@ impl Command for ReasonCommand {
                 CreateCommandOption::new(
                     CommandOptionType::String,
                     "uuid",
-                    "The UUID of the action",
+                    "The UUID or case number of the action",
                 )
                 .required(true),
             )
@@ -76,7 +78,7 @@ impl Command for ReasonCommand {
         };
 
         let id = match options.get_string("uuid").into_owned() {
-            Some(id) => id,
+            Some(id) => resolve_action_id(handler, cmd.guild_id.unwrap().get() as i64, id).await,
             None => match sqlx::query!(
                 "SELECT id FROM actions WHERE moderator_id = $1 ORDER BY created_at DESC",
                 cmd.user.id.get() as i64
@@ -95,6 +97,34 @@ impl Command for ReasonCommand {
             },
         };
 
+        let guild_id = cmd.guild_id.unwrap().get() as i64;
+        let (old_reason, case_number) = match sqlx::query!(
+            "SELECT reason, case_number FROM actions WHERE id = $1 AND guild_id = $2",
+            id,
+            guild_id
+        )
+        .fetch_optional(&handler.main_database)
+        .await
+        {
+            Ok(Some(record)) => (record.reason, record.case_number),
+            Ok(None) => {
+                return Err(ResponseError::Execution(
+                    "Action not found",
+                    Some("No action with this ID exists in this server.".to_string()),
+                ))
+            }
+            Err(err) => {
+                error!("Could not fetch action, failed with error: {:?}", err);
+                return Err(ResponseError::Execution(
+                    "Could not update action reason",
+                    Some(
+                        "The action reason could not be updated. Please try again later."
+                            .to_string(),
+                    ),
+                ));
+            }
+        };
+
         if let Err(err) = sqlx::query!("UPDATE actions SET reason = $1 WHERE id = $2", reason, id)
             .execute(&handler.main_database)
             .await
@@ -108,6 +138,16 @@ impl Command for ReasonCommand {
                 Some("The action reason could not be updated. Please try again later.".to_string()),
             ));
         };
+        insert_action_revision(
+            handler,
+            guild_id,
+            &id,
+            RevisionField::Reason,
+            Some(old_reason.clone()),
+            Some(reason.clone()),
+            cmd.user.id.get() as i64,
+        )
+        .await;
 
         let reply = ctx.reply(
             cmd,
@@ -115,7 +155,7 @@ impl Command for ReasonCommand {
                 CreateEmbed::new()
                     .title("Reason updated")
                     .description(format!(
-                        "The reason of action `{id}` has been updated to {reason}"
+                        "The reason of case #{case_number} has been updated to {reason}"
                     ))
                     .color(0x0abfd6)
                     .footer(CreateEmbedFooter::new(format!(
@@ -125,26 +165,21 @@ impl Command for ReasonCommand {
             ),
         );
 
-        let log = if let Ok(config) = sqlx::query_as!(
+        if let Ok(config) = sqlx::query_as!(
             LoggingConfig,
-            "SELECT log_actions, log_messages, log_voice, log_channel, log_action_channel, log_message_channel, log_voice_channel FROM logging_configuration WHERE guild_id = $1",
-            cmd.guild_id.unwrap().get() as i64
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
+            guild_id
         )
         .fetch_one(&handler.main_database)
         .await {
-            get_log_channel(handler, &config, &LogType::Action).await.map(|channel| ChannelId::new(channel as u64)
-                .send_message(
-                    &ctx.ctx,
-                    CreateMessage::new()
-                        .embed(CreateEmbed::new().title("Reason updated").description(format!("The reason of action `{id}` has been updated to {reason}")).footer(CreateEmbedFooter::new(format!("Reason updated | UUID: {id}"))).color(0x0abfd6))
-            ))
-        } else {
-            None
-        };
-
-        match log {
-            Some(log_future) => tokio::join!(log_future, reply).1,
-            None => reply.await,
+            if let Some(channel) = get_log_channel(handler, &config, &LogType::Action).await {
+                handler.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title("Reason updated").description(format!("The reason of action `{id}` has been updated to {reason}")).field("Previous reason", old_reason, false).field("Moderator", format!("<@{}>", cmd.user.id), true).footer(CreateEmbedFooter::new(format!("Reason updated | Case #{case_number} | UUID: {id}"))).color(0x0abfd6),
+                );
+            }
         }
+
+        reply.await
     }
 }
@@ -1,6 +1,6 @@
 use serenity::{
     all::{ChannelId, CommandInteraction, CommandOptionType},
-    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateMessage},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter},
 };
 use std::time::Instant;
 use tracing::error;
@@ -10,7 +10,9 @@ use crate::{
         logging::{get_log_channel, LogType},
         options::Options,
     },
+    database::postgres::actions::{insert_action_revision, resolve_action_id},
     models::{
+        action_history::RevisionField,
         command::{Command, CommandContext, CommandContextReply},
         config::LoggingConfig,
         handler::Handler,
@@ -35,7 +37,7 @@ impl Command for RemoveCommand {
                 CreateCommandOption::new(
                     CommandOptionType::String,
                     "uuid",
-                    "The UUID of the action",
+                    "The UUID or case number of the action",
                 )
                 .required(true),
             )
@@ -66,24 +68,75 @@ impl Command for RemoveCommand {
                 Some("A UUID must be provided before continuing!".to_string()),
             ));
         };
+        let id = resolve_action_id(handler, ctx.guild.id.get() as i64, id).await;
 
-        if let Err(err) = sqlx::query!("DELETE FROM actions WHERE id = $1", id)
-            .execute(&handler.main_database)
-            .await
+        let guild_id = cmd.guild_id.unwrap().get() as i64;
+        let recovery_days = match sqlx::query!(
+            "SELECT removal_recovery_days FROM moderation_configuration WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_one(&handler.main_database)
+        .await
         {
-            error!("Could not remove action, failed with error: {:?}", err);
-            return Err(ResponseError::Execution(
-                "Could not remove action",
-                Some("The action could not be removed. Please try again later.".to_string()),
-            ));
+            Ok(config) => config.removal_recovery_days,
+            Err(err) => {
+                error!(
+                    "Could not fetch removal recovery period, failed with error: {:?}",
+                    err
+                );
+                return Err(ResponseError::Execution(
+                    "Could not remove action",
+                    Some("The action could not be removed. Please try again later.".to_string()),
+                ));
+            }
         };
 
+        let case_number = match sqlx::query!(
+            "WITH removed AS (DELETE FROM actions WHERE id = $1 AND guild_id = $2 RETURNING *) INSERT INTO removed_actions (id, action_type, user_id, moderator_id, guild_id, reason, active, expiry, created_at, custom_action, points, case_number, import_source, removed_by) SELECT id, action_type, user_id, moderator_id, guild_id, reason, active, expiry, created_at, custom_action, points, case_number, import_source, $3 FROM removed RETURNING case_number",
+            id,
+            guild_id,
+            cmd.user.id.get() as i64
+        )
+        .fetch_optional(&handler.main_database)
+        .await
+        {
+            Ok(Some(record)) => record.case_number,
+            Ok(None) => {
+                return Err(ResponseError::Execution(
+                    "Action not found",
+                    Some("No action with this ID exists in this server.".to_string()),
+                ));
+            }
+            Err(err) => {
+                error!("Could not remove action, failed with error: {:?}", err);
+                return Err(ResponseError::Execution(
+                    "Could not remove action",
+                    Some("The action could not be removed. Please try again later.".to_string()),
+                ));
+            }
+        };
+        insert_action_revision(
+            handler,
+            guild_id,
+            &id,
+            RevisionField::Removed,
+            None,
+            None,
+            cmd.user.id.get() as i64,
+        )
+        .await;
+
+        let recoverable_until = (time::OffsetDateTime::now_utc()
+            + time::Duration::days(i64::from(recovery_days)))
+        .unix_timestamp();
+        let description = format!("Case #{case_number} has been removed. It can be restored with `/restore` until <t:{recoverable_until}:F>");
+
         let reply = ctx.reply(
             cmd,
             Response::new().embed(
                 CreateEmbed::new()
                     .title("Action removed")
-                    .description(format!("The action with ID `{id}` has been removed"))
+                    .description(description.clone())
                     .color(0x2e4045)
                     .footer(CreateEmbedFooter::new(format!(
                         "Total execution time: {:?}",
@@ -92,26 +145,21 @@ impl Command for RemoveCommand {
             ),
         );
 
-        let log = if let Ok(config) = sqlx::query_as!(
+        if let Ok(config) = sqlx::query_as!(
             LoggingConfig,
-            "SELECT log_actions, log_messages, log_voice, log_channel, log_action_channel, log_message_channel, log_voice_channel FROM logging_configuration WHERE guild_id = $1",
-            cmd.guild_id.unwrap().get() as i64
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
+            guild_id
         )
         .fetch_one(&handler.main_database)
         .await {
-            get_log_channel(handler, &config, &LogType::Action).await.map(|channel| ChannelId::new(channel as u64)
-                .send_message(
-                    &ctx.ctx,
-                    CreateMessage::new()
-                        .embed(CreateEmbed::new().title("Action removed").description(format!("The action with ID `{id}` has been removed")).footer(CreateEmbedFooter::new(format!("Action removed | UUID: {id}"))).color(0x2e4045))
-            ))
-        } else {
-            None
-        };
-
-        match log {
-            Some(log_future) => tokio::join!(log_future, reply).1,
-            None => reply.await,
+            if let Some(channel) = get_log_channel(handler, &config, &LogType::Action).await {
+                handler.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title("Action removed").description(description).field("Moderator", format!("<@{}>", cmd.user.id), true).footer(CreateEmbedFooter::new(format!("Action removed | Case #{case_number} | UUID: {id}"))).color(0x2e4045),
+                );
+            }
         }
+
+        reply.await
     }
 }
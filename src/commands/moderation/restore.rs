@@ -0,0 +1,147 @@
+use serenity::{
+    all::{ChannelId, CommandInteraction, CommandOptionType},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter},
+};
+use std::time::Instant;
+use tracing::error;
+
+use crate::{
+    common::{
+        logging::{get_log_channel, LogType},
+        options::Options,
+        scheduler::ScheduledTask,
+    },
+    database::postgres::actions::{insert_action_revision, resolve_action_id},
+    models::{
+        action_history::RevisionField,
+        command::{Command, CommandContext, CommandContextReply},
+        config::LoggingConfig,
+        handler::Handler,
+        permissions::Permission,
+        response::{Response, ResponseError, ResponseResult},
+    },
+};
+
+pub struct RestoreCommand;
+
+#[async_trait::async_trait]
+impl Command for RestoreCommand {
+    fn name(&self) -> &'static str {
+        "restore"
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new("restore")
+            .dm_permission(false)
+            .description("Restore a removed action")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "uuid",
+                    "The UUID or case number of the removed action",
+                )
+                .required(true),
+            )
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        let start = Instant::now();
+
+        if !ctx.user_permissions.contains(&Permission::ModerationRemove) {
+            return Err(ResponseError::Execution(
+                "You do not have permission to do this!",
+                Some(format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationRemove)),
+            ));
+        }
+
+        let options = Options {
+            options: cmd.data.options(),
+        };
+
+        let Some(id) = options.get_string("uuid").into_owned() else {
+            return Err(ResponseError::Execution(
+                "UUID not provided!",
+                Some("A UUID must be provided before continuing!".to_string()),
+            ));
+        };
+        let id = resolve_action_id(handler, ctx.guild.id.get() as i64, id).await;
+        let guild_id = cmd.guild_id.unwrap().get() as i64;
+
+        let case_number = match sqlx::query!(
+            "WITH restored AS (DELETE FROM removed_actions WHERE id = $1 AND guild_id = $2 RETURNING *) INSERT INTO actions (id, action_type, user_id, moderator_id, guild_id, reason, active, expiry, created_at, custom_action, points, case_number, import_source) SELECT id, action_type, user_id, moderator_id, guild_id, reason, active, expiry, created_at, custom_action, points, case_number, import_source FROM restored RETURNING case_number, active, expiry",
+            id,
+            guild_id
+        )
+        .fetch_optional(&handler.main_database)
+        .await
+        {
+            Ok(Some(record)) => {
+                if let (true, Some(expiry)) = (record.active, record.expiry) {
+                    handler
+                        .scheduler
+                        .schedule(expiry.assume_utc(), ScheduledTask::ActionExpiry(id.clone()));
+                }
+                record.case_number
+            }
+            Ok(None) => {
+                return Err(ResponseError::Execution(
+                    "Action not found",
+                    Some("No removed action with this ID exists in this server. It may have been removed too long ago to be restored.".to_string()),
+                ));
+            }
+            Err(err) => {
+                error!("Could not restore action, failed with error: {:?}", err);
+                return Err(ResponseError::Execution(
+                    "Could not restore action",
+                    Some("The action could not be restored. Please try again later.".to_string()),
+                ));
+            }
+        };
+        insert_action_revision(
+            handler,
+            guild_id,
+            &id,
+            RevisionField::Restored,
+            None,
+            None,
+            cmd.user.id.get() as i64,
+        )
+        .await;
+
+        let reply = ctx.reply(
+            cmd,
+            Response::new().embed(
+                CreateEmbed::new()
+                    .title("Action restored")
+                    .description(format!("Case #{case_number} has been restored"))
+                    .color(0x2e4045)
+                    .footer(CreateEmbedFooter::new(format!(
+                        "Total execution time: {:?}",
+                        start.elapsed()
+                    ))),
+            ),
+        );
+
+        if let Ok(config) = sqlx::query_as!(
+            LoggingConfig,
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_one(&handler.main_database)
+        .await {
+            if let Some(channel) = get_log_channel(handler, &config, &LogType::Action).await {
+                handler.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title("Action restored").description(format!("Case #{case_number} has been restored")).field("Moderator", format!("<@{}>", cmd.user.id), true).footer(CreateEmbedFooter::new(format!("Action restored | Case #{case_number} | UUID: {id}"))).color(0x2e4045),
+                );
+            }
+        }
+
+        reply.await
+    }
+}
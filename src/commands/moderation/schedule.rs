@@ -0,0 +1,488 @@
+use serenity::{
+    all::{CommandInteraction, CommandOptionType},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed},
+};
+use tracing::error;
+
+use crate::{
+    common::{duration::Duration, options::Options, scheduler::ScheduledTask},
+    models::{
+        command::{Command, CommandContext, CommandContextReply},
+        handler::Handler,
+        highest_role::get_highest_role,
+        import::parse_timestamp,
+        permissions::Permission,
+        response::{Response, ResponseError, ResponseResult},
+        scheduled::{ScheduledAction, ScheduledActionType},
+    },
+};
+
+const SCHEDULE_COLOR: u32 = 0x2e4045;
+const MAX_PENDING_ACTIONS: i64 = 100;
+const MAX_LISTED_ACTIONS: usize = 25;
+const MAX_SCHEDULE_DAYS: i64 = 365;
+
+const SCHEDULED_TYPES: [ScheduledActionType; 6] = [
+    ScheduledActionType::Strike,
+    ScheduledActionType::Mute,
+    ScheduledActionType::Kick,
+    ScheduledActionType::Ban,
+    ScheduledActionType::Unmute,
+    ScheduledActionType::Unban,
+];
+
+fn missing_permission(permission: Permission) -> ResponseError {
+    ResponseError::Execution(
+        "You do not have permission to do this!",
+        Some(format!("You are missing the `{permission}` permission. If you believe this is a mistake, please contact your server administrators.")),
+    )
+}
+
+fn parse_when(value: &str) -> Option<time::OffsetDateTime> {
+    parse_timestamp(value).or_else(|| {
+        let duration = Duration::new(value);
+        duration
+            .to_timestamp()
+            .filter(|_| duration.string.chars().any(|char| char.is_ascii_digit()))
+    })
+}
+
+async fn add(
+    handler: &Handler,
+    ctx: &CommandContext,
+    cmd: &CommandInteraction,
+    options: &Options<'_>,
+) -> ResponseResult {
+    let Some(action_type) = options
+        .get_string("action")
+        .into_owned()
+        .map(ScheduledActionType::from)
+    else {
+        return Err(ResponseError::Execution(
+            "No action provided!",
+            Some("Please provide the action to schedule.".to_string()),
+        ));
+    };
+    if !ctx.user_permissions.contains(&action_type.permission()) {
+        return Err(missing_permission(action_type.permission()));
+    }
+
+    let Some(user) = options.get_user("user").into_owned() else {
+        return Err(ResponseError::Execution("No member found!", Some("The user option either was not provided, or this command was not ran in a guild. Both of these should not occur, if they do, please contact a developer.".to_string())));
+    };
+    if user == cmd.user {
+        return Err(ResponseError::Execution(
+            "You cannot schedule an action against yourself!",
+            Some("You cannot moderate yourself, that would be silly.".to_string()),
+        ));
+    }
+    let Some(reason) = options.get_string("reason").into_owned() else {
+        return Err(ResponseError::Execution(
+            "No reason provided!",
+            Some("Please provide a reason for the action.".to_string()),
+        ));
+    };
+
+    let when = options.get_string("when").into_owned().unwrap_or_default();
+    let now = time::OffsetDateTime::now_utc();
+    let Some(execute_at) = parse_when(&when).filter(|execute_at| *execute_at > now) else {
+        return Err(ResponseError::Execution(
+            "Invalid time!",
+            Some(format!("`{when}` is not a valid time in the future. Use a duration such as `2h` or `1d12h`, or a date such as `2024-05-01 18:00`.")),
+        ));
+    };
+    if execute_at > now + time::Duration::days(MAX_SCHEDULE_DAYS) {
+        return Err(ResponseError::Execution(
+            "Invalid time!",
+            Some(format!(
+                "Actions can be scheduled at most {MAX_SCHEDULE_DAYS} days ahead."
+            )),
+        ));
+    }
+
+    let duration = options.get_string("duration").into_owned();
+    if let Some(duration) = &duration {
+        if matches!(
+            action_type,
+            ScheduledActionType::Kick | ScheduledActionType::Unmute | ScheduledActionType::Unban
+        ) {
+            return Err(ResponseError::Execution(
+                "Invalid duration!",
+                Some(format!("A {action_type} cannot have a duration.")),
+            ));
+        }
+        // The duration is applied with Duration::new when the action runs, which does not take dates
+        if Duration::new(duration)
+            .to_timestamp()
+            .is_none_or(|expiry| expiry <= time::OffsetDateTime::now_utc())
+        {
+            return Err(ResponseError::Execution(
+                "Invalid duration!",
+                Some(format!(
+                    "`{duration}` is not a valid duration. Use a duration such as `2h` or `7d`."
+                )),
+            ));
+        }
+    } else if action_type == ScheduledActionType::Mute {
+        return Err(ResponseError::Execution(
+            "No duration provided!",
+            Some("A scheduled mute needs a duration.".to_string()),
+        ));
+    }
+
+    if !matches!(
+        action_type,
+        ScheduledActionType::Unmute | ScheduledActionType::Unban
+    ) {
+        let target_user_highest_role = get_highest_role(ctx, &user).await;
+        if ctx.highest_role <= target_user_highest_role {
+            return Err(ResponseError::Execution(
+                "You cannot moderate this user!",
+                Some(
+                    "You cannot moderate a user with a role equal to or higher than yours."
+                        .to_string(),
+                ),
+            ));
+        }
+    }
+
+    let guild_id = ctx.guild.id.get() as i64;
+    let pending = sqlx::query!(
+        r#"SELECT COUNT(*) AS "count!" FROM scheduled_actions WHERE guild_id = $1"#,
+        guild_id
+    )
+    .fetch_one(&handler.main_database)
+    .await?
+    .count;
+    if pending >= MAX_PENDING_ACTIONS {
+        return Err(ResponseError::Execution(
+            "Too many scheduled actions!",
+            Some(format!("This server already has {MAX_PENDING_ACTIONS} pending actions. Cancel some before scheduling more.")),
+        ));
+    }
+
+    let id = match sqlx::query!(
+        "INSERT INTO scheduled_actions (guild_id, user_id, moderator_id, action_type, reason, duration, execute_at) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id",
+        guild_id,
+        user.id.get() as i64,
+        cmd.user.id.get() as i64,
+        action_type.to_string(),
+        reason,
+        duration,
+        time::PrimitiveDateTime::new(execute_at.date(), execute_at.time())
+    )
+    .fetch_one(&handler.main_database)
+    .await
+    {
+        Ok(record) => record.id,
+        Err(err) => {
+            error!("Could not schedule action, failed with error: {:?}", err);
+            return Err(ResponseError::Execution(
+                "Could not schedule action",
+                Some("The action could not be scheduled. Please try again later.".to_string()),
+            ));
+        }
+    };
+    handler
+        .scheduler
+        .schedule(execute_at, ScheduledTask::ScheduledAction(id));
+
+    ctx.reply(
+        cmd,
+        Response::new().embed(
+            CreateEmbed::new()
+                .title("Action scheduled!")
+                .description(format!(
+                    "A {action_type} for <@{}> will be issued <t:{}:R>",
+                    user.id.get(),
+                    execute_at.unix_timestamp()
+                ))
+                .field("Reason", reason, true)
+                .field(
+                    "Duration",
+                    duration.unwrap_or("Permanent".to_string()),
+                    true,
+                )
+                .field("ID", format!("`{id}`"), true)
+                .color(SCHEDULE_COLOR),
+        ),
+    )
+    .await
+}
+
+async fn list(handler: &Handler, ctx: &CommandContext, cmd: &CommandInteraction) -> ResponseResult {
+    if !SCHEDULED_TYPES
+        .iter()
+        .any(|typ| ctx.user_permissions.contains(&typ.permission()))
+    {
+        return Err(missing_permission(ScheduledActionType::Strike.permission()));
+    }
+
+    let scheduled = match sqlx::query_as!(
+        ScheduledAction,
+        "SELECT * FROM scheduled_actions WHERE guild_id = $1 ORDER BY execute_at",
+        ctx.guild.id.get() as i64
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(scheduled) => scheduled,
+        Err(err) => {
+            error!(
+                "Could not list scheduled actions, failed with error: {:?}",
+                err
+            );
+            return Err(ResponseError::Execution(
+                "Could not list scheduled actions",
+                Some(
+                    "The scheduled actions could not be listed. Please try again later."
+                        .to_string(),
+                ),
+            ));
+        }
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title("Scheduled actions")
+        .color(SCHEDULE_COLOR);
+    if scheduled.is_empty() {
+        embed = embed.description("There are no pending actions in this server");
+    } else if scheduled.len() > MAX_LISTED_ACTIONS {
+        embed = embed.description(format!(
+            "Showing the next {MAX_LISTED_ACTIONS} of {} pending actions",
+            scheduled.len()
+        ));
+    }
+    for action in scheduled.into_iter().take(MAX_LISTED_ACTIONS) {
+        embed = embed.field(
+            format!("`{}` | {}", action.id, action.action_type),
+            format!(
+                "<@{}> <t:{}:R>{}\nScheduled by <@{}> <t:{}:R>\n> {}",
+                action.user_id,
+                action.execute_at.assume_utc().unix_timestamp(),
+                action
+                    .duration
+                    .map(|duration| format!(" for {duration}"))
+                    .unwrap_or_default(),
+                action.moderator_id,
+                action.created_at.assume_utc().unix_timestamp(),
+                action.reason.chars().take(100).collect::<String>()
+            ),
+            false,
+        );
+    }
+
+    ctx.reply(cmd, Response::new().embed(embed).ephemeral(true))
+        .await
+}
+
+async fn cancel(
+    handler: &Handler,
+    ctx: &CommandContext,
+    cmd: &CommandInteraction,
+    options: &Options<'_>,
+) -> ResponseResult {
+    let Some(id) = options
+        .get_integer("id")
+        .and_then(|id| i32::try_from(id).ok())
+    else {
+        return Err(ResponseError::Execution(
+            "No ID provided!",
+            Some("Please provide the ID of the scheduled action to cancel.".to_string()),
+        ));
+    };
+
+    let Some(action) = sqlx::query_as!(
+        ScheduledAction,
+        "SELECT * FROM scheduled_actions WHERE id = $1 AND guild_id = $2",
+        id,
+        ctx.guild.id.get() as i64
+    )
+    .fetch_optional(&handler.main_database)
+    .await?
+    else {
+        return Err(ResponseError::Execution(
+            "Scheduled action not found!",
+            Some(format!(
+                "No pending action with ID `{id}` exists in this server. It may have already been executed."
+            )),
+        ));
+    };
+    let permission = ScheduledActionType::from(action.action_type.as_str()).permission();
+    if !ctx.user_permissions.contains(&permission) {
+        return Err(missing_permission(permission));
+    }
+
+    if sqlx::query!("DELETE FROM scheduled_actions WHERE id = $1", id)
+        .execute(&handler.main_database)
+        .await?
+        .rows_affected()
+        == 0
+    {
+        return Err(ResponseError::Execution(
+            "Scheduled action not found!",
+            Some(format!(
+                "The action with ID `{id}` has already been executed."
+            )),
+        ));
+    }
+    handler
+        .scheduler
+        .unschedule(&ScheduledTask::ScheduledAction(id));
+
+    ctx.reply(
+        cmd,
+        Response::new().embed(
+            CreateEmbed::new()
+                .title("Scheduled action cancelled!")
+                .description(format!(
+                    "The {} for <@{}> will no longer be issued",
+                    action.action_type, action.user_id
+                ))
+                .color(SCHEDULE_COLOR),
+        ),
+    )
+    .await
+}
+
+pub struct ScheduleCommand;
+
+#[async_trait::async_trait]
+impl Command for ScheduleCommand {
+    fn name(&self) -> &'static str {
+        "schedule"
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new("schedule")
+            .dm_permission(false)
+            .description("Schedule moderation actions for later")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "add",
+                    "Schedule an action against a user",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "action",
+                        "The action to issue",
+                    )
+                    .add_string_choice("Strike", "strike")
+                    .add_string_choice("Mute", "mute")
+                    .add_string_choice("Kick", "kick")
+                    .add_string_choice("Ban", "ban")
+                    .add_string_choice("Unmute", "unmute")
+                    .add_string_choice("Unban", "unban")
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::User,
+                        "user",
+                        "The user to issue the action against",
+                    )
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "when",
+                        "When to issue the action, such as 2h or 2024-05-01 18:00 (UTC)",
+                    )
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "reason",
+                        "The reason for the action",
+                    )
+                    .max_length(255)
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "duration",
+                        "How long the action lasts once issued",
+                    )
+                    .max_length(32),
+                ),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "list",
+                "List pending scheduled actions",
+            ))
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "cancel",
+                    "Cancel a pending scheduled action",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "id",
+                        "The ID of the scheduled action",
+                    )
+                    .required(true),
+                ),
+            )
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        let options = Options {
+            options: cmd.data.options(),
+        };
+
+        match cmd.data.options.first().map(|option| option.name.as_str()) {
+            Some("add") => add(handler, ctx, cmd, &options).await,
+            Some("list") => list(handler, ctx, cmd).await,
+            Some("cancel") => cancel(handler, ctx, cmd, &options).await,
+            _ => Err(ResponseError::Execution(
+                "Invalid command",
+                Some("You must specify a subcommand to use this command!".to_string()),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_when;
+
+    #[test]
+    fn parses_absolute_times() {
+        let when = parse_when("2030-01-02T03:04:05Z").unwrap();
+        assert_eq!(
+            (when.year(), u8::from(when.month()), when.day()),
+            (2030, 1, 2)
+        );
+        assert_eq!((when.hour(), when.minute(), when.second()), (3, 4, 5));
+        assert_eq!(
+            parse_when("1893456000").unwrap().unix_timestamp(),
+            1_893_456_000
+        );
+    }
+
+    #[test]
+    fn parses_relative_durations() {
+        let expected = time::OffsetDateTime::now_utc() + time::Duration::hours(2);
+        let when = parse_when("2h").unwrap();
+        assert!((when - expected).abs() < time::Duration::seconds(5));
+    }
+
+    #[test]
+    fn rejects_values_without_a_time() {
+        assert!(parse_when("soon").is_none());
+        assert!(parse_when("permanent").is_none());
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     collections::HashMap,
+    fmt::Write,
     time::{Duration, Instant},
 };
 
@@ -11,17 +12,24 @@ use serenity::{
     },
     builder::{
         CreateActionRow, CreateButton, CreateCommand, CreateCommandOption, CreateEmbed,
-        CreateEmbedFooter, CreateInteractionResponse,
+        CreateEmbedFooter, CreateInteractionResponse, CreateSelectMenu, CreateSelectMenuKind,
+        CreateSelectMenuOption,
     },
     futures::StreamExt,
 };
 use tracing::error;
 
 use crate::{
-    common::options::Options,
+    commands::moderation::note::notes_embed,
+    common::options::{invalid_date, Options},
+    database::postgres::{
+        actions::get_action_revisions, appeals::get_action_appeals, notes::get_user_notes,
+    },
     models::{
+        action_history::ActionRevision,
         actions::Action,
         actions::{ActionType, DatabaseAction},
+        appeal::Appeal,
         command::{
             Command, CommandContext, CommandContextReply, InteractionContext,
             InteractionContextReply,
@@ -32,100 +40,299 @@ use crate::{
     },
 };
 
-fn generate_search_response(
-    user: &User,
+const PAGE_SIZE: usize = 10;
+const MAX_RESULTS: usize = 250;
+
+fn search_title(user: Option<&User>, expired: bool) -> String {
+    match (user, expired) {
+        (Some(user), true) => format!("{}'s history", user.name),
+        (Some(user), false) => format!("{}'s active history", user.name),
+        (None, true) => "Search results".to_string(),
+        (None, false) => "Active search results".to_string(),
+    }
+}
+
+fn search_permission(user: Option<&User>, searcher: &User, expired: bool) -> Permission {
+    if user == Some(searcher) {
+        if expired {
+            Permission::ModerationSearchSelfExpired
+        } else {
+            Permission::ModerationSearchSelf
+        }
+    } else if expired {
+        Permission::ModerationSearchOthersExpired
+    } else {
+        Permission::ModerationSearchOthers
+    }
+}
+
+fn type_label(action: &Action) -> String {
+    format!(
+        "{}{}",
+        action.type_name().to_title_case(),
+        if action.active { "" } else { " (Expired)" }
+    )
+}
+
+fn truncate(text: &str, length: usize) -> String {
+    if text.chars().count() > length {
+        format!("{}...", text.chars().take(length - 3).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
+fn generate_list_response(
+    user: Option<&User>,
     actions: &HashMap<u8, Action>,
     page: u8,
     expired: bool,
+    has_notes: bool,
     time: &Instant,
 ) -> Response {
-    if actions.is_empty() {
+    if actions.is_empty() && !has_notes {
         return Response::new().embed(
             CreateEmbed::new()
-                .title(format!("{}'s history", user.name))
+                .title(search_title(user, expired))
                 .description("No actions found"),
         );
     }
-    let Some(action) = actions.get(&(page - 1)) else {
-        return Response::new().embed(
-            CreateEmbed::new()
-                .title(format!("{}'s history", user.name))
-                .description("Invalid page"),
-        );
-    };
+    let pages = actions.len().div_ceil(PAGE_SIZE).max(1);
+    let indexes = ((usize::from(page) - 1) * PAGE_SIZE
+        ..actions.len().min(usize::from(page) * PAGE_SIZE))
+        .filter_map(|index| u8::try_from(index).ok())
+        .collect::<Vec<_>>();
+
+    let mut description = indexes
+        .iter()
+        .filter_map(|index| actions.get(index))
+        .map(|action| {
+            format!(
+                "**{}** | {} | <@{}> | <t:{}:d>\n> {}",
+                action.case(),
+                type_label(action),
+                action.user_id,
+                action.created_at.unix_timestamp(),
+                truncate(&action.reason, 80)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if actions.is_empty() {
+        description = "No actions found".to_string();
+    }
+    if actions.len() >= MAX_RESULTS {
+        write!(
+            description,
+            "\n\n*Only the {MAX_RESULTS} most recent matching actions are shown*"
+        )
+        .unwrap();
+    }
+
+    let mut components = vec![];
+    if !indexes.is_empty() {
+        components.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(
+                "action",
+                CreateSelectMenuKind::String {
+                    options: indexes
+                        .iter()
+                        .filter_map(|index| actions.get(index).map(|action| (index, action)))
+                        .map(|(index, action)| {
+                            CreateSelectMenuOption::new(
+                                format!("{} - {}", action.case(), type_label(action)),
+                                index.to_string(),
+                            )
+                            .description(truncate(&action.reason, 100))
+                        })
+                        .collect(),
+                },
+            )
+            .placeholder("Select an action to view"),
+        ));
+    }
+    components.push(CreateActionRow::Buttons(vec![
+        CreateButton::new("previous")
+            .style(ButtonStyle::Primary)
+            .emoji(ReactionType::Unicode("◀".to_string()))
+            .disabled(page == 1),
+        CreateButton::new("next")
+            .style(ButtonStyle::Primary)
+            .emoji(ReactionType::Unicode("▶".to_string()))
+            .disabled(usize::from(page) >= pages),
+        CreateButton::new("close")
+            .emoji('✅')
+            .style(ButtonStyle::Success),
+        CreateButton::new("notes")
+            .label("Notes")
+            .style(ButtonStyle::Secondary)
+            .disabled(!has_notes),
+    ]));
+
     Response::new()
         .embed(
             CreateEmbed::new()
-                .title(format!(
-                    "{}'s {}history",
-                    user.name,
-                    if expired { "" } else { "active " }
-                ))
-                .description(format!("<@{}>", user.id.get()))
-                .field(
-                    "Type",
-                    format!(
-                        "{} {}",
-                        action.typ.to_string().to_title_case(),
-                        if action.active { "" } else { "(Expired)" }
-                    ),
-                    true,
-                )
-                .field("Reason", action.reason.to_string(), true)
-                .field("Moderator", format!("<@{}>", action.moderator_id), false)
-                .field(
-                    "Issued at",
-                    format!("<t:{}:F>", action.created_at.unix_timestamp()),
-                    true,
-                )
-                .field(
-                    "Expires at",
-                    match action.expiry {
-                        Some(expiry) => format!("<t:{}:F>", expiry.unix_timestamp()),
-                        None => "Never".to_string(),
-                    },
-                    true,
-                )
-                .color(if action.active {
-                    match action.typ {
-                        ActionType::Strike => 0xeb966d,
-                        ActionType::Mute => 0x2e4045,
-                        ActionType::Kick => 0x000080,
-                        ActionType::Ban => 0xf54029,
-                    }
-                } else {
-                    match action.typ {
-                        ActionType::Strike => 0xbd7857,
-                        ActionType::Mute => 0x182124,
-                        ActionType::Kick => 0x000054,
-                        ActionType::Ban => 0xba2f1e,
-                    }
-                })
+                .title(search_title(user, expired))
+                .description(description)
+                .color(0x2e4045)
                 .footer(CreateEmbedFooter::new(format!(
-                    "Page {}/{} | Total execution time: {:?}",
+                    "Page {}/{} | {} actions | Total execution time: {:?}",
                     page,
+                    pages,
                     actions.len(),
                     time.elapsed()
                 ))),
         )
+        .components(components)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_search_response(
+    user: Option<&User>,
+    actions: &HashMap<u8, Action>,
+    appeals: &HashMap<String, Appeal>,
+    revisions: &HashMap<String, Vec<ActionRevision>>,
+    has_notes: bool,
+    page: u8,
+    expired: bool,
+    time: &Instant,
+) -> Response {
+    // Notes are only sent privately, so the list still offers them when there are no actions
+    if actions.is_empty() {
+        return generate_list_response(user, actions, 1, expired, has_notes, time);
+    }
+    let pages = actions.len();
+    let embed = match actions.get(&(page - 1)) {
+        Some(action) => action_embed(user, action, appeals, revisions, expired),
+        None => {
+            return Response::new().embed(
+                CreateEmbed::new()
+                    .title(search_title(user, expired))
+                    .description("Invalid page"),
+            )
+        }
+    };
+    Response::new()
+        .embed(embed.footer(CreateEmbedFooter::new(format!(
+            "Page {}/{} | Total execution time: {:?}",
+            page,
+            pages,
+            time.elapsed()
+        ))))
         .components(vec![CreateActionRow::Buttons(vec![
             CreateButton::new("previous")
                 .style(ButtonStyle::Primary)
                 .emoji(ReactionType::Unicode("◀".to_string()))
-                .disabled(page - 1 == 0),
+                .disabled(page == 1),
             CreateButton::new("next")
                 .style(ButtonStyle::Primary)
                 .emoji(ReactionType::Unicode("▶".to_string()))
-                .disabled(page - 1 == u8::try_from(actions.len()).unwrap() - 1),
+                .disabled(usize::from(page) == pages),
             CreateButton::new("close")
                 .emoji('✅')
                 .style(ButtonStyle::Success),
             CreateButton::new("uuid")
                 .label("Get UUID")
                 .style(ButtonStyle::Secondary),
+            CreateButton::new("list")
+                .label("List")
+                .style(ButtonStyle::Secondary),
         ])])
 }
 
+fn revision_timeline(revisions: &[ActionRevision]) -> String {
+    let mut timeline = Vec::new();
+    let mut length = 0;
+    for revision in revisions.iter().rev() {
+        let line = revision.describe();
+        length += line.len() + 1;
+        if length > 1000 {
+            timeline.push(format!(
+                "*{} older changes*",
+                revisions.len() - timeline.len()
+            ));
+            break;
+        }
+        timeline.push(line);
+    }
+    timeline.reverse();
+    timeline.join("\n")
+}
+
+fn action_embed(
+    user: Option<&User>,
+    action: &Action,
+    appeals: &HashMap<String, Appeal>,
+    revisions: &HashMap<String, Vec<ActionRevision>>,
+    expired: bool,
+) -> CreateEmbed {
+    CreateEmbed::new()
+        .title(search_title(user, expired))
+        .description(format!("<@{}> | {}", action.user_id, action.case()))
+        .field("Type", type_label(action), true)
+        .field("Reason", action.reason.to_string(), true)
+        .fields(
+            action
+                .points
+                .map(|points| ("Points", points.to_string(), true)),
+        )
+        .field("Moderator", format!("<@{}>", action.moderator_id), false)
+        .fields(appeals.get(&action.get_id()).map(|appeal| {
+            (
+                "Appeal",
+                format!(
+                    "{}{}\n> {}{}",
+                    appeal.status.to_string().to_title_case(),
+                    match appeal.reviewer_id {
+                        Some(reviewer_id) => format!(" by <@{reviewer_id}>"),
+                        None => String::new(),
+                    },
+                    appeal.reason,
+                    match &appeal.response {
+                        Some(response) => format!("\n**Response:** {response}"),
+                        None => String::new(),
+                    }
+                ),
+                false,
+            )
+        }))
+        .field(
+            "Issued at",
+            format!("<t:{}:F>", action.created_at.unix_timestamp()),
+            true,
+        )
+        .field(
+            "Expires at",
+            match action.expiry {
+                Some(expiry) => format!("<t:{}:F>", expiry.unix_timestamp()),
+                None => "Never".to_string(),
+            },
+            true,
+        )
+        .fields(
+            revisions
+                .get(&action.get_id())
+                .map(|revisions| ("History", revision_timeline(revisions), false)),
+        )
+        .color(if action.active {
+            match action.typ {
+                ActionType::Strike => 0xeb966d,
+                ActionType::Mute => 0x2e4045,
+                ActionType::Kick => 0x000080,
+                ActionType::Ban => 0xf54029,
+                ActionType::Custom => 0x8e44ad,
+            }
+        } else {
+            match action.typ {
+                ActionType::Strike => 0xbd7857,
+                ActionType::Mute => 0x182124,
+                ActionType::Kick => 0x000054,
+                ActionType::Ban => 0xba2f1e,
+                ActionType::Custom => 0x5e2d73,
+            }
+        })
+}
+
 pub struct SearchCommand;
 
 #[async_trait::async_trait]
@@ -139,8 +346,12 @@ impl Command for SearchCommand {
             .dm_permission(false)
             .description("Searches for moderation history")
             .add_option(
-                CreateCommandOption::new(CommandOptionType::User, "user", "The user to search")
-                    .required(false),
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "user",
+                    "The user to search, defaults to yourself unless other filters are used",
+                )
+                .required(false),
             )
             .add_option(
                 CreateCommandOption::new(
@@ -150,6 +361,59 @@ impl Command for SearchCommand {
                 )
                 .required(false),
             )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "moderator",
+                    "Only show actions issued by this moderator",
+                )
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "type",
+                    "Only show actions of this type",
+                )
+                .add_string_choice("Strike", "strike")
+                .add_string_choice("Mute", "mute")
+                .add_string_choice("Kick", "kick")
+                .add_string_choice("Ban", "ban")
+                .add_string_choice("Custom", "custom")
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "reason",
+                    "Only show actions whose reason contains this text",
+                )
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "after",
+                    "Only show actions issued on or after this date (YYYY-MM-DD)",
+                )
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "before",
+                    "Only show actions issued before this date (YYYY-MM-DD)",
+                )
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "compact",
+                    "Whether to list 10 actions per page instead of one",
+                )
+                .required(false),
+            )
     }
 
     async fn router(
@@ -164,31 +428,37 @@ impl Command for SearchCommand {
             options: cmd.data.options(),
         };
 
+        let moderator = options.get_user("moderator").into_owned();
+        let action_type = options.get_string("type").into_owned();
+        let reason = options.get_string("reason").into_owned();
+        let after = options
+            .get_date("after")
+            .map_err(|value| invalid_date(&value))?;
+        let before = options
+            .get_date("before")
+            .map_err(|value| invalid_date(&value))?;
+        let filtered = moderator.is_some()
+            || action_type.is_some()
+            || reason.is_some()
+            || after.is_some()
+            || before.is_some();
+
         let user = match options.get_user("user").into_owned() {
             Some(user) => {
                 if user == cmd.user {
-                    cmd.user.clone()
+                    Some(cmd.user.clone())
                 } else {
-                    user
+                    Some(user)
                 }
             }
-            None => cmd.user.clone(),
+            None if filtered => None,
+            None => Some(cmd.user.clone()),
         };
 
         let expired = options.get_boolean("expired").unwrap_or(false);
+        let mut compact = options.get_boolean("compact").unwrap_or(false);
 
-        let permission_required = if user == cmd.user {
-            if expired {
-                Permission::ModerationSearchSelfExpired
-            } else {
-                Permission::ModerationSearchSelf
-            }
-        } else if expired {
-            Permission::ModerationSearchOthersExpired
-        } else {
-            Permission::ModerationSearchOthers
-        };
-
+        let permission_required = search_permission(user.as_ref(), &cmd.user, expired);
         if !ctx.user_permissions.contains(&permission_required) {
             return Err(ResponseError::Execution(
                 "You do not have permission to do this!",
@@ -196,11 +466,32 @@ impl Command for SearchCommand {
             ));
         }
 
-        let actions = match if expired {
-            sqlx::query_as!(DatabaseAction, "SELECT * FROM actions WHERE user_id = $1 AND guild_id = $2 ORDER BY created_at DESC", user.id.get() as i64, cmd.guild_id.unwrap().get() as i64).fetch_all(&handler.main_database).await
-        } else {
-            sqlx::query_as!(DatabaseAction, "SELECT * FROM actions WHERE user_id = $1 AND guild_id = $2 AND active=true ORDER BY created_at DESC", user.id.get() as i64, cmd.guild_id.unwrap().get() as i64).fetch_all(&handler.main_database).await
-        } {
+        let guild_id = cmd.guild_id.unwrap().get() as i64;
+        let reason_pattern = reason.map(|reason| {
+            format!(
+                "%{}%",
+                reason
+                    .replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_")
+            )
+        });
+        let actions = match sqlx::query_as!(
+            DatabaseAction,
+            "SELECT * FROM actions WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR user_id = $2) AND ($3::BIGINT IS NULL OR moderator_id = $3) AND ($4::VARCHAR IS NULL OR action_type = $4) AND ($5::VARCHAR IS NULL OR reason ILIKE $5) AND ($6::TIMESTAMP IS NULL OR created_at >= $6) AND ($7::TIMESTAMP IS NULL OR created_at < $7) AND ($8 OR active = true) ORDER BY created_at DESC LIMIT $9",
+            guild_id,
+            user.as_ref().map(|user| user.id.get() as i64),
+            moderator.as_ref().map(|moderator| moderator.id.get() as i64),
+            action_type,
+            reason_pattern,
+            after,
+            before,
+            expired,
+            MAX_RESULTS as i64
+        )
+        .fetch_all(&handler.main_database)
+        .await
+        {
             Ok(db_actions) => db_actions
                 .iter()
                 .enumerate()
@@ -218,12 +509,54 @@ impl Command for SearchCommand {
                 ))
             }
         };
+        let action_ids = actions.values().map(Action::get_id).collect::<Vec<_>>();
+
+        let appeals = get_action_appeals(handler, guild_id, &action_ids)
+            .await
+            .into_iter()
+            .map(|appeal| (appeal.action_id.clone(), appeal))
+            .collect::<HashMap<_, _>>();
+
+        let revisions = get_action_revisions(handler, guild_id, &action_ids).await;
+
+        let notes = match &user {
+            Some(user)
+                if *user != cmd.user
+                    && !filtered
+                    && ctx.user_permissions.contains(&Permission::ModerationNotes) =>
+            {
+                Some(get_user_notes(handler, guild_id, user.id.get() as i64).await)
+            }
+            _ => None,
+        };
+        let pages = actions.len();
 
         let mut page = 1;
+        let mut list_page = 1;
         let message = ctx
             .reply_get_message(
                 cmd,
-                generate_search_response(&user, &actions, page, expired, &start),
+                if compact {
+                    generate_list_response(
+                        user.as_ref(),
+                        &actions,
+                        list_page,
+                        expired,
+                        notes.is_some(),
+                        &start,
+                    )
+                } else {
+                    generate_search_response(
+                        user.as_ref(),
+                        &actions,
+                        &appeals,
+                        &revisions,
+                        notes.is_some(),
+                        page,
+                        expired,
+                        &start,
+                    )
+                },
             )
             .await?;
 
@@ -236,22 +569,17 @@ impl Command for SearchCommand {
                 InteractionContext::new(handler, ctx.ctx.clone(), &interaction).await;
 
             start = Instant::now();
-            match interaction_context.interaction.data.kind {
-                ComponentInteractionDataKind::Button => {}
+            let selected = match &interaction_context.interaction.data.kind {
+                ComponentInteractionDataKind::Button => None,
+                ComponentInteractionDataKind::StringSelect { values } => values.first().cloned(),
                 _ => continue,
-            }
+            };
             if interaction_context.interaction.user.id != cmd.user.id {
-                let permission_required = if user == interaction_context.interaction.user {
-                    if expired {
-                        Permission::ModerationSearchSelfExpired
-                    } else {
-                        Permission::ModerationSearchSelf
-                    }
-                } else if expired {
-                    Permission::ModerationSearchOthersExpired
-                } else {
-                    Permission::ModerationSearchOthers
-                };
+                let permission_required = search_permission(
+                    user.as_ref(),
+                    &interaction_context.interaction.user,
+                    expired,
+                );
 
                 if !interaction_context
                     .user_permissions
@@ -272,19 +600,78 @@ impl Command for SearchCommand {
             let mut update_required = false;
             match interaction_context.interaction.data.custom_id.as_str() {
                 "previous" => {
-                    if page > 1 {
+                    if compact && list_page > 1 {
+                        list_page -= 1;
+                        update_required = true;
+                    } else if !compact && page > 1 {
                         page -= 1;
                         update_required = true;
                     }
                 }
                 "next" => {
-                    if page < u8::try_from(actions.len()).unwrap() {
+                    if compact && usize::from(list_page) < actions.len().div_ceil(PAGE_SIZE) {
+                        list_page += 1;
+                        update_required = true;
+                    } else if !compact && usize::from(page) < pages {
                         page += 1;
                         update_required = true;
                     }
                 }
+                "action" => {
+                    if let Some(index) = selected.and_then(|value| value.parse::<u8>().ok()) {
+                        if actions.contains_key(&index) {
+                            page = index + 1;
+                            compact = false;
+                            update_required = true;
+                        }
+                    }
+                }
+                "list" => {
+                    list_page = u8::try_from(
+                        (usize::from(page) - 1).min(actions.len().saturating_sub(1)) / PAGE_SIZE
+                            + 1,
+                    )
+                    .unwrap_or(1);
+                    compact = true;
+                    update_required = true;
+                }
+                "notes" => {
+                    let (Some(user), Some(notes)) = (&user, &notes) else {
+                        continue;
+                    };
+                    // The search message can be public, so notes only go to moderators privately
+                    if interaction_context.interaction.user.id == user.id
+                        || !interaction_context
+                            .user_permissions
+                            .contains(&Permission::ModerationNotes)
+                    {
+                        if let Err(err) = interaction_context.error_message(
+                            ResponseError::Execution(
+                                "You do not have permission to do this!",
+                                Some(format!(
+                                    "You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.",
+                                    Permission::ModerationNotes
+                                ))
+                            )).await {
+                            error!("Failed to reply to command interaction with error: {:?}", err);
+                        }
+                        continue;
+                    }
+                    if let Err(err) = interaction_context
+                        .reply(
+                            Response::new()
+                                .embed(notes_embed(user, notes))
+                                .ephemeral(true),
+                        )
+                        .await
+                    {
+                        error!("Failed to send notes with error: {:?}", err);
+                    }
+                }
                 "uuid" => {
-                    let action = actions.get(&(page - 1)).unwrap();
+                    let Some(action) = actions.get(&(page - 1)) else {
+                        continue;
+                    };
                     if let Err(err) = interaction_context
                         .reply(Response::new().content(action.get_id()).ephemeral(true))
                         .await
@@ -305,7 +692,27 @@ impl Command for SearchCommand {
             if update_required {
                 ctx.reply(
                     cmd,
-                    generate_search_response(&user, &actions, page, expired, &start),
+                    if compact {
+                        generate_list_response(
+                            user.as_ref(),
+                            &actions,
+                            list_page,
+                            expired,
+                            notes.is_some(),
+                            &start,
+                        )
+                    } else {
+                        generate_search_response(
+                            user.as_ref(),
+                            &actions,
+                            &appeals,
+                            &revisions,
+                            notes.is_some(),
+                            page,
+                            expired,
+                            &start,
+                        )
+                    },
                 )
                 .await?;
                 if let Err(err) = interaction_context
@@ -0,0 +1,306 @@
+use std::fmt::Write;
+
+use inflections::Inflect;
+use serenity::{
+    all::{CommandInteraction, GuildId},
+    builder::{CreateCommand, CreateEmbed},
+};
+use tracing::error;
+
+use crate::{
+    database::postgres::{actions::get_active_strikes, guild::get_moderation_config},
+    models::{
+        actions::{Action, ActionEscalation, EscalationType},
+        command::{
+            get_member_permissions, Command, CommandContext, CommandContextReply,
+            FailedCommandContext,
+        },
+        handler::Handler,
+        permissions::Permission,
+        response::{Response, ResponseError, ResponseResult},
+    },
+};
+
+const STANDING_COLOR: u32 = 0xeb966d;
+const MAX_LISTED_STRIKES: usize = 10;
+const MAX_DM_GUILDS: usize = 10;
+// Discord rejects messages whose embeds add up to more than 6000 characters
+const MAX_DM_LENGTH: usize = 5800;
+
+fn describe_escalation(escalation: &ActionEscalation) -> Option<String> {
+    let name = match escalation.action_type {
+        EscalationType::Alert => return None,
+        EscalationType::Warn => "Warning".to_string(),
+        EscalationType::Custom => escalation
+            .custom_action
+            .clone()
+            .unwrap_or_default()
+            .to_title_case(),
+        typ => typ.to_string().to_title_case(),
+    };
+    Some(match &escalation.action_duration {
+        Some(duration) => format!("{name} for {duration}"),
+        None => name,
+    })
+}
+
+fn describe_tier(escalations: &[ActionEscalation], tier: i64) -> String {
+    let actions = escalations
+        .iter()
+        .filter(|escalation| escalation.points == tier)
+        .filter_map(describe_escalation)
+        .collect::<Vec<_>>();
+    if actions.is_empty() {
+        format!("{tier} points")
+    } else {
+        format!("{tier} points: {}", actions.join(", "))
+    }
+}
+
+async fn standing_embed(
+    handler: &Handler,
+    guild_id: i64,
+    guild_name: &str,
+    user_id: i64,
+) -> (CreateEmbed, usize) {
+    let strike_window = get_moderation_config(handler, guild_id)
+        .await
+        .and_then(|config| config.strike_window)
+        .map(|days| time::OffsetDateTime::now_utc() - time::Duration::days(i64::from(days)));
+    let mut strikes = get_active_strikes(handler, guild_id, user_id)
+        .await
+        .into_iter()
+        .map(Action::from)
+        .collect::<Vec<_>>();
+    strikes.sort_by_key(|strike| strike.created_at);
+    let counted = |strike: &Action| strike_window.is_none_or(|window| strike.created_at >= window);
+    let points = strikes
+        .iter()
+        .filter(|strike| counted(strike))
+        .map(|strike| i64::from(strike.points.unwrap_or(1)))
+        .sum::<i64>();
+
+    let escalations = match sqlx::query_as!(
+        ActionEscalation,
+        "SELECT * FROM strike_escalations WHERE guild_id = $1 ORDER BY points",
+        guild_id
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(escalations) => escalations,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} strike escalations, failed with error: {err}"
+            );
+            vec![]
+        }
+    };
+    let current_tier = escalations
+        .iter()
+        .map(|escalation| escalation.points)
+        .filter(|tier| *tier <= points)
+        .max();
+    let next_tier = escalations
+        .iter()
+        .map(|escalation| escalation.points)
+        .filter(|tier| *tier > points)
+        .min();
+
+    let mut description = String::new();
+    if strikes.is_empty() {
+        description.push_str("You have no active strikes");
+    }
+    for strike in strikes.iter().take(MAX_LISTED_STRIKES) {
+        writeln!(
+            description,
+            "**{}** | {} | {}{}\n> {}",
+            strike.case(),
+            match strike.points.unwrap_or(1) {
+                1 => "1 point".to_string(),
+                points => format!("{points} points"),
+            },
+            match strike.expiry {
+                Some(expiry) => format!("Expires <t:{}:R>", expiry.unix_timestamp()),
+                None => "Never expires".to_string(),
+            },
+            if counted(strike) {
+                ""
+            } else {
+                " | No longer counted"
+            },
+            strike.reason.chars().take(100).collect::<String>()
+        )
+        .unwrap();
+    }
+    if strikes.len() > MAX_LISTED_STRIKES {
+        write!(
+            description,
+            "*{} more active strikes*",
+            strikes.len() - MAX_LISTED_STRIKES
+        )
+        .unwrap();
+    }
+
+    let title = format!("Your standing in {guild_name}");
+    let mut fields = vec![
+        ("Points", points.to_string(), true),
+        (
+            "Current escalation",
+            current_tier.map_or("None".to_string(), |tier| describe_tier(&escalations, tier)),
+            true,
+        ),
+        (
+            "Next escalation",
+            next_tier.map_or("None".to_string(), |tier| {
+                format!(
+                    "{} ({} more)",
+                    describe_tier(&escalations, tier),
+                    tier - points
+                )
+            }),
+            true,
+        ),
+    ];
+    if let Some(window) = strike_window {
+        fields.push((
+            "Strike window",
+            format!(
+                "Only strikes issued after <t:{}:d> count towards escalations",
+                window.unix_timestamp()
+            ),
+            false,
+        ));
+    }
+
+    let length = title.chars().count()
+        + description.chars().count()
+        + fields
+            .iter()
+            .map(|(name, value, _)| name.chars().count() + value.chars().count())
+            .sum::<usize>();
+    let embed = CreateEmbed::new()
+        .title(title)
+        .description(description)
+        .fields(fields)
+        .color(STANDING_COLOR);
+    (embed, length)
+}
+
+pub struct StandingCommand;
+
+#[async_trait::async_trait]
+impl Command for StandingCommand {
+    fn name(&self) -> &'static str {
+        "standing"
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new("standing")
+            .dm_permission(true)
+            .description("View your active strikes and escalations")
+    }
+
+    async fn router(
+        &self,
+        handler: &Handler,
+        ctx: &CommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        if !ctx
+            .user_permissions
+            .contains(&Permission::ModerationSearchSelf)
+        {
+            return Err(ResponseError::Execution(
+                "You do not have permission to do this!",
+                Some(format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationSearchSelf)),
+            ));
+        }
+
+        let (embed, _) = standing_embed(
+            handler,
+            ctx.guild.id.get() as i64,
+            &ctx.guild.name,
+            cmd.user.id.get() as i64,
+        )
+        .await;
+        ctx.reply(cmd, Response::new().embed(embed).ephemeral(true))
+            .await
+    }
+
+    fn dm_enabled(&self) -> bool {
+        true
+    }
+
+    async fn dm_router(
+        &self,
+        handler: &Handler,
+        ctx: &FailedCommandContext,
+        cmd: &CommandInteraction,
+    ) -> ResponseResult {
+        let user_id = cmd.user.id.get() as i64;
+        let guild_ids = match sqlx::query!(
+            "SELECT DISTINCT guild_id FROM actions WHERE user_id = $1 AND action_type = 'strike' AND active = true",
+            user_id
+        )
+        .fetch_all(&handler.main_database)
+        .await
+        {
+            Ok(records) => records.into_iter().map(|record| record.guild_id),
+            Err(err) => {
+                error!("Could not fetch standing guilds, failed with error: {:?}", err);
+                return Err(ResponseError::Execution(
+                    "Could not fetch your standing",
+                    Some("Please try again later.".to_string()),
+                ));
+            }
+        };
+
+        let mut embeds = vec![];
+        let mut length = 0;
+        let mut truncated = false;
+        for guild_id in guild_ids {
+            let guild = GuildId::new(guild_id as u64);
+            let Some(guild_name) = guild
+                .to_guild_cached(&ctx.ctx.cache)
+                .map(|guild| guild.name.clone())
+            else {
+                continue;
+            };
+            if !get_member_permissions(handler, &ctx.ctx, guild, cmd.user.id)
+                .await
+                .contains(&Permission::ModerationSearchSelf)
+            {
+                continue;
+            }
+            let (embed, embed_length) =
+                standing_embed(handler, guild_id, &guild_name, user_id).await;
+            if length + embed_length > MAX_DM_LENGTH {
+                truncated = true;
+                break;
+            }
+            length += embed_length;
+            embeds.push(embed);
+            if embeds.len() == MAX_DM_GUILDS {
+                break;
+            }
+        }
+        if truncated {
+            embeds.push(
+                CreateEmbed::new()
+                    .description("Not every server fits in one message. Use `/standing` in a server to see your standing there.")
+                    .color(STANDING_COLOR),
+            );
+        }
+        if embeds.is_empty() {
+            embeds.push(
+                CreateEmbed::new()
+                    .title("Your standing")
+                    .description("You have no active strikes in any server that uses Reaper")
+                    .color(STANDING_COLOR),
+            );
+        }
+
+        ctx.reply(cmd, Response::new().embeds(embeds)).await
+    }
+}
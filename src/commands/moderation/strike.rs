@@ -1,9 +1,10 @@
+use inflections::Inflect;
 use serenity::{
     all::{ChannelId, CommandInteraction, CommandOptionType, GuildId, UserId},
     builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateMessage},
 };
 use std::time::Instant;
-use tracing::debug;
+use tracing::{debug, error};
 
 use crate::{
     common::{
@@ -11,9 +12,13 @@ use crate::{
         logging::{get_log_channel, LogType},
         options::Options,
     },
-    database::postgres::{actions::get_active_strikes, guild::get_moderation_config},
+    database::postgres::{
+        actions::{get_active_strikes, get_custom_action},
+        guild::get_moderation_config,
+    },
+    events::appeal::get_appeal_components,
     models::{
-        actions::{Action, ActionType, DatabaseActionEscalation},
+        actions::{Action, ActionEscalation, ActionType, EscalationType},
         command::{Command, CommandContext, CommandContextReply},
         config::LoggingConfig,
         handler::Handler,
@@ -25,11 +30,25 @@ use crate::{
 
 pub struct StrikeAction {
     pub strike: Action,
-    pub escalation: Option<Action>,
+    pub escalations: Vec<Action>,
+    pub points: i64,
     pub dm_notified: bool,
 }
 
+/// Returns the highest escalation tier reached by this strike, tiers that were already reached
+/// before it are not run again
+fn crossed_tier(
+    tiers: impl Iterator<Item = i64>,
+    previous_points: i64,
+    total_points: i64,
+) -> Option<i64> {
+    tiers
+        .filter(|tier| previous_points < *tier && *tier <= total_points)
+        .max()
+}
+
 impl Handler {
+    #[allow(clippy::too_many_arguments)]
     pub async fn strike_user(
         &self,
         ctx: &CommandContext,
@@ -38,9 +57,12 @@ impl Handler {
         reason: String,
         moderator_id: Option<i64>,
         duration: Option<Duration>,
+        points: i32,
     ) -> Result<StrikeAction, ResponseError> {
         let start = Instant::now();
 
+        let moderation_config = get_moderation_config(self, guild_id).await;
+
         let duration = if let Some(duration) = duration {
             if duration.permanent {
                 None
@@ -48,11 +70,11 @@ impl Handler {
                 Some(duration)
             }
         } else {
-            let moderation_config = get_moderation_config(self, guild_id).await;
-            let duration = match moderation_config {
+            let duration = match &moderation_config {
                 Some(config) => config
                     .default_strike_duration
-                    .map(|duration| Duration::new(&duration)),
+                    .as_ref()
+                    .map(|duration| Duration::new(duration)),
                 None => None,
             };
             if duration.is_none() {
@@ -71,7 +93,7 @@ impl Handler {
             start.elapsed()
         );
 
-        let action = Action::new(
+        let mut action = Action::new(
             ActionType::Strike,
             user_id,
             moderator_id,
@@ -79,15 +101,32 @@ impl Handler {
             reason,
             duration,
         );
+        action.points = Some(points);
+
+        let strike_window = moderation_config
+            .as_ref()
+            .and_then(|config| config.strike_window)
+            .map(|days| time::OffsetDateTime::now_utc() - time::Duration::days(i64::from(days)));
+        let previous_points = get_active_strikes(self, guild_id, user_id)
+            .await
+            .iter()
+            .filter(|strike| match strike_window {
+                Some(window) => strike.created_at.assume_utc() >= window,
+                None => true,
+            })
+            .map(|strike| i64::from(strike.points.unwrap_or(1)))
+            .sum::<i64>();
+        let total_points = previous_points + i64::from(points);
 
         let mut strike_action = StrikeAction {
             strike: action.clone(),
-            escalation: None,
+            escalations: vec![],
+            points: total_points,
             dm_notified: false,
         };
 
         let guild_escalations = match sqlx::query_as!(
-            DatabaseActionEscalation,
+            ActionEscalation,
             "SELECT * FROM strike_escalations WHERE guild_id = $1",
             guild_id
         )
@@ -98,84 +137,110 @@ impl Handler {
             Err(_) => vec![],
         };
 
-        if !guild_escalations.is_empty() {
-            let strike_count = get_active_strikes(self, guild_id, user_id).await.len();
-            if let Some(escalation) = guild_escalations
+        if let Some(tier) = crossed_tier(
+            guild_escalations.iter().map(|escalation| escalation.points),
+            previous_points,
+            total_points,
+        ) {
+            let mut tier_escalations = guild_escalations
                 .iter()
-                .find(|escalation| escalation.strike_count == (strike_count + 1) as i64)
-            {
-                match ActionType::from(escalation.action_type.as_str()) {
-                    ActionType::Strike => {
-                        return Err(ResponseError::Execution(
-                            "Strike escalation action type is strike!",
-                            Some("This should not happen, please contact a developer.".to_string()),
-                        ))
-                    }
-                    ActionType::Kick => {
-                        if let Ok(escalation) = self
-                            .kick_user(
-                                ctx,
-                                guild_id,
-                                user_id,
-                                format!(
-                                    "Strike escalation (reached {} strikes)",
-                                    escalation.strike_count
-                                ),
-                                None,
-                            )
-                            .await
-                        {
-                            strike_action.escalation = Some(escalation.action);
+                .filter(|escalation| escalation.points == tier)
+                .collect::<Vec<_>>();
+            tier_escalations.sort_by_key(|escalation| match escalation.action_type {
+                EscalationType::Warn => 0,
+                EscalationType::Alert => 2,
+                _ => 1,
+            });
+
+            let escalation_reason = format!("Strike escalation (reached {total_points} points)");
+            for escalation in tier_escalations {
+                let duration = escalation
+                    .action_duration
+                    .as_ref()
+                    .map(|duration| Duration::new(duration));
+                let result = match escalation.action_type {
+                    EscalationType::Kick => self
+                        .kick_user(ctx, guild_id, user_id, escalation_reason.clone(), None)
+                        .await
+                        .map(|action| Some(action.action)),
+                    EscalationType::Mute => {
+                        let Some(duration) = duration else {
+                            error!(
+                                "Skipped mute strike escalation for user {user_id} in guild {guild_id}: no duration was configured"
+                            );
+                            continue;
                         };
+                        self.mute_user(
+                            ctx,
+                            guild_id,
+                            user_id,
+                            escalation_reason.clone(),
+                            None,
+                            duration,
+                        )
+                        .await
+                        .map(|action| Some(action.action))
                     }
-                    ActionType::Mute => {
-                        let Some(duration) = &escalation.action_duration else {
-                            return Err(ResponseError::Execution(
-                                "Strike escalation mute did not provide a duration",
-                                Some(
-                                    "This should not happen, please contact a developer."
-                                        .to_string(),
-                                ),
-                            ));
-                        };
-                        if let Ok(escalation) = self
-                            .mute_user(
-                                ctx,
-                                guild_id,
-                                user_id,
-                                format!(
-                                    "Strike escalation (reached {} strikes)",
-                                    escalation.strike_count
-                                ),
-                                None,
-                                Duration::new(duration),
-                            )
-                            .await
-                        {
-                            strike_action.escalation = Some(escalation.action);
+                    EscalationType::Ban => self
+                        .ban_user(
+                            ctx,
+                            guild_id,
+                            user_id,
+                            escalation_reason.clone(),
+                            None,
+                            duration,
+                        )
+                        .await
+                        .map(|action| Some(action.action)),
+                    EscalationType::Custom => {
+                        let Some(custom_action) = (match &escalation.custom_action {
+                            Some(name) => get_custom_action(self, guild_id, name).await,
+                            None => None,
+                        }) else {
+                            error!(
+                                "Skipped custom strike escalation for user {user_id} in guild {guild_id}: the punishment no longer exists"
+                            );
+                            continue;
                         };
+                        self.punish_user(
+                            ctx,
+                            guild_id,
+                            user_id,
+                            &custom_action,
+                            escalation_reason.clone(),
+                            None,
+                            duration,
+                        )
+                        .await
+                        .map(|action| Some(action.action))
                     }
-                    ActionType::Ban => {
-                        if let Ok(escalation) = self
-                            .ban_user(
-                                ctx,
-                                guild_id,
-                                user_id,
-                                format!(
-                                    "Strike escalation (reached {} strikes)",
-                                    escalation.strike_count
-                                ),
-                                None,
-                                escalation
-                                    .action_duration
-                                    .as_ref()
-                                    .map(|duration| Duration::new(duration)),
-                            )
-                            .await
-                        {
-                            strike_action.escalation = Some(escalation.action);
-                        };
+                    EscalationType::Warn => {
+                        self.warn_user(ctx, guild_id, user_id, escalation, total_points)
+                            .await;
+                        Ok(None)
+                    }
+                    EscalationType::Alert => {
+                        self.alert_escalation(
+                            guild_id,
+                            user_id,
+                            escalation,
+                            &strike_action.escalations,
+                            moderation_config
+                                .as_ref()
+                                .and_then(|config| config.escalation_channel),
+                            total_points,
+                        )
+                        .await;
+                        Ok(None)
                     }
+                };
+                match result {
+                    Ok(Some(action)) => strike_action.escalations.push(action),
+                    Ok(None) => {}
+                    Err(err) => error!(
+                        "Failed to run {} strike escalation for user {user_id} in guild {guild_id}: {:?}",
+                        escalation.action_type, err
+                    ),
                 }
             }
         }
@@ -203,28 +268,25 @@ impl Handler {
                 },
                 true,
             ),
+            ("Points", format!("{points} ({total_points} total)"), true),
         ];
 
-        let log_message = match sqlx::query_as!(
+        if let Ok(config) = sqlx::query_as!(
             LoggingConfig,
-            "SELECT log_actions, log_messages, log_voice, log_channel, log_action_channel, log_message_channel, log_voice_channel FROM logging_configuration WHERE guild_id = $1",
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
             guild_id
         )
         .fetch_one(&self.main_database)
         .await {
-            Ok(config) => {
-                get_log_channel(self, &config, &LogType::Action).await
-                    .map(|channel| {
-                        ChannelId::new(channel as u64)
-                            .send_message(
-                                &ctx.ctx,
-                                CreateMessage::new()
-                                    .embed(CreateEmbed::new().title("Strike issued").description(format!("<@{}> has been issued a strike", action.user_id)).fields(fields.clone()).footer(CreateEmbedFooter::new(format!("User {} striked | UUID: {}", action.user_id, action.get_id()))).color(0xeb966d))
-                            )
-                    })
-            },
-            Err(_) => None
-        };
+            if let Some(channel) = get_log_channel(self, &config, &LogType::Action).await {
+                self.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title("Strike issued").description(format!("<@{}> has been issued a strike", action.user_id)).fields(fields.clone()).footer(CreateEmbedFooter::new(format!("User {} striked | {} | UUID: {}", action.user_id, action.case(), action.get_id()))).color(0xeb966d),
+                );
+            }
+        }
+
+        let appeal_components = get_appeal_components(self, guild_id, &action.get_id()).await;
 
         let dm_channel =
             if sqlx::query!("SELECT active FROM global_kills WHERE feature = 'commands.dm'")
@@ -244,34 +306,32 @@ impl Handler {
                 None
             };
 
-        if let Some(Ok(channel)) = match (log_message, dm_channel) {
-            (Some(log_future), Some(dm_channel)) => Some(tokio::join!(log_future, dm_channel).1),
-            (None, Some(dm_channel)) => Some(dm_channel.await),
-            (Some(log_future), None) => {
-                let _ = log_future.await;
-                None
-            }
-            (None, None) => None,
+        if let Some(Ok(channel)) = match dm_channel {
+            Some(dm_channel) => Some(dm_channel.await),
+            None => None,
         } {
             strike_action.dm_notified = channel
                 .send_message(
                     &ctx.ctx,
-                    CreateMessage::new().embed(
-                        CreateEmbed::new()
-                            .title("Strike received")
-                            .description(match GuildId::new(guild_id as u64).name(&ctx.ctx) {
-                                Some(guild_name) => {
-                                    format!("You've been issued a strike in {guild_name}")
-                                }
-                                None => "A server has issued you a strike".to_string(),
-                            })
-                            .fields(fields)
-                            .footer(CreateEmbedFooter::new(format!(
-                                "If you wish to appeal, please refer to the following action ID: {}",
-                                action.get_id()
-                            )))
-                            .color(0xeb966d),
-                    ),
+                    CreateMessage::new()
+                        .embed(
+                            CreateEmbed::new()
+                                .title("Strike received")
+                                .description(match GuildId::new(guild_id as u64).name(&ctx.ctx) {
+                                    Some(guild_name) => {
+                                        format!("You've been issued a strike in {guild_name}")
+                                    }
+                                    None => "A server has issued you a strike".to_string(),
+                                })
+                                .fields(fields)
+                                .footer(CreateEmbedFooter::new(format!(
+                                    "If you wish to appeal, please refer to {} (action ID: {})",
+                                    action.case(),
+                                    action.get_id()
+                                )))
+                                .color(0xeb966d),
+                        )
+                        .components(appeal_components),
                 )
                 .await
                 .is_ok();
@@ -281,6 +341,111 @@ impl Handler {
 
         Ok(strike_action)
     }
+
+    async fn warn_user(
+        &self,
+        ctx: &CommandContext,
+        guild_id: i64,
+        user_id: i64,
+        escalation: &ActionEscalation,
+        total_points: i64,
+    ) {
+        if !sqlx::query!("SELECT active FROM global_kills WHERE feature = 'commands.dm'")
+            .fetch_one(&self.main_database)
+            .await
+            .unwrap()
+            .active
+            || ctx
+                .ctx
+                .http
+                .get_member(GuildId::new(guild_id as u64), UserId::new(user_id as u64))
+                .await
+                .is_err()
+        {
+            return;
+        }
+
+        let Ok(channel) = UserId::new(user_id as u64)
+            .create_dm_channel(&ctx.ctx.http)
+            .await
+        else {
+            return;
+        };
+        if let Err(err) = channel
+            .send_message(
+                &ctx.ctx,
+                CreateMessage::new().embed(
+                    CreateEmbed::new()
+                        .title("Warning")
+                        .description(match &escalation.message {
+                            Some(message) => message.clone(),
+                            None => match GuildId::new(guild_id as u64).name(&ctx.ctx) {
+                                Some(guild_name) => format!("You have reached {total_points} strike points in {guild_name}, further strikes may result in harsher punishments"),
+                                None => format!("You have reached {total_points} strike points in a server, further strikes may result in harsher punishments"),
+                            },
+                        })
+                        .color(0xeb966d),
+                ),
+            )
+            .await
+        {
+            error!("Failed to send strike escalation warning: {}", err);
+        }
+    }
+
+    async fn alert_escalation(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        escalation: &ActionEscalation,
+        actions: &[Action],
+        escalation_channel: Option<i64>,
+        total_points: i64,
+    ) {
+        let channel = match escalation_channel {
+            Some(channel) => Some(channel),
+            None => match sqlx::query_as!(
+                LoggingConfig,
+                "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
+                guild_id
+            )
+            .fetch_one(&self.main_database)
+            .await {
+                Ok(config) => get_log_channel(self, &config, &LogType::Action).await,
+                Err(_) => None,
+            },
+        };
+        let Some(channel) = channel else {
+            return;
+        };
+
+        let mut embed = CreateEmbed::new()
+            .title("Strike escalation")
+            .description(format!(
+                "<@{user_id}> has reached **{total_points}** points, triggering the **{}** point escalation",
+                escalation.points
+            ))
+            .field(
+                "Actions taken",
+                if actions.is_empty() {
+                    "None".to_string()
+                } else {
+                    actions
+                        .iter()
+                        .map(|action| format!("{} ({})", action.type_name().to_title_case(), action.case()))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                },
+                false,
+            )
+            .color(0xeb966d);
+        if let Some(message) = &escalation.message {
+            embed = embed.field("Note", message.clone(), false);
+        }
+
+        self.log_dispatcher
+            .dispatch(ChannelId::new(channel as u64), embed);
+    }
 }
 
 pub struct StrikeCommand;
@@ -315,6 +480,16 @@ impl Command for StrikeCommand {
                 )
                 .required(false),
             )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "points",
+                    "How many points the strike is worth, defaults to 1",
+                )
+                .min_int_value(1)
+                .max_int_value(100)
+                .required(false),
+            )
     }
 
     async fn router(
@@ -356,6 +531,10 @@ impl Command for StrikeCommand {
             .into_owned()
             .as_deref()
             .map(Duration::new);
+        let points = options
+            .get_integer("points")
+            .and_then(|points| i32::try_from(points).ok())
+            .unwrap_or(1);
 
         let target_user_highest_role = get_highest_role(ctx, &user).await;
         if ctx.highest_role <= target_user_highest_role {
@@ -375,6 +554,7 @@ impl Command for StrikeCommand {
             reason.clone(),
             Some(cmd.user.id.get() as i64),
             duration,
+            points,
         ))
         .await?;
 
@@ -402,8 +582,14 @@ impl Command for StrikeCommand {
                         },
                         true,
                     )
+                    .field(
+                        "Points",
+                        format!("{points} ({} total)", action.points),
+                        true,
+                    )
                     .footer(CreateEmbedFooter::new(format!(
-                        "UUID: {} | Total execution time: {:?}",
+                        "{} | UUID: {} | Total execution time: {:?}",
+                        action.strike.case(),
                         action.strike.get_id(),
                         start.elapsed()
                     )))
@@ -413,3 +599,31 @@ impl Command for StrikeCommand {
         .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::crossed_tier;
+
+    #[test]
+    fn fires_the_tier_reached_by_the_strike() {
+        assert_eq!(crossed_tier([3, 5, 10].into_iter(), 2, 3), Some(3));
+        assert_eq!(crossed_tier([3, 5, 10].into_iter(), 4, 6), Some(5));
+    }
+
+    #[test]
+    fn fires_the_highest_tier_when_several_are_crossed() {
+        assert_eq!(crossed_tier([3, 5, 10].into_iter(), 0, 12), Some(10));
+    }
+
+    #[test]
+    fn does_not_repeat_a_tier_that_was_already_reached() {
+        assert_eq!(crossed_tier([3, 5, 10].into_iter(), 10, 11), None);
+        assert_eq!(crossed_tier([3, 5, 10].into_iter(), 5, 7), None);
+    }
+
+    #[test]
+    fn no_tiers_below_the_total() {
+        assert_eq!(crossed_tier([3, 5].into_iter(), 0, 2), None);
+        assert_eq!(crossed_tier(std::iter::empty(), 0, 5), None);
+    }
+}
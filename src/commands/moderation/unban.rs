@@ -1,6 +1,6 @@
 use serenity::{
     all::{ChannelId, CommandInteraction, CommandOptionType},
-    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateMessage},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter},
 };
 use std::time::Instant;
 use tracing::error;
@@ -107,30 +107,21 @@ impl Command for UnbanCommand {
             ),
         );
 
-        let log = if let Ok(config) = sqlx::query_as!(
+        if let Ok(config) = sqlx::query_as!(
             LoggingConfig,
-            "SELECT log_actions, log_messages, log_voice, log_channel, log_action_channel, log_message_channel, log_voice_channel FROM logging_configuration WHERE guild_id = $1",
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
             cmd.guild_id.unwrap().get() as i64
         )
         .fetch_one(&handler.main_database)
         .await {
             if let Some(channel) = get_log_channel(handler, &config, &LogType::Action).await {
-                Some(ChannelId::new(channel as u64)
-                    .send_message(
-                        &ctx.ctx,
-                        CreateMessage::new()
-                            .embed(CreateEmbed::new().title("User unbanned").description(format!("<@{}> has been unbanned", user.id.get())).footer(CreateEmbedFooter::new(format!("User {} unbanned", user.id.get()))).color(0x0abfd6))
-                ))
-            } else {
-                None
+                handler.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title("User unbanned").description(format!("<@{}> has been unbanned", user.id.get())).footer(CreateEmbedFooter::new(format!("User {} unbanned", user.id.get()))).color(0x0abfd6),
+                );
             }
-        } else {
-            None
-        };
-
-        match log {
-            Some(log_future) => tokio::join!(log_future, reply).1,
-            None => reply.await,
         }
+
+        reply.await
     }
 }
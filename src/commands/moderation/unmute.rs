@@ -1,6 +1,6 @@
 use serenity::{
     all::{ChannelId, CommandInteraction, CommandOptionType, RoleId},
-    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter, CreateMessage},
+    builder::{CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter},
 };
 use std::time::Instant;
 use tracing::error;
@@ -9,6 +9,7 @@ use crate::{
     common::{
         logging::{get_log_channel, LogType},
         options::Options,
+        timeout::remove_timeout,
     },
     database::postgres::guild::get_moderation_config,
     models::{
@@ -67,9 +68,10 @@ impl Command for UnmuteCommand {
         let mute_role = match get_moderation_config(handler, cmd.guild_id.unwrap().get() as i64)
             .await
         {
-            Some(config) => match config.mute_role {
-                Some(id) => RoleId::new(id as u64),
-                None => {
+            Some(config) => match (config.use_timeouts, config.mute_role) {
+                (true, _) => None,
+                (false, Some(id)) => Some(RoleId::new(id as u64)),
+                (false, None) => {
                     return Err(ResponseError::Execution(
                         "Could not find mute role!",
                         Some(
@@ -90,21 +92,23 @@ impl Command for UnmuteCommand {
             }
         };
 
-        if let Err(err) = ctx
-            .ctx
-            .http
-            .remove_member_role(
-                cmd.guild_id.unwrap(),
-                user.id,
-                mute_role,
-                Some(&format!(
-                    "Unmute by {} ({})",
-                    cmd.user.name,
-                    cmd.user.id.get()
-                )),
-            )
-            .await
-        {
+        let audit_reason = format!("Unmute by {} ({})", cmd.user.name, cmd.user.id.get());
+        let unmute_result = match mute_role {
+            Some(mute_role) => {
+                ctx.ctx
+                    .http
+                    .remove_member_role(
+                        cmd.guild_id.unwrap(),
+                        user.id,
+                        mute_role,
+                        Some(&audit_reason),
+                    )
+                    .await
+            }
+            None => remove_timeout(&ctx.ctx, cmd.guild_id.unwrap(), user.id, &audit_reason).await,
+        };
+
+        if let Err(err) = unmute_result {
             error!(
                 "Could not unmute user {} in guild {}. Failed with error: {:?}",
                 user.id.get(),
@@ -113,7 +117,11 @@ impl Command for UnmuteCommand {
             );
             return Err(ResponseError::Execution(
                 "Could not unmute user",
-                Some("The user could not be unmuted. This could be because they currently do not have the mute role. Please double check before trying again".to_string()),
+                Some(if mute_role.is_some() {
+                    "The user could not be unmuted. This could be because they currently do not have the mute role. Please double check before trying again".to_string()
+                } else {
+                    "The user could not be unmuted. This could be because they are no longer in the server. Please double check before trying again".to_string()
+                }),
             ));
         }
 
@@ -135,30 +143,21 @@ impl Command for UnmuteCommand {
             ),
         );
 
-        let log = if let Ok(config) = sqlx::query_as!(
+        if let Ok(config) = sqlx::query_as!(
             LoggingConfig,
-            "SELECT log_actions, log_messages, log_voice, log_channel, log_action_channel, log_message_channel, log_voice_channel FROM logging_configuration WHERE guild_id = $1",
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
             cmd.guild_id.unwrap().get() as i64
         )
         .fetch_one(&handler.main_database)
         .await {
             if let Some(channel) = get_log_channel(handler, &config, &LogType::Action).await {
-                Some(ChannelId::new(channel as u64)
-                    .send_message(
-                        &ctx.ctx,
-                        CreateMessage::new()
-                            .embed(CreateEmbed::new().title("User unmuted").description(format!("<@{}> has been unmuted", user.id.get())).footer(CreateEmbedFooter::new(format!("User {} unmuted", user.id.get()))).color(0xd1bfba))
-                ))
-            } else {
-                None
+                handler.log_dispatcher.dispatch(
+                    ChannelId::new(channel as u64),
+                    CreateEmbed::new().title("User unmuted").description(format!("<@{}> has been unmuted", user.id.get())).footer(CreateEmbedFooter::new(format!("User {} unmuted", user.id.get()))).color(0xd1bfba),
+                );
             }
-        } else {
-            None
-        };
-
-        match log {
-            Some(log_future) => tokio::join!(log_future, reply).1,
-            None => reply.await,
         }
+
+        reply.await
     }
 }
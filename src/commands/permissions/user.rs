@@ -30,27 +30,35 @@ use crate::{
 };
 
 fn create_components(permissions: &[Permission]) -> Vec<CreateActionRow> {
-    vec![
-        CreateActionRow::SelectMenu(CreateSelectMenu::new(
-            "permissions".to_string(),
-            CreateSelectMenuKind::String {
-                options: Permission::iter()
-                    .map(|permission| {
-                        let label = if permissions.contains(&permission) {
-                            format!("Remove {permission}")
-                        } else {
-                            format!("Add {permission}")
-                        };
+    // Discord caps select menus at 25 options, so permissions are split across several menus
+    let mut components = Permission::iter()
+        .collect::<Vec<_>>()
+        .chunks(25)
+        .enumerate()
+        .map(|(index, chunk)| {
+            CreateActionRow::SelectMenu(CreateSelectMenu::new(
+                format!("permissions_{index}"),
+                CreateSelectMenuKind::String {
+                    options: chunk
+                        .iter()
+                        .map(|permission| {
+                            let label = if permissions.contains(permission) {
+                                format!("Remove {permission}")
+                            } else {
+                                format!("Add {permission}")
+                            };
 
-                        CreateSelectMenuOption::new(label, permission.to_string())
-                    })
-                    .collect(),
-            },
-        )),
-        CreateActionRow::Buttons(vec![CreateButton::new("done")
-            .emoji('✅')
-            .style(ButtonStyle::Success)]),
-    ]
+                            CreateSelectMenuOption::new(label, permission.to_string())
+                        })
+                        .collect(),
+                },
+            ))
+        })
+        .collect::<Vec<_>>();
+    components.push(CreateActionRow::Buttons(vec![CreateButton::new("done")
+        .emoji('✅')
+        .style(ButtonStyle::Success)]));
+    components
 }
 
 pub async fn user(
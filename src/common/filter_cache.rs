@@ -0,0 +1,42 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::models::filter::CompiledFilterRule;
+
+/// Compiled filter rules per guild, so messages aren't checked against a fresh query and
+/// recompiled patterns every time
+pub struct FilterCache {
+    guilds: Mutex<HashMap<i64, Arc<Vec<CompiledFilterRule>>>>,
+}
+
+impl FilterCache {
+    pub fn new() -> Self {
+        FilterCache {
+            guilds: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, guild_id: i64) -> Option<Arc<Vec<CompiledFilterRule>>> {
+        self.guilds.lock().unwrap().get(&guild_id).cloned()
+    }
+
+    pub fn insert(
+        &self,
+        guild_id: i64,
+        rules: Vec<CompiledFilterRule>,
+    ) -> Arc<Vec<CompiledFilterRule>> {
+        let rules = Arc::new(rules);
+        self.guilds
+            .lock()
+            .unwrap()
+            .insert(guild_id, Arc::clone(&rules));
+        rules
+    }
+
+    /// Must be called whenever a guild's filter rules are changed
+    pub fn invalidate(&self, guild_id: i64) {
+        self.guilds.lock().unwrap().remove(&guild_id);
+    }
+}
@@ -0,0 +1,537 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use serde_derive::{Deserialize, Serialize};
+use serenity::{
+    all::{ChannelId, HttpError, UserId, WebhookId},
+    builder::{CreateAttachment, CreateEmbed, CreateWebhook},
+    prelude::Context,
+};
+use tokio::task::JoinSet;
+use tracing::{debug, error, warn};
+
+use crate::models::handler::Handler;
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+const MAX_BATCH_EMBEDS: usize = 10;
+// Discord caps the combined text of all embeds in a message at 6000 characters,
+// the serialized size is always larger so this stays on the safe side
+const MAX_BATCH_SIZE: usize = 6000;
+const MAX_ATTEMPTS: u32 = 5;
+const RETRY_BATCH: usize = 50;
+// Doubled on every failed attempt
+const RETRY_BASE_DELAY_SECONDS: i64 = 5;
+const WEBHOOK_NAME: &str = "Reaper Logs";
+
+// Sorted set of serialized batches, scored by the unix time they may be retried at
+fn retry_key() -> String {
+    "log_dispatcher:retries".to_string()
+}
+
+fn retry_delay(attempts: u32) -> i64 {
+    RETRY_BASE_DELAY_SECONDS << attempts.saturating_sub(1).min(10)
+}
+
+/// Rate limits, server errors and network failures may succeed later, anything else, like a
+/// missing permission or an invalid payload, fails the same way every time
+fn is_transient(err: &serenity::Error) -> bool {
+    match err {
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response)) => {
+            is_transient_status(response.status_code.as_u16())
+        }
+        serenity::Error::Http(HttpError::Request(_)) => true,
+        _ => false,
+    }
+}
+
+fn is_transient_status(status: u16) -> bool {
+    status == 429 || status >= 500
+}
+
+struct QueuedLog {
+    mention: Option<UserId>,
+    embed: serde_json::Value,
+    size: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LogBatch {
+    channel_id: ChannelId,
+    content: Option<String>,
+    embeds: Vec<serde_json::Value>,
+    attempts: u32,
+}
+
+impl LogBatch {
+    fn payload(&self) -> serde_json::Value {
+        let mut payload = serde_json::json!({
+            "embeds": self.embeds,
+            "allowed_mentions": { "parse": [] },
+        });
+        if let Some(content) = &self.content {
+            payload["content"] = serde_json::Value::String(content.clone());
+        }
+        payload
+    }
+}
+
+pub struct LogDispatcher {
+    pending: Mutex<HashMap<ChannelId, Vec<QueuedLog>>>,
+    webhooks: Mutex<HashMap<ChannelId, (WebhookId, String)>>,
+    started: AtomicBool,
+}
+
+impl LogDispatcher {
+    pub fn new() -> Self {
+        LogDispatcher {
+            pending: Mutex::new(HashMap::new()),
+            webhooks: Mutex::new(HashMap::new()),
+            started: AtomicBool::new(false),
+        }
+    }
+
+    /// Queues a log embed for the channel, it is delivered on the next flush
+    pub fn dispatch(&self, channel: ChannelId, embed: CreateEmbed) {
+        self.queue(channel, None, embed);
+    }
+
+    /// Same as `dispatch`, but also mentions the user in the message content without pinging them
+    pub fn dispatch_mention(&self, channel: ChannelId, user_id: UserId, embed: CreateEmbed) {
+        self.queue(channel, Some(user_id), embed);
+    }
+
+    fn queue(&self, channel: ChannelId, mention: Option<UserId>, embed: CreateEmbed) {
+        let embed = match serde_json::to_value(embed) {
+            Ok(embed) => embed,
+            Err(err) => {
+                error!("Failed to serialize log embed: {:?}", err);
+                return;
+            }
+        };
+        let size = embed.to_string().len();
+        self.pending
+            .lock()
+            .unwrap()
+            .entry(channel)
+            .or_default()
+            .push(QueuedLog {
+                mention,
+                embed,
+                size,
+            });
+    }
+
+    /// Returns true only for the first caller, so the flush loop is spawned once across reconnects
+    pub fn start(&self) -> bool {
+        !self.started.swap(true, Ordering::SeqCst)
+    }
+
+    fn take_batches(&self) -> HashMap<ChannelId, Vec<LogBatch>> {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        pending
+            .into_iter()
+            .map(|(channel_id, logs)| {
+                let mut batches: Vec<(Vec<QueuedLog>, usize)> = vec![];
+                for log in logs {
+                    match batches.last_mut() {
+                        Some((batch, size))
+                            if batch.len() < MAX_BATCH_EMBEDS
+                                && *size + log.size <= MAX_BATCH_SIZE =>
+                        {
+                            *size += log.size;
+                            batch.push(log);
+                        }
+                        _ => {
+                            let size = log.size;
+                            batches.push((vec![log], size));
+                        }
+                    }
+                }
+                let batches = batches
+                    .into_iter()
+                    .map(|(logs, _)| {
+                        let mentions = logs
+                            .iter()
+                            .filter_map(|log| log.mention.map(|user_id| format!("<@{user_id}>")))
+                            .collect::<Vec<_>>();
+                        LogBatch {
+                            channel_id,
+                            content: (!mentions.is_empty()).then(|| mentions.join(" ")),
+                            embeds: logs.into_iter().map(|log| log.embed).collect(),
+                            attempts: 0,
+                        }
+                    })
+                    .collect();
+                (channel_id, batches)
+            })
+            .collect()
+    }
+
+    async fn webhook(
+        &self,
+        ctx: &Context,
+        channel: ChannelId,
+    ) -> Result<(WebhookId, String), serenity::Error> {
+        if let Some(webhook) = self.webhooks.lock().unwrap().get(&channel) {
+            return Ok(webhook.clone());
+        }
+
+        let current_user = ctx.cache.current_user().id;
+        let existing = channel
+            .webhooks(&ctx.http)
+            .await?
+            .into_iter()
+            .find(|webhook| {
+                webhook.token.is_some()
+                    && webhook
+                        .user
+                        .as_ref()
+                        .is_some_and(|user| user.id == current_user)
+            });
+        let webhook = if let Some(webhook) = existing {
+            webhook
+        } else {
+            debug!("Creating log webhook in channel {}", channel);
+            channel
+                .create_webhook(&ctx.http, CreateWebhook::new(WEBHOOK_NAME))
+                .await?
+        };
+        // The token is only exposed through the execute URL, which ends with it
+        let url = webhook.url()?;
+        let Some(token) = url.rsplit('/').next() else {
+            return Err(serenity::Error::Other("Log webhook has no token"));
+        };
+        let webhook = (webhook.id, token.to_string());
+        self.webhooks
+            .lock()
+            .unwrap()
+            .insert(channel, webhook.clone());
+        Ok(webhook)
+    }
+
+    async fn deliver(
+        &self,
+        ctx: &Context,
+        channel_id: ChannelId,
+        payload: &serde_json::Value,
+        files: Vec<CreateAttachment>,
+        use_webhooks: bool,
+    ) -> Result<(), serenity::Error> {
+        if use_webhooks {
+            // A webhook deleted by someone is recreated once before falling back
+            for _ in 0..2 {
+                let (webhook_id, token) = match self.webhook(ctx, channel_id).await {
+                    Ok(webhook) => webhook,
+                    Err(err) => {
+                        // Usually a missing Manage Webhooks permission, the bot can still post itself
+                        warn!(
+                            "Failed to get log webhook for channel {}, sending directly: {}",
+                            channel_id, err
+                        );
+                        break;
+                    }
+                };
+                match ctx
+                    .http
+                    .execute_webhook(webhook_id, None, &token, false, files.clone(), payload)
+                    .await
+                {
+                    Ok(_) => return Ok(()),
+                    Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(response)))
+                        if response.status_code.as_u16() == 404 =>
+                    {
+                        self.webhooks.lock().unwrap().remove(&channel_id);
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+
+        ctx.http
+            .send_message(channel_id, files, payload)
+            .await
+            .map(|_| ())
+    }
+}
+
+async fn queue_retry(handler: &Handler, mut batch: LogBatch, err: &serenity::Error) {
+    if !is_transient(err) {
+        error!(
+            "Dropping {} logs for channel {} after a permanent error: {}",
+            batch.embeds.len(),
+            batch.channel_id,
+            err
+        );
+        return;
+    }
+    batch.attempts += 1;
+    if batch.attempts >= MAX_ATTEMPTS {
+        error!(
+            "Dropping {} logs for channel {} after {} attempts",
+            batch.embeds.len(),
+            batch.channel_id,
+            batch.attempts
+        );
+        return;
+    }
+
+    let serialized = match serde_json::to_string(&batch) {
+        Ok(serialized) => serialized,
+        Err(err) => {
+            error!("Failed to serialize log batch: {:?}", err);
+            return;
+        }
+    };
+    let mut connection = match handler
+        .redis_database
+        .get_multiplexed_async_connection()
+        .await
+    {
+        Ok(connection) => connection,
+        Err(err) => {
+            error!("Failed to get Redis connection: {:?}", err);
+            return;
+        }
+    };
+    let retry_at = time::OffsetDateTime::now_utc().unix_timestamp() + retry_delay(batch.attempts);
+    if let Err(err) = redis::cmd("ZADD")
+        .arg(retry_key())
+        .arg(retry_at)
+        .arg(serialized)
+        .query_async::<_, ()>(&mut connection)
+        .await
+    {
+        error!("Failed to queue log batch for retry: {:?}", err);
+    }
+}
+
+async fn take_retries(handler: &Handler) -> Vec<LogBatch> {
+    let mut connection = match handler
+        .redis_database
+        .get_multiplexed_async_connection()
+        .await
+    {
+        Ok(connection) => connection,
+        Err(err) => {
+            error!("Failed to get Redis connection: {:?}", err);
+            return vec![];
+        }
+    };
+    let retries: Vec<String> = match redis::cmd("ZRANGEBYSCORE")
+        .arg(retry_key())
+        .arg("-inf")
+        .arg(time::OffsetDateTime::now_utc().unix_timestamp())
+        .arg("LIMIT")
+        .arg(0)
+        .arg(RETRY_BATCH)
+        .query_async(&mut connection)
+        .await
+    {
+        Ok(retries) => retries,
+        Err(err) => {
+            error!("Failed to get log retries from Redis: {:?}", err);
+            return vec![];
+        }
+    };
+    if retries.is_empty() {
+        return vec![];
+    }
+    if let Err(err) = redis::cmd("ZREM")
+        .arg(retry_key())
+        .arg(&retries)
+        .query_async::<_, ()>(&mut connection)
+        .await
+    {
+        error!("Failed to remove log retries from Redis: {:?}", err);
+        return vec![];
+    }
+    retries
+        .into_iter()
+        .filter_map(|retry| match serde_json::from_str(&retry) {
+            Ok(batch) => Some(batch),
+            Err(err) => {
+                error!("Failed to deserialize log batch: {:?}", err);
+                None
+            }
+        })
+        .collect()
+}
+
+async fn webhooks_active(handler: &Handler) -> bool {
+    sqlx::query!("SELECT active FROM global_kills WHERE feature = 'logging.webhooks'")
+        .fetch_one(&handler.main_database)
+        .await
+        .map_or(true, |record| record.active)
+}
+
+/// Sends a log with a file right away, files are too large to batch or keep in the retry queue
+pub async fn send_log_file(
+    handler: &Handler,
+    ctx: &Context,
+    channel: ChannelId,
+    embed: CreateEmbed,
+    file: CreateAttachment,
+) {
+    let payload = serde_json::json!({
+        "embeds": [embed],
+        "allowed_mentions": { "parse": [] },
+        "attachments": [{ "id": 0, "filename": file.filename }],
+    });
+    let use_webhooks = webhooks_active(handler).await;
+    if let Err(err) = handler
+        .log_dispatcher
+        .deliver(ctx, channel, &payload, vec![file], use_webhooks)
+        .await
+    {
+        error!("Failed to send log file to channel {}: {}", channel, err);
+    }
+}
+
+pub async fn run_log_dispatcher(handler: Handler, ctx: Context) {
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        // Retries go first so a channel's logs stay roughly in order
+        let mut batches: HashMap<ChannelId, Vec<LogBatch>> = HashMap::new();
+        for retry in take_retries(&handler).await {
+            batches.entry(retry.channel_id).or_default().push(retry);
+        }
+        for (channel_id, channel_batches) in handler.log_dispatcher.take_batches() {
+            batches
+                .entry(channel_id)
+                .or_default()
+                .extend(channel_batches);
+        }
+        if batches.is_empty() {
+            continue;
+        }
+        let use_webhooks = webhooks_active(&handler).await;
+
+        // Channels are delivered concurrently, so one rate limited channel doesn't hold up the rest
+        let mut deliveries = JoinSet::new();
+        for channel_batches in batches.into_values() {
+            let handler = handler.clone();
+            let ctx = ctx.clone();
+            deliveries.spawn(async move {
+                for batch in channel_batches {
+                    if let Err(err) = handler
+                        .log_dispatcher
+                        .deliver(
+                            &ctx,
+                            batch.channel_id,
+                            &batch.payload(),
+                            vec![],
+                            use_webhooks,
+                        )
+                        .await
+                    {
+                        error!(
+                            "Failed to deliver logs to channel {}: {}",
+                            batch.channel_id, err
+                        );
+                        queue_retry(&handler, batch, &err).await;
+                    }
+                }
+            });
+        }
+        while deliveries.join_next().await.is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serenity::{
+        all::{ChannelId, UserId},
+        builder::CreateEmbed,
+    };
+
+    use super::{is_transient_status, retry_delay, LogDispatcher, MAX_BATCH_EMBEDS};
+
+    #[test]
+    fn batches_up_to_ten_embeds() {
+        let dispatcher = LogDispatcher::new();
+        let channel = ChannelId::new(1);
+        for index in 0..25 {
+            dispatcher.dispatch(channel, CreateEmbed::new().title(index.to_string()));
+        }
+
+        let batches = dispatcher.take_batches().remove(&channel).unwrap();
+        assert_eq!(
+            batches
+                .iter()
+                .map(|batch| batch.embeds.len())
+                .collect::<Vec<_>>(),
+            vec![MAX_BATCH_EMBEDS, MAX_BATCH_EMBEDS, 5]
+        );
+        assert_eq!(batches[0].embeds[0]["title"], "0");
+        assert_eq!(batches[2].embeds[4]["title"], "24");
+        assert!(dispatcher.take_batches().is_empty());
+    }
+
+    #[test]
+    fn splits_batches_by_size() {
+        let dispatcher = LogDispatcher::new();
+        let channel = ChannelId::new(1);
+        for _ in 0..3 {
+            dispatcher.dispatch(channel, CreateEmbed::new().description("a".repeat(2500)));
+        }
+
+        let batches = dispatcher.take_batches().remove(&channel).unwrap();
+        assert_eq!(
+            batches
+                .iter()
+                .map(|batch| batch.embeds.len())
+                .collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+    }
+
+    #[test]
+    fn keeps_channels_and_mentions_apart() {
+        let dispatcher = LogDispatcher::new();
+        dispatcher.dispatch_mention(ChannelId::new(1), UserId::new(10), CreateEmbed::new());
+        dispatcher.dispatch(ChannelId::new(1), CreateEmbed::new());
+        dispatcher.dispatch_mention(ChannelId::new(1), UserId::new(11), CreateEmbed::new());
+        dispatcher.dispatch(ChannelId::new(2), CreateEmbed::new());
+
+        let mut batches = dispatcher.take_batches();
+        let first = batches.remove(&ChannelId::new(1)).unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].content.as_deref(), Some("<@10> <@11>"));
+        assert_eq!(
+            first[0].payload()["allowed_mentions"]["parse"],
+            serde_json::json!([])
+        );
+
+        let second = batches.remove(&ChannelId::new(2)).unwrap();
+        assert_eq!(second[0].content, None);
+        assert!(second[0].payload().get("content").is_none());
+    }
+
+    #[test]
+    fn retries_back_off() {
+        assert_eq!(
+            (1..=4).map(retry_delay).collect::<Vec<_>>(),
+            vec![5, 10, 20, 40]
+        );
+        assert_eq!(retry_delay(0), 5);
+        assert!(retry_delay(u32::MAX) > 0);
+    }
+
+    #[test]
+    fn only_retries_transient_statuses() {
+        assert!(is_transient_status(429));
+        assert!(is_transient_status(500));
+        assert!(is_transient_status(503));
+        assert!(!is_transient_status(400));
+        assert!(!is_transient_status(403));
+        assert!(!is_transient_status(404));
+    }
+}
@@ -1,9 +1,38 @@
-use crate::models::{config::LoggingConfig, handler::Handler};
+use std::fmt::Display;
 
+use serenity::{
+    all::{audit_log::Action, AuditLogEntry, ChannelId, GuildId, RoleId, UserId},
+    prelude::Context,
+};
+use tracing::error;
+
+use crate::{
+    database::postgres::logging::get_logging_ignores,
+    models::{config::LoggingConfig, handler::Handler},
+};
+
+// Audit log entries are written shortly before or after the gateway event they belong to
+const AUDIT_LOG_WINDOW_SECONDS: i64 = 10;
+
+#[derive(Clone, Copy, PartialEq)]
 pub enum LogType {
     Action,
     Message,
     Voice,
+    Member,
+    Server,
+}
+
+impl Display for LogType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogType::Action => write!(f, "actions"),
+            LogType::Message => write!(f, "messages"),
+            LogType::Voice => write!(f, "voice"),
+            LogType::Member => write!(f, "members"),
+            LogType::Server => write!(f, "server"),
+        }
+    }
 }
 
 pub async fn get_log_channel(
@@ -60,6 +89,28 @@ pub async fn get_log_channel(
                 return None;
             }
         }
+        LogType::Member => {
+            if !feature_flags
+                .iter()
+                .find(|flag| flag.feature == "logging.member")
+                .unwrap()
+                .active
+                || !logging_configuration.log_members
+            {
+                return None;
+            }
+        }
+        LogType::Server => {
+            if !feature_flags
+                .iter()
+                .find(|flag| flag.feature == "logging.server")
+                .unwrap()
+                .active
+                || !logging_configuration.log_server
+            {
+                return None;
+            }
+        }
     }
 
     if logging_configuration.log_channel.is_some() {
@@ -69,6 +120,108 @@ pub async fn get_log_channel(
             LogType::Action => logging_configuration.log_action_channel,
             LogType::Message => logging_configuration.log_message_channel,
             LogType::Voice => logging_configuration.log_voice_channel,
+            LogType::Member => logging_configuration.log_member_channel,
+            LogType::Server => logging_configuration.log_server_channel,
+        }
+    }
+}
+
+pub async fn get_guild_log_channel(
+    handler: &Handler,
+    guild_id: GuildId,
+    log_type: &LogType,
+) -> Option<ChannelId> {
+    let logging_configuration = sqlx::query_as!(
+        LoggingConfig,
+        "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
+        guild_id.get() as i64
+    )
+    .fetch_one(&handler.main_database)
+    .await
+    .ok()?;
+    get_log_channel(handler, &logging_configuration, log_type)
+        .await
+        .map(|channel| ChannelId::new(channel as u64))
+}
+
+fn parent_channel(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> Option<ChannelId> {
+    let guild = ctx.cache.guild(guild_id)?;
+    match guild.channels.get(&channel_id) {
+        Some(channel) => channel.parent_id,
+        None => guild
+            .threads
+            .iter()
+            .find(|thread| thread.id == channel_id)
+            .and_then(|thread| thread.parent_id),
+    }
+}
+
+pub fn cached_roles(ctx: &Context, guild_id: GuildId, user_id: UserId) -> Vec<RoleId> {
+    ctx.cache
+        .member(guild_id, user_id)
+        .map(|member| member.roles.clone())
+        .unwrap_or_default()
+}
+
+/// Checks the guild's ignore lists for the log type, a channel is also ignored through its category
+/// and a thread through its parent channel
+pub async fn is_log_ignored(
+    handler: &Handler,
+    ctx: &Context,
+    guild_id: GuildId,
+    log_type: &LogType,
+    channels: &[ChannelId],
+    user_id: Option<UserId>,
+    roles: &[RoleId],
+) -> bool {
+    let ignores = get_logging_ignores(handler, guild_id.get() as i64, log_type).await;
+
+    for channel in channels {
+        let mut current = Some(*channel);
+        // Thread -> channel -> category
+        for _ in 0..3 {
+            let Some(channel) = current else {
+                break;
+            };
+            if ignores.ignored_channels.contains(&(channel.get() as i64)) {
+                return true;
+            }
+            current = parent_channel(ctx, guild_id, channel);
+        }
+    }
+
+    user_id.is_some_and(|user_id| ignores.ignored_users.contains(&(user_id.get() as i64)))
+        || roles
+            .iter()
+            .any(|role| ignores.ignored_roles.contains(&(role.get() as i64)))
+}
+
+pub fn is_recent_entry(entry: &AuditLogEntry) -> bool {
+    time::OffsetDateTime::now_utc().unix_timestamp() - entry.id.created_at().unix_timestamp()
+        <= AUDIT_LOG_WINDOW_SECONDS
+}
+
+/// Finds the audit log entry for an event that just happened, older entries for the same target
+/// belong to an earlier event and are ignored
+pub async fn get_audit_log_entry(
+    ctx: &Context,
+    guild_id: GuildId,
+    action: Action,
+    target_id: Option<u64>,
+) -> Option<AuditLogEntry> {
+    match ctx
+        .http
+        .get_audit_logs(guild_id, Some(action), None, None, Some(5))
+        .await
+    {
+        Ok(audit_log) => audit_log.entries.into_iter().find(|entry| {
+            is_recent_entry(entry)
+                && target_id
+                    .is_none_or(|target_id| entry.target_id.is_some_and(|id| id.get() == target_id))
+        }),
+        Err(err) => {
+            error!("Failed to get audit log: {:?}", err);
+            None
         }
     }
 }
@@ -1,5 +1,9 @@
 pub mod duration;
+pub mod filter_cache;
+pub mod log_dispatcher;
 pub mod logging;
 pub mod r#macro;
 pub mod options;
 pub mod reply;
+pub mod scheduler;
+pub mod timeout;
@@ -1,6 +1,8 @@
 use std::borrow::Cow;
 
-use serenity::all::{ResolvedOption, ResolvedValue, Role, User};
+use serenity::all::{Attachment, PartialChannel, ResolvedOption, ResolvedValue, Role, User};
+
+use crate::models::response::ResponseError;
 
 #[derive(Debug, Clone)]
 pub struct Options<'a> {
@@ -112,4 +114,74 @@ impl Options<'_> {
         }
         None
     }
+
+    pub fn get_channel(&self, name: &str) -> Cow<'_, Option<PartialChannel>> {
+        for option in &self.options {
+            match &option.value {
+                ResolvedValue::SubCommandGroup(cmd) | ResolvedValue::SubCommand(cmd) => {
+                    let sub_options = Options {
+                        options: cmd.clone(),
+                    };
+                    let channel = sub_options.get_channel(name).into_owned().clone();
+                    return Cow::Owned(channel);
+                }
+                ResolvedValue::Channel(channel) => {
+                    if option.name == name {
+                        return Cow::Owned(Some(channel.to_owned().clone()));
+                    }
+                }
+                _ => continue,
+            }
+        }
+        Cow::Owned(None)
+    }
+
+    pub fn get_attachment(&self, name: &str) -> Cow<'_, Option<Attachment>> {
+        for option in &self.options {
+            match &option.value {
+                ResolvedValue::SubCommandGroup(cmd) | ResolvedValue::SubCommand(cmd) => {
+                    let sub_options = Options {
+                        options: cmd.clone(),
+                    };
+                    let attachment = sub_options.get_attachment(name).into_owned().clone();
+                    return Cow::Owned(attachment);
+                }
+                ResolvedValue::Attachment(attachment) => {
+                    if option.name == name {
+                        return Cow::Owned(Some(attachment.to_owned().clone()));
+                    }
+                }
+                _ => continue,
+            }
+        }
+        Cow::Owned(None)
+    }
+
+    pub fn get_date(&self, name: &str) -> Result<Option<time::PrimitiveDateTime>, String> {
+        match self.get_string(name).into_owned() {
+            Some(value) => parse_date(&value).map(Some).ok_or(value),
+            None => Ok(None),
+        }
+    }
+}
+
+pub fn invalid_date(value: &str) -> ResponseError {
+    ResponseError::Execution(
+        "Invalid date!",
+        Some(format!(
+            "`{value}` is not a valid date, please use the format `YYYY-MM-DD`."
+        )),
+    )
+}
+
+fn parse_date(value: &str) -> Option<time::PrimitiveDateTime> {
+    let mut parts = value.trim().splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = time::Month::try_from(parts.next()?.parse::<u8>().ok()?).ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Some(
+        time::Date::from_calendar_date(year, month, day)
+            .ok()?
+            .midnight(),
+    )
 }
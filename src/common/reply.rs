@@ -38,6 +38,11 @@ impl CommandContextReply for CommandContext {
             if let Some(components) = response.components {
                 edit = edit.components(components);
             }
+            if let Some(attachments) = response.attachments {
+                for attachment in attachments {
+                    edit = edit.new_attachment(attachment);
+                }
+            }
 
             match cmd.edit_response(&self.ctx.http, edit).await {
                 Ok(message) => message,
@@ -60,6 +65,9 @@ impl CommandContextReply for CommandContext {
             if let Some(components) = response.components {
                 reply = reply.components(components);
             }
+            if let Some(attachments) = response.attachments {
+                reply = reply.add_files(attachments);
+            }
             if response.ephemeral {
                 reply = reply.ephemeral(true);
             }
@@ -117,6 +125,9 @@ impl CommandContextReply for FailedCommandContext {
         if let Some(components) = response.components {
             reply = reply.components(components);
         }
+        if let Some(attachments) = response.attachments {
+            reply = reply.add_files(attachments);
+        }
         if response.ephemeral {
             reply = reply.ephemeral(true);
         }
@@ -166,6 +177,11 @@ impl InteractionContextReply for InteractionContext {
             if let Some(components) = response.components {
                 edit = edit.components(components);
             }
+            if let Some(attachments) = response.attachments {
+                for attachment in attachments {
+                    edit = edit.new_attachment(attachment);
+                }
+            }
 
             if let Err(err) = self.interaction.edit_response(&self.ctx.http, edit).await {
                 error!("Attempted to edit a interaction response, failed with error: {err}");
@@ -185,6 +201,9 @@ impl InteractionContextReply for InteractionContext {
             if let Some(components) = response.components {
                 reply = reply.components(components);
             }
+            if let Some(attachments) = response.attachments {
+                reply = reply.add_files(attachments);
+            }
             if response.ephemeral {
                 reply = reply.ephemeral(true);
             }
@@ -0,0 +1,169 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use tokio::sync::Notify;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ScheduledTask {
+    ActionExpiry(String),
+    ScheduledAction(i32),
+    GiveawayCleanup(i64),
+}
+
+type QueueEntry = Reverse<(time::OffsetDateTime, ScheduledTask)>;
+
+pub struct Scheduler {
+    queue: Mutex<BinaryHeap<QueueEntry>>,
+    notify: Notify,
+    started: AtomicBool,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Scheduler {
+            queue: Mutex::new(BinaryHeap::new()),
+            notify: Notify::new(),
+            started: AtomicBool::new(false),
+        }
+    }
+
+    pub fn schedule(&self, at: time::OffsetDateTime, task: ScheduledTask) {
+        self.queue.lock().unwrap().push(Reverse((at, task)));
+        self.notify.notify_one();
+    }
+
+    pub fn unschedule(&self, task: &ScheduledTask) {
+        self.queue
+            .lock()
+            .unwrap()
+            .retain(|Reverse((_, queued))| queued != task);
+        self.notify.notify_one();
+    }
+
+    pub fn replace(&self, tasks: Vec<(time::OffsetDateTime, ScheduledTask)>) {
+        *self.queue.lock().unwrap() = tasks.into_iter().map(Reverse).collect();
+        self.notify.notify_one();
+    }
+
+    pub fn pending(&self) -> usize {
+        self.queue.lock().unwrap().len()
+    }
+
+    /// Returns true only for the first caller, so the runner is spawned once across reconnects
+    pub fn start(&self) -> bool {
+        !self.started.swap(true, Ordering::SeqCst)
+    }
+
+    pub fn take_due(&self) -> Vec<ScheduledTask> {
+        let now = time::OffsetDateTime::now_utc();
+        let mut queue = self.queue.lock().unwrap();
+        let mut due = vec![];
+        while queue.peek().is_some_and(|Reverse((at, _))| *at <= now) {
+            let Reverse((_, task)) = queue.pop().unwrap();
+            due.push(task);
+        }
+        due
+    }
+
+    pub async fn wait(&self) {
+        let next = self
+            .queue
+            .lock()
+            .unwrap()
+            .peek()
+            .map(|Reverse((at, _))| *at);
+        match next {
+            Some(at) => {
+                let delay = (at - time::OffsetDateTime::now_utc())
+                    .try_into()
+                    .unwrap_or(Duration::ZERO);
+                tokio::select! {
+                    () = tokio::time::sleep(delay) => {}
+                    () = self.notify.notified() => {}
+                }
+            }
+            None => self.notify.notified().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScheduledTask, Scheduler};
+
+    fn minutes(minutes: i64) -> time::OffsetDateTime {
+        time::OffsetDateTime::now_utc() + time::Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn takes_due_tasks_in_order() {
+        let scheduler = Scheduler::new();
+        scheduler.schedule(minutes(-1), ScheduledTask::ScheduledAction(2));
+        scheduler.schedule(minutes(-5), ScheduledTask::ActionExpiry("a".to_string()));
+        scheduler.schedule(minutes(-3), ScheduledTask::GiveawayCleanup(3));
+
+        assert_eq!(
+            scheduler.take_due(),
+            vec![
+                ScheduledTask::ActionExpiry("a".to_string()),
+                ScheduledTask::GiveawayCleanup(3),
+                ScheduledTask::ScheduledAction(2),
+            ]
+        );
+        assert_eq!(scheduler.pending(), 0);
+    }
+
+    #[test]
+    fn leaves_future_tasks_queued() {
+        let scheduler = Scheduler::new();
+        scheduler.schedule(minutes(10), ScheduledTask::ScheduledAction(1));
+        scheduler.schedule(minutes(-1), ScheduledTask::ScheduledAction(2));
+
+        assert_eq!(
+            scheduler.take_due(),
+            vec![ScheduledTask::ScheduledAction(2)]
+        );
+        assert_eq!(scheduler.pending(), 1);
+        assert!(scheduler.take_due().is_empty());
+    }
+
+    #[test]
+    fn unschedule_removes_every_entry_of_a_task() {
+        let scheduler = Scheduler::new();
+        scheduler.schedule(minutes(-2), ScheduledTask::ScheduledAction(1));
+        scheduler.schedule(minutes(-1), ScheduledTask::ScheduledAction(1));
+        scheduler.schedule(minutes(-1), ScheduledTask::ScheduledAction(2));
+        scheduler.unschedule(&ScheduledTask::ScheduledAction(1));
+
+        assert_eq!(
+            scheduler.take_due(),
+            vec![ScheduledTask::ScheduledAction(2)]
+        );
+    }
+
+    #[test]
+    fn replace_discards_previous_tasks() {
+        let scheduler = Scheduler::new();
+        scheduler.schedule(minutes(-1), ScheduledTask::ScheduledAction(1));
+        scheduler.replace(vec![(minutes(-1), ScheduledTask::GiveawayCleanup(5))]);
+
+        assert_eq!(
+            scheduler.take_due(),
+            vec![ScheduledTask::GiveawayCleanup(5)]
+        );
+    }
+
+    #[test]
+    fn start_only_succeeds_once() {
+        let scheduler = Scheduler::new();
+        assert!(scheduler.start());
+        assert!(!scheduler.start());
+    }
+}
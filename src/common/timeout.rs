@@ -0,0 +1,57 @@
+use serenity::{
+    all::{GuildId, Timestamp, UserId},
+    builder::EditMember,
+    prelude::Context,
+};
+
+// Discord does not allow a member to be timed out for longer than 28 days
+pub const MAX_TIMEOUT_SECONDS: i64 = 60 * 60 * 24 * 28;
+
+// Longer timeouts are re-applied once less than this remains on the current one
+pub const TIMEOUT_RENEWAL_SECONDS: i64 = 60 * 60 * 24;
+
+// Permanent mutes get the longest timeout possible and are renewed like any other long mute
+pub fn get_timeout_end(expiry: Option<time::OffsetDateTime>) -> Timestamp {
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    let end = expiry.map_or(now + MAX_TIMEOUT_SECONDS, |expiry| {
+        expiry.unix_timestamp().min(now + MAX_TIMEOUT_SECONDS)
+    });
+    Timestamp::from_unix_timestamp(end).unwrap_or_else(|_| Timestamp::now())
+}
+
+pub async fn apply_timeout(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    expiry: Option<time::OffsetDateTime>,
+    reason: &str,
+) -> Result<(), serenity::Error> {
+    guild_id
+        .edit_member(
+            &ctx.http,
+            user_id,
+            EditMember::new()
+                .disable_communication_until_datetime(get_timeout_end(expiry))
+                .audit_log_reason(reason),
+        )
+        .await?;
+    Ok(())
+}
+
+pub async fn remove_timeout(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    reason: &str,
+) -> Result<(), serenity::Error> {
+    guild_id
+        .edit_member(
+            &ctx.http,
+            user_id,
+            EditMember::new()
+                .enable_communication()
+                .audit_log_reason(reason),
+        )
+        .await?;
+    Ok(())
+}
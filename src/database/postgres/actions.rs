@@ -1,6 +1,12 @@
+use std::collections::HashMap;
+
 use tracing::error;
 
-use crate::models::{actions::DatabaseAction, handler::Handler};
+use crate::models::{
+    action_history::{ActionRevision, RevisionField},
+    actions::{CustomAction, DatabaseAction},
+    handler::Handler,
+};
 
 pub async fn get_active_strikes(
     handler: &Handler,
@@ -22,3 +28,129 @@ pub async fn get_active_strikes(
         }
     }
 }
+
+pub async fn get_custom_actions(handler: &Handler, guild_id: i64) -> Vec<CustomAction> {
+    match sqlx::query_as!(
+        CustomAction,
+        "SELECT name, role_id FROM custom_actions WHERE guild_id = $1 ORDER BY name",
+        guild_id
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(custom_actions) => custom_actions,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} custom actions, failed with error: {err}",
+            );
+            Vec::new()
+        }
+    }
+}
+
+pub async fn get_custom_action(
+    handler: &Handler,
+    guild_id: i64,
+    name: &str,
+) -> Option<CustomAction> {
+    match sqlx::query_as!(
+        CustomAction,
+        "SELECT name, role_id FROM custom_actions WHERE guild_id = $1 AND LOWER(name) = LOWER($2)",
+        guild_id,
+        name
+    )
+    .fetch_optional(&handler.main_database)
+    .await
+    {
+        Ok(custom_action) => custom_action,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} custom action {name}, failed with error: {err}",
+            );
+            None
+        }
+    }
+}
+
+pub async fn insert_action_revision(
+    handler: &Handler,
+    guild_id: i64,
+    action_id: &str,
+    field: RevisionField,
+    old_value: Option<String>,
+    new_value: Option<String>,
+    changed_by: i64,
+) {
+    if let Err(err) = sqlx::query!(
+        "INSERT INTO action_history (action_id, guild_id, field, old_value, new_value, changed_by) VALUES ($1, $2, $3, $4, $5, $6)",
+        action_id,
+        guild_id,
+        field.to_string(),
+        old_value,
+        new_value,
+        changed_by
+    )
+    .execute(&handler.main_database)
+    .await
+    {
+        error!(
+            "Attempted to insert revision of action {action_id} into main database, failed with error: {err}",
+        );
+    }
+}
+
+pub async fn get_action_revisions(
+    handler: &Handler,
+    guild_id: i64,
+    action_ids: &[String],
+) -> HashMap<String, Vec<ActionRevision>> {
+    match sqlx::query_as!(
+        ActionRevision,
+        "SELECT action_id, field, old_value, new_value, changed_by, changed_at FROM action_history WHERE guild_id = $1 AND action_id = ANY($2) ORDER BY changed_at",
+        guild_id,
+        action_ids
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(revisions) => {
+            let mut by_action: HashMap<String, Vec<ActionRevision>> = HashMap::new();
+            for revision in revisions {
+                by_action
+                    .entry(revision.action_id.clone())
+                    .or_default()
+                    .push(revision);
+            }
+            by_action
+        }
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} action revisions, failed with error: {err}",
+            );
+            HashMap::new()
+        }
+    }
+}
+
+pub async fn resolve_action_id(handler: &Handler, guild_id: i64, id: String) -> String {
+    let Ok(case_number) = id.trim_start_matches('#').parse::<i32>() else {
+        return id;
+    };
+    match sqlx::query!(
+        r#"SELECT id AS "id!" FROM actions WHERE guild_id = $1 AND case_number = $2 UNION ALL SELECT id FROM removed_actions WHERE guild_id = $1 AND case_number = $2"#,
+        guild_id,
+        case_number
+    )
+    .fetch_optional(&handler.main_database)
+    .await
+    {
+        Ok(Some(record)) => record.id,
+        Ok(None) => id,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} case {case_number}, failed with error: {err}",
+            );
+            id
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use tracing::error;
+
+use crate::models::{appeal::Appeal, handler::Handler};
+
+pub async fn get_appeal(handler: &Handler, id: i32) -> Option<Appeal> {
+    match sqlx::query_as!(
+        Appeal,
+        "SELECT id, action_id, guild_id, user_id, reason, status, reviewer_id, response FROM appeals WHERE id = $1",
+        id
+    )
+    .fetch_optional(&handler.main_database)
+    .await
+    {
+        Ok(appeal) => appeal,
+        Err(err) => {
+            error!("Attempted to query main database for appeal {id}, failed with error: {err}");
+            None
+        }
+    }
+}
+
+pub async fn get_action_appeals(
+    handler: &Handler,
+    guild_id: i64,
+    action_ids: &[String],
+) -> Vec<Appeal> {
+    match sqlx::query_as!(
+        Appeal,
+        "SELECT id, action_id, guild_id, user_id, reason, status, reviewer_id, response FROM appeals WHERE guild_id = $1 AND action_id = ANY($2)",
+        guild_id,
+        action_ids
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(appeals) => appeals,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} action appeals, failed with error: {err}",
+            );
+            Vec::new()
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use tracing::error;
+
+use crate::models::{automod::AutoModMapping, handler::Handler};
+
+pub async fn get_automod_mappings(handler: &Handler, guild_id: i64) -> Vec<AutoModMapping> {
+    match sqlx::query_as!(
+        AutoModMapping,
+        "SELECT rule_id, action, duration, reason FROM automod_mappings WHERE guild_id = $1",
+        guild_id
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(mappings) => mappings,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} automod mappings, failed with error: {err}",
+            );
+            Vec::new()
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use tracing::error;
+
+use crate::models::{
+    filter::{CompiledFilterRule, FilterRule},
+    handler::Handler,
+};
+
+async fn fetch_filter_rules(
+    handler: &Handler,
+    guild_id: i64,
+) -> Result<Vec<FilterRule>, sqlx::Error> {
+    sqlx::query_as!(
+        FilterRule,
+        "SELECT id, name, pattern, pattern_type, action, duration, exempt_roles, exempt_channels FROM filter_rules WHERE guild_id = $1 ORDER BY id",
+        guild_id
+    )
+    .fetch_all(&handler.main_database)
+    .await
+}
+
+pub async fn get_filter_rules(handler: &Handler, guild_id: i64) -> Vec<FilterRule> {
+    match fetch_filter_rules(handler, guild_id).await {
+        Ok(rules) => rules,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} filter rules, failed with error: {err}",
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// Same as `get_filter_rules`, but with the patterns compiled and cached until the rules change
+pub async fn get_compiled_filter_rules(
+    handler: &Handler,
+    guild_id: i64,
+) -> Arc<Vec<CompiledFilterRule>> {
+    if let Some(rules) = handler.filter_cache.get(guild_id) {
+        return rules;
+    }
+
+    let rules = match fetch_filter_rules(handler, guild_id).await {
+        Ok(rules) => rules,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} filter rules, failed with error: {err}",
+            );
+            // Not cached, so the next message tries again
+            return Arc::new(Vec::new());
+        }
+    };
+    let compiled = rules
+        .into_iter()
+        .filter_map(|rule| match rule.regex() {
+            Ok(regex) => Some(CompiledFilterRule { rule, regex }),
+            Err(err) => {
+                error!("Failed to compile filter rule {}: {:?}", rule.id, err);
+                None
+            }
+        })
+        .collect();
+    handler.filter_cache.insert(guild_id, compiled)
+}
@@ -1,12 +1,15 @@
 use tracing::{debug, error};
 
-use crate::models::{config::ModerationConfig, handler::Handler};
+use crate::models::{
+    config::{AntiRaidConfig, ModerationConfig, SpamConfig},
+    handler::Handler,
+};
 
 pub async fn get_moderation_config(handler: &Handler, guild_id: i64) -> Option<ModerationConfig> {
     debug!("Querying main database for guild {guild_id} moderation configuration");
     match sqlx::query_as!(
         ModerationConfig,
-        "SELECT mute_role, default_strike_duration FROM moderation_configuration WHERE guild_id = $1",
+        "SELECT mute_role, default_strike_duration, use_timeouts, escalation_channel, strike_window FROM moderation_configuration WHERE guild_id = $1",
         guild_id
     )
     .fetch_optional(&handler.main_database)
@@ -20,3 +23,41 @@ pub async fn get_moderation_config(handler: &Handler, guild_id: i64) -> Option<M
         }
     }
 }
+
+pub async fn get_anti_raid_config(handler: &Handler, guild_id: i64) -> Option<AntiRaidConfig> {
+    debug!("Querying main database for guild {guild_id} anti-raid configuration");
+    match sqlx::query_as!(
+        AntiRaidConfig,
+        "SELECT enabled, join_threshold, join_window, lockdown_duration, quarantine_role, kick_account_age, lock_channels FROM anti_raid_configuration WHERE guild_id = $1",
+        guild_id
+    )
+    .fetch_optional(&handler.main_database)
+    .await {
+        Ok(config) => config,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} anti-raid configuration, failed with error: {err}",
+            );
+            None
+        }
+    }
+}
+
+pub async fn get_spam_config(handler: &Handler, guild_id: i64) -> Option<SpamConfig> {
+    debug!("Querying main database for guild {guild_id} spam configuration");
+    match sqlx::query_as!(
+        SpamConfig,
+        "SELECT enabled, message_limit, message_window, duplicate_limit, mention_limit, newline_limit, emoji_limit, punishment, mute_duration, reason FROM spam_configuration WHERE guild_id = $1",
+        guild_id
+    )
+    .fetch_optional(&handler.main_database)
+    .await {
+        Ok(config) => config,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} spam configuration, failed with error: {err}",
+            );
+            None
+        }
+    }
+}
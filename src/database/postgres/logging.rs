@@ -0,0 +1,30 @@
+use tracing::error;
+
+use crate::{
+    common::logging::LogType,
+    models::{config::LoggingIgnores, handler::Handler},
+};
+
+pub async fn get_logging_ignores(
+    handler: &Handler,
+    guild_id: i64,
+    log_type: &LogType,
+) -> LoggingIgnores {
+    match sqlx::query_as!(
+        LoggingIgnores,
+        "SELECT ignored_channels, ignored_roles, ignored_users FROM logging_ignores WHERE guild_id = $1 AND log_type = $2",
+        guild_id,
+        log_type.to_string()
+    )
+    .fetch_optional(&handler.main_database)
+    .await
+    {
+        Ok(ignores) => ignores.unwrap_or_default(),
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} logging ignores, failed with error: {err}",
+            );
+            LoggingIgnores::default()
+        }
+    }
+}
@@ -1,3 +1,9 @@
 pub mod actions;
+pub mod appeals;
+pub mod automod;
+pub mod filters;
 pub mod guild;
+pub mod logging;
+pub mod notes;
 pub mod permissions;
+pub mod stats;
@@ -0,0 +1,23 @@
+use tracing::error;
+
+use crate::models::{handler::Handler, note::Note};
+
+pub async fn get_user_notes(handler: &Handler, guild_id: i64, user_id: i64) -> Vec<Note> {
+    match sqlx::query_as!(
+        Note,
+        "SELECT id, author_id, content, created_at FROM notes WHERE guild_id = $1 AND user_id = $2 ORDER BY created_at DESC",
+        guild_id,
+        user_id
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(notes) => notes,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} notes for user {user_id}, failed with error: {err}",
+            );
+            Vec::new()
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use tracing::error;
+
+use crate::models::{
+    handler::Handler,
+    stats::{ActivityCount, ModeratorActionCount, ReasonCount},
+};
+
+pub async fn get_moderator_action_counts(
+    handler: &Handler,
+    guild_id: i64,
+    moderator_id: Option<i64>,
+    since: Option<time::PrimitiveDateTime>,
+) -> Vec<ModeratorActionCount> {
+    match sqlx::query_as!(
+        ModeratorActionCount,
+        r#"SELECT moderator_id AS "moderator_id!", action_type AS "action_type!", COUNT(*) AS "total!", COUNT(*) FILTER (WHERE removed) AS "removed!", COUNT(*) FILTER (WHERE expired_early) AS "expired_early!" FROM (SELECT moderator_id, action_type, false AS removed, EXISTS (SELECT 1 FROM action_history WHERE action_history.action_id = actions.id AND field = 'active') AS expired_early FROM actions WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR moderator_id = $2) AND ($3::TIMESTAMP IS NULL OR created_at >= $3) UNION ALL SELECT moderator_id, action_type, true, false FROM removed_actions WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR moderator_id = $2) AND ($3::TIMESTAMP IS NULL OR created_at >= $3)) issued GROUP BY moderator_id, action_type"#,
+        guild_id,
+        moderator_id,
+        since
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(counts) => counts,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} moderator action counts, failed with error: {err}",
+            );
+            Vec::new()
+        }
+    }
+}
+
+pub async fn get_activity_counts(
+    handler: &Handler,
+    guild_id: i64,
+    moderator_id: Option<i64>,
+    since: Option<time::PrimitiveDateTime>,
+    granularity: &str,
+) -> Vec<ActivityCount> {
+    match sqlx::query_as!(
+        ActivityCount,
+        r#"SELECT date_trunc($4, created_at) AS "period!", COUNT(*) AS "total!" FROM (SELECT created_at FROM actions WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR moderator_id = $2) AND ($3::TIMESTAMP IS NULL OR created_at >= $3) UNION ALL SELECT created_at FROM removed_actions WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR moderator_id = $2) AND ($3::TIMESTAMP IS NULL OR created_at >= $3)) issued GROUP BY 1 ORDER BY 1"#,
+        guild_id,
+        moderator_id,
+        since,
+        granularity
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(counts) => counts,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} activity counts, failed with error: {err}",
+            );
+            Vec::new()
+        }
+    }
+}
+
+pub async fn get_top_reasons(
+    handler: &Handler,
+    guild_id: i64,
+    moderator_id: Option<i64>,
+    since: Option<time::PrimitiveDateTime>,
+) -> Vec<ReasonCount> {
+    match sqlx::query_as!(
+        ReasonCount,
+        r#"SELECT MIN(reason) AS "reason!", COUNT(*) AS "total!" FROM (SELECT reason FROM actions WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR moderator_id = $2) AND ($3::TIMESTAMP IS NULL OR created_at >= $3) UNION ALL SELECT reason FROM removed_actions WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR moderator_id = $2) AND ($3::TIMESTAMP IS NULL OR created_at >= $3)) issued GROUP BY LOWER(TRIM(reason)) ORDER BY 2 DESC, 1 LIMIT 10"#,
+        guild_id,
+        moderator_id,
+        since
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(reasons) => reasons,
+        Err(err) => {
+            error!(
+                "Attempted to query main database for guild {guild_id} top reasons, failed with error: {err}",
+            );
+            Vec::new()
+        }
+    }
+}
@@ -0,0 +1,445 @@
+use serenity::{
+    all::{
+        ButtonStyle, Channel, ChannelId, ComponentInteraction, GuildId, Member,
+        PermissionOverwrite, PermissionOverwriteType, Permissions, RoleId, UserId,
+    },
+    builder::{
+        CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter, CreateInteractionResponse,
+        CreateInteractionResponseMessage, CreateMessage,
+    },
+    prelude::Context,
+};
+use tracing::{error, warn};
+
+use crate::{
+    common::logging::{get_log_channel, LogType},
+    database::postgres::guild::get_anti_raid_config,
+    models::{
+        command::get_member_permissions, config::AntiRaidConfig, config::LoggingConfig,
+        handler::Handler, permissions::Permission,
+    },
+};
+
+fn joins_key(guild_id: GuildId) -> String {
+    format!("anti_raid:{guild_id}:joins")
+}
+
+fn lockdown_key(guild_id: GuildId) -> String {
+    format!("anti_raid:{guild_id}:lockdown")
+}
+
+fn channels_key(guild_id: GuildId) -> String {
+    format!("anti_raid:{guild_id}:channels")
+}
+
+impl Handler {
+    pub async fn on_anti_raid_join(&self, ctx: &Context, member: &Member) {
+        if !sqlx::query!("SELECT active FROM global_kills WHERE feature = 'event.anti_raid'")
+            .fetch_one(&self.main_database)
+            .await
+            .unwrap()
+            .active
+        {
+            return;
+        }
+
+        let Some(config) = get_anti_raid_config(self, member.guild_id.get() as i64).await else {
+            return;
+        };
+        if !config.enabled {
+            return;
+        }
+
+        let mut connection = match self.redis_database.get_multiplexed_async_connection().await {
+            Ok(connection) => connection,
+            Err(err) => {
+                error!("Failed to get Redis connection: {:?}", err);
+                return;
+            }
+        };
+
+        let in_lockdown: bool = match redis::cmd("EXISTS")
+            .arg(lockdown_key(member.guild_id))
+            .query_async(&mut connection)
+            .await
+        {
+            Ok(exists) => exists,
+            Err(err) => {
+                error!("Failed to check lockdown state in Redis: {:?}", err);
+                return;
+            }
+        };
+        if in_lockdown {
+            respond_to_join(ctx, &config, member.guild_id, member.user.id).await;
+            return;
+        }
+
+        let now = time::OffsetDateTime::now_utc().unix_timestamp() * 1000;
+        let window = i64::from(config.join_window) * 1000;
+        let joins: Vec<u64> = match redis::pipe()
+            .cmd("ZADD")
+            .arg(joins_key(member.guild_id))
+            .arg(now)
+            .arg(member.user.id.get())
+            .ignore()
+            .cmd("ZREMRANGEBYSCORE")
+            .arg(joins_key(member.guild_id))
+            .arg("-inf")
+            .arg(now - window)
+            .ignore()
+            .cmd("EXPIRE")
+            .arg(joins_key(member.guild_id))
+            .arg(config.join_window)
+            .ignore()
+            .cmd("ZRANGE")
+            .arg(joins_key(member.guild_id))
+            .arg(0)
+            .arg(-1)
+            .query_async::<_, (Vec<u64>,)>(&mut connection)
+            .await
+        {
+            Ok((joins,)) => joins,
+            Err(err) => {
+                error!("Failed to track join rate in Redis: {:?}", err);
+                return;
+            }
+        };
+
+        if joins.len() < usize::try_from(config.join_threshold).unwrap_or(usize::MAX) {
+            return;
+        }
+
+        // SET NX makes sure only one join starts the lockdown when several arrive at once,
+        // and the expiry ends it even if the alert with the end button was never sent
+        let started: bool = match redis::cmd("SET")
+            .arg(lockdown_key(member.guild_id))
+            .arg(now)
+            .arg("NX")
+            .arg("EX")
+            .arg(i64::from(config.lockdown_duration) * 60)
+            .query_async::<_, Option<String>>(&mut connection)
+            .await
+        {
+            Ok(result) => result.is_some(),
+            Err(err) => {
+                error!("Failed to set lockdown state in Redis: {:?}", err);
+                return;
+            }
+        };
+        if !started {
+            respond_to_join(ctx, &config, member.guild_id, member.user.id).await;
+            return;
+        }
+
+        warn!(
+            "Join rate exceeded in guild {}, {} joins in {} seconds. Starting lockdown",
+            member.guild_id,
+            joins.len(),
+            config.join_window
+        );
+
+        let mut locked_channels = Vec::new();
+        for channel_id in &config.lock_channels {
+            let channel_id = ChannelId::new(*channel_id as u64);
+            if self
+                .lock_channel(ctx, &mut connection, member.guild_id, channel_id)
+                .await
+            {
+                locked_channels.push(channel_id);
+            }
+        }
+
+        for user_id in &joins {
+            respond_to_join(ctx, &config, member.guild_id, UserId::new(*user_id)).await;
+        }
+
+        let mut responses = Vec::new();
+        if let Some(role) = config.quarantine_role {
+            responses.push(format!("New joiners are given <@&{role}>"));
+        }
+        if let Some(days) = config.kick_account_age {
+            responses.push(format!(
+                "New accounts younger than **{days}** days are kicked"
+            ));
+        }
+        if !locked_channels.is_empty() {
+            responses.push(format!(
+                "Locked {}",
+                locked_channels
+                    .iter()
+                    .map(|channel| format!("<#{channel}>"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        if responses.is_empty() {
+            responses.push("No automatic responses are configured".to_string());
+        }
+
+        let Ok(logging_config) = sqlx::query_as!(
+            LoggingConfig,
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
+            member.guild_id.get() as i64
+        )
+        .fetch_one(&self.main_database)
+        .await else {
+            return;
+        };
+        let Some(channel) = get_log_channel(self, &logging_config, &LogType::Action).await else {
+            return;
+        };
+        if let Err(err) = ChannelId::new(channel as u64)
+            .send_message(
+                &ctx,
+                CreateMessage::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title("Raid detected")
+                            .description(format!(
+                                "**{}** users joined within **{}** seconds, the server is now in lockdown for **{}** minutes",
+                                joins.len(),
+                                config.join_window,
+                                config.lockdown_duration
+                            ))
+                            .field("Response", responses.join("\n"), false)
+                            .footer(CreateEmbedFooter::new(format!(
+                                "Lockdown started | Guild: {}",
+                                member.guild_id
+                            )))
+                            .color(0xf54029),
+                    )
+                    .components(vec![CreateActionRow::Buttons(vec![CreateButton::new(
+                        format!("lockdown_end:{}", member.guild_id),
+                    )
+                    .label("End lockdown")
+                    .style(ButtonStyle::Danger)])]),
+            )
+            .await
+        {
+            error!("Failed to send raid alert: {:?}", err);
+        }
+    }
+
+    async fn lock_channel(
+        &self,
+        ctx: &Context,
+        connection: &mut redis::aio::MultiplexedConnection,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> bool {
+        let everyone = PermissionOverwriteType::Role(guild_id.everyone_role());
+        let channel = match channel_id.to_channel(ctx).await.map(Channel::guild) {
+            Ok(Some(channel)) => channel,
+            Ok(None) => return false,
+            Err(err) => {
+                error!(
+                    "Failed to fetch channel {channel_id} for lockdown: {:?}",
+                    err
+                );
+                return false;
+            }
+        };
+        let existing = channel
+            .permission_overwrites
+            .iter()
+            .find(|overwrite| overwrite.kind == everyone);
+
+        // Remember the original overwrite so it can be restored when the lockdown ends
+        let original = existing.map_or("none".to_string(), |overwrite| {
+            format!("{}:{}", overwrite.allow.bits(), overwrite.deny.bits())
+        });
+        if let Err(err) = redis::cmd("HSETNX")
+            .arg(channels_key(guild_id))
+            .arg(channel_id.get())
+            .arg(original)
+            .query_async::<_, ()>(connection)
+            .await
+        {
+            error!("Failed to store channel permissions in Redis: {:?}", err);
+            return false;
+        }
+
+        let (allow, deny) = existing
+            .map_or((Permissions::empty(), Permissions::empty()), |overwrite| {
+                (overwrite.allow, overwrite.deny)
+            });
+        let locked = Permissions::SEND_MESSAGES
+            | Permissions::SEND_MESSAGES_IN_THREADS
+            | Permissions::CREATE_PUBLIC_THREADS
+            | Permissions::ADD_REACTIONS;
+        if let Err(err) = channel_id
+            .create_permission(
+                &ctx.http,
+                PermissionOverwrite {
+                    allow: allow - locked,
+                    deny: deny | locked,
+                    kind: everyone,
+                },
+            )
+            .await
+        {
+            error!("Failed to lock channel {channel_id}: {:?}", err);
+            return false;
+        }
+        true
+    }
+
+    /// Restores locked channels and clears the lockdown, returning whether anything was left to end.
+    /// Channels stay locked after the lockdown expires, until they are restored here
+    pub async fn end_lockdown(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+    ) -> Result<bool, redis::RedisError> {
+        let mut connection = self
+            .redis_database
+            .get_multiplexed_async_connection()
+            .await?;
+
+        let channels: Vec<(u64, String)> = redis::cmd("HGETALL")
+            .arg(channels_key(guild_id))
+            .query_async(&mut connection)
+            .await?;
+        let everyone = PermissionOverwriteType::Role(guild_id.everyone_role());
+        for (channel_id, original) in channels {
+            let channel_id = ChannelId::new(channel_id);
+            let result = match original.split_once(':') {
+                Some((allow, deny)) => {
+                    channel_id
+                        .create_permission(
+                            &ctx.http,
+                            PermissionOverwrite {
+                                allow: Permissions::from_bits_truncate(
+                                    allow.parse().unwrap_or_default(),
+                                ),
+                                deny: Permissions::from_bits_truncate(
+                                    deny.parse().unwrap_or_default(),
+                                ),
+                                kind: everyone,
+                            },
+                        )
+                        .await
+                }
+                None => channel_id.delete_permission(&ctx.http, everyone).await,
+            };
+            if let Err(err) = result {
+                error!("Failed to unlock channel {channel_id}: {:?}", err);
+            }
+        }
+
+        let (cleared,): (u64,) = redis::pipe()
+            .cmd("DEL")
+            .arg(lockdown_key(guild_id))
+            .arg(channels_key(guild_id))
+            .cmd("DEL")
+            .arg(joins_key(guild_id))
+            .ignore()
+            .query_async(&mut connection)
+            .await?;
+        Ok(cleared > 0)
+    }
+
+    pub async fn on_lockdown_end_button(
+        &self,
+        ctx: Context,
+        interaction: ComponentInteraction,
+        guild_id: &str,
+    ) {
+        let Some(guild_id) = guild_id.parse::<u64>().ok().map(GuildId::new) else {
+            return;
+        };
+        if interaction.guild_id != Some(guild_id)
+            || !get_member_permissions(self, &ctx, guild_id, interaction.user.id)
+                .await
+                .contains(&Permission::ModerationLockdown)
+        {
+            if let Err(err) = interaction
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .embed(
+                                CreateEmbed::new()
+                                    .title("You do not have permission to do this!")
+                                    .description(format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationLockdown))
+                                    .color(0xff0000),
+                            )
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+            {
+                error!("Failed to respond to lockdown interaction: {:?}", err);
+            }
+            return;
+        }
+
+        if let Err(err) = self.end_lockdown(&ctx, guild_id).await {
+            error!("Failed to end lockdown in guild {guild_id}: {:?}", err);
+            return;
+        }
+
+        let embed = interaction.message.embeds.first().map_or_else(
+            || CreateEmbed::new().title("Raid detected").color(0xf54029),
+            |embed| CreateEmbed::from(embed.clone()),
+        );
+        if let Err(err) = interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(embed.field(
+                            "Lockdown ended",
+                            format!("Ended by <@{}>", interaction.user.id),
+                            false,
+                        ))
+                        .components(vec![]),
+                ),
+            )
+            .await
+        {
+            error!("Failed to update raid alert: {:?}", err);
+        }
+    }
+}
+
+async fn respond_to_join(
+    ctx: &Context,
+    config: &AntiRaidConfig,
+    guild_id: GuildId,
+    user_id: UserId,
+) {
+    if let Some(days) = config.kick_account_age {
+        let age = time::OffsetDateTime::now_utc().unix_timestamp()
+            - user_id.created_at().unix_timestamp();
+        if age < i64::from(days) * 86400 {
+            if let Err(err) = ctx
+                .http
+                .kick_member(
+                    guild_id,
+                    user_id,
+                    Some(&format!("Anti-raid: account younger than {days} days")),
+                )
+                .await
+            {
+                error!("Failed to kick new account during lockdown: {:?}", err);
+            }
+            return;
+        }
+    }
+
+    if let Some(role) = config.quarantine_role {
+        if let Err(err) = ctx
+            .http
+            .add_member_role(
+                guild_id,
+                user_id,
+                RoleId::new(role as u64),
+                Some("Anti-raid: quarantined during lockdown"),
+            )
+            .await
+        {
+            error!("Failed to quarantine new member during lockdown: {:?}", err);
+        }
+    }
+}
@@ -0,0 +1,629 @@
+use serenity::{
+    all::{
+        ActionRowComponent, ButtonStyle, ChannelId, ComponentInteraction, GuildId, InputTextStyle,
+        ModalInteraction, UserId,
+    },
+    builder::{
+        CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter, CreateInputText,
+        CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, CreateModal,
+    },
+    prelude::Context,
+};
+use tracing::error;
+
+use crate::{
+    database::postgres::appeals::get_appeal,
+    models::{
+        actions::{Action, DatabaseAction},
+        appeal::AppealStatus,
+        command::get_member_permissions,
+        handler::Handler,
+        permissions::Permission,
+    },
+};
+
+pub async fn get_appeal_components(
+    handler: &Handler,
+    guild_id: i64,
+    action_id: &str,
+) -> Vec<CreateActionRow> {
+    if !sqlx::query!("SELECT active FROM global_kills WHERE feature = 'event.appeals'")
+        .fetch_one(&handler.main_database)
+        .await
+        .map_or(true, |record| record.active)
+    {
+        return vec![];
+    }
+
+    match sqlx::query!(
+        "SELECT appeal_channel FROM moderation_configuration WHERE guild_id = $1",
+        guild_id
+    )
+    .fetch_optional(&handler.main_database)
+    .await
+    {
+        Ok(Some(config)) if config.appeal_channel.is_some() => {
+            vec![CreateActionRow::Buttons(vec![CreateButton::new(format!(
+                "appeal:{action_id}"
+            ))
+            .label("Appeal")
+            .style(ButtonStyle::Secondary)])]
+        }
+        _ => vec![],
+    }
+}
+
+fn get_modal_value(interaction: &ModalInteraction) -> Option<String> {
+    interaction
+        .data
+        .components
+        .first()
+        .and_then(|row| row.components.first())
+        .and_then(|component| match component {
+            ActionRowComponent::InputText(text) => text.value.clone(),
+            _ => None,
+        })
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+async fn respond(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    title: &str,
+    description: &str,
+) {
+    if let Err(err) = interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(title)
+                            .description(description)
+                            .color(0xff0000),
+                    )
+                    .ephemeral(true),
+            ),
+        )
+        .await
+    {
+        error!("Failed to respond to appeal interaction: {:?}", err);
+    }
+}
+
+async fn respond_modal(
+    ctx: &Context,
+    interaction: &ModalInteraction,
+    title: &str,
+    description: &str,
+) {
+    if let Err(err) = interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(title)
+                            .description(description)
+                            .color(0xff0000),
+                    )
+                    .ephemeral(true),
+            ),
+        )
+        .await
+    {
+        error!("Failed to respond to appeal interaction: {:?}", err);
+    }
+}
+
+impl Handler {
+    async fn get_appealable_action(
+        &self,
+        action_id: &str,
+        user_id: i64,
+    ) -> Result<DatabaseAction, &'static str> {
+        let action = match sqlx::query_as!(
+            DatabaseAction,
+            "SELECT * FROM actions WHERE id = $1",
+            action_id
+        )
+        .fetch_optional(&self.main_database)
+        .await
+        {
+            Ok(Some(action)) => action,
+            Ok(None) => return Err("This action no longer exists."),
+            Err(err) => {
+                error!("Could not fetch action {action_id}: {:?}", err);
+                return Err("We could not find this action, please try again later.");
+            }
+        };
+        if action.user_id != user_id {
+            return Err("You cannot appeal this action.");
+        }
+        if !action.active {
+            return Err("This action is no longer active.");
+        }
+        match sqlx::query!("SELECT id FROM appeals WHERE action_id = $1", action_id)
+            .fetch_optional(&self.main_database)
+            .await
+        {
+            Ok(None) => Ok(action),
+            Ok(Some(_)) => Err("You have already appealed this action."),
+            Err(err) => {
+                error!("Could not fetch appeals for action {action_id}: {:?}", err);
+                Err("We could not check your existing appeals, please try again later.")
+            }
+        }
+    }
+
+    pub async fn on_appeal_button(
+        &self,
+        ctx: Context,
+        interaction: ComponentInteraction,
+        action_id: &str,
+    ) {
+        if let Err(description) = self
+            .get_appealable_action(action_id, interaction.user.id.get() as i64)
+            .await
+        {
+            respond(&ctx, &interaction, "Cannot appeal", description).await;
+            return;
+        }
+
+        if let Err(err) = interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Modal(
+                    CreateModal::new(format!("appeal_submit:{action_id}"), "Appeal").components(
+                        vec![CreateActionRow::InputText(
+                            CreateInputText::new(
+                                InputTextStyle::Paragraph,
+                                "Why should this action be removed?",
+                                "appeal_reason",
+                            )
+                            .max_length(1000)
+                            .required(true),
+                        )],
+                    ),
+                ),
+            )
+            .await
+        {
+            error!("Failed to open appeal modal: {:?}", err);
+        }
+    }
+
+    pub async fn on_appeal_submit(
+        &self,
+        ctx: Context,
+        interaction: ModalInteraction,
+        action_id: &str,
+    ) {
+        let reply = |title: &'static str, description: String| {
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new().embed(
+                    CreateEmbed::new()
+                        .title(title)
+                        .description(description)
+                        .color(0xff0000),
+                ),
+            )
+        };
+
+        let action = match self
+            .get_appealable_action(action_id, interaction.user.id.get() as i64)
+            .await
+        {
+            Ok(action) => action,
+            Err(description) => {
+                if let Err(err) = interaction
+                    .create_response(&ctx.http, reply("Cannot appeal", description.to_string()))
+                    .await
+                {
+                    error!("Failed to respond to appeal submission: {:?}", err);
+                }
+                return;
+            }
+        };
+        let Some(reason) = get_modal_value(&interaction) else {
+            return;
+        };
+
+        let appeal_channel = match sqlx::query!(
+            "SELECT appeal_channel FROM moderation_configuration WHERE guild_id = $1",
+            action.guild_id
+        )
+        .fetch_optional(&self.main_database)
+        .await
+        {
+            Ok(Some(config)) => config.appeal_channel,
+            _ => None,
+        };
+        let Some(appeal_channel) = appeal_channel else {
+            if let Err(err) = interaction
+                .create_response(
+                    &ctx.http,
+                    reply(
+                        "Appeals are disabled",
+                        "This server is no longer accepting appeals.".to_string(),
+                    ),
+                )
+                .await
+            {
+                error!("Failed to respond to appeal submission: {:?}", err);
+            }
+            return;
+        };
+
+        let appeal_id = match sqlx::query!(
+            "INSERT INTO appeals (action_id, guild_id, user_id, reason) VALUES ($1, $2, $3, $4) RETURNING id",
+            action.id,
+            action.guild_id,
+            action.user_id,
+            reason
+        )
+        .fetch_one(&self.main_database)
+        .await
+        {
+            Ok(appeal) => appeal.id,
+            Err(err) => {
+                error!("Failed to insert appeal for action {}: {:?}", action.id, err);
+                if let Err(err) = interaction
+                    .create_response(
+                        &ctx.http,
+                        reply(
+                            "Could not submit appeal",
+                            "Your appeal could not be submitted, please try again later."
+                                .to_string(),
+                        ),
+                    )
+                    .await
+                {
+                    error!("Failed to respond to appeal submission: {:?}", err);
+                }
+                return;
+            }
+        };
+
+        let action = Action::from(action);
+        if let Err(err) = ChannelId::new(appeal_channel as u64)
+            .send_message(
+                &ctx,
+                CreateMessage::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title("Appeal received")
+                            .description(format!(
+                                "<@{}> has appealed their {}",
+                                action.user_id,
+                                action.type_name()
+                            ))
+                            .field("Action reason", action.reason.clone(), true)
+                            .field("Moderator", format!("<@{}>", action.moderator_id), true)
+                            .field("Appeal", reason, false)
+                            .footer(CreateEmbedFooter::new(format!(
+                                "Appeal {appeal_id} | {} | UUID: {}",
+                                action.case(),
+                                action.get_id()
+                            )))
+                            .color(0xfdca4c),
+                    )
+                    .components(vec![CreateActionRow::Buttons(vec![
+                        CreateButton::new(format!("appeal_accept:{appeal_id}"))
+                            .label("Accept")
+                            .style(ButtonStyle::Success),
+                        CreateButton::new(format!("appeal_deny:{appeal_id}"))
+                            .label("Deny")
+                            .style(ButtonStyle::Danger),
+                    ])]),
+            )
+            .await
+        {
+            error!(
+                "Failed to send appeal {appeal_id} to appeals channel: {:?}",
+                err
+            );
+        }
+
+        if let Err(err) = interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new().embed(
+                        CreateEmbed::new()
+                            .title("Appeal submitted")
+                            .description("Your appeal has been sent to the moderators. You will be notified once it has been reviewed.")
+                            .color(0x00ff00),
+                    ),
+                ),
+            )
+            .await
+        {
+            error!("Failed to respond to appeal submission: {:?}", err);
+        }
+    }
+
+    async fn can_review_appeal(
+        &self,
+        ctx: &Context,
+        guild_id: Option<GuildId>,
+        user_id: UserId,
+    ) -> bool {
+        match guild_id {
+            Some(guild_id) => get_member_permissions(self, ctx, guild_id, user_id)
+                .await
+                .contains(&Permission::ModerationAppeals),
+            None => false,
+        }
+    }
+
+    pub async fn on_appeal_review_button(
+        &self,
+        ctx: Context,
+        interaction: ComponentInteraction,
+        appeal_id: &str,
+        accept: bool,
+    ) {
+        if !self
+            .can_review_appeal(&ctx, interaction.guild_id, interaction.user.id)
+            .await
+        {
+            respond(
+                &ctx,
+                &interaction,
+                "You do not have permission to do this!",
+                &format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationAppeals),
+            )
+            .await;
+            return;
+        }
+
+        if let Err(err) = interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Modal(
+                    CreateModal::new(
+                        format!(
+                            "{}:{appeal_id}",
+                            if accept {
+                                "appeal_accept_submit"
+                            } else {
+                                "appeal_deny_submit"
+                            }
+                        ),
+                        if accept {
+                            "Accept Appeal"
+                        } else {
+                            "Deny Appeal"
+                        },
+                    )
+                    .components(vec![CreateActionRow::InputText(
+                        CreateInputText::new(
+                            InputTextStyle::Paragraph,
+                            "Response to the user",
+                            "appeal_response",
+                        )
+                        .max_length(1000)
+                        .required(false),
+                    )]),
+                ),
+            )
+            .await
+        {
+            error!("Failed to open appeal review modal: {:?}", err);
+        }
+    }
+
+    pub async fn on_appeal_review_submit(
+        &self,
+        ctx: Context,
+        interaction: ModalInteraction,
+        appeal_id: &str,
+        accept: bool,
+    ) {
+        if !self
+            .can_review_appeal(&ctx, interaction.guild_id, interaction.user.id)
+            .await
+        {
+            respond_modal(
+                &ctx,
+                &interaction,
+                "You do not have permission to do this!",
+                &format!("You are missing the `{}` permission. If you believe this is a mistake, please contact your server administrators.", Permission::ModerationAppeals),
+            )
+            .await;
+            return;
+        }
+        let Some(appeal) = (match appeal_id.parse::<i32>() {
+            Ok(appeal_id) => get_appeal(self, appeal_id).await,
+            Err(_) => None,
+        }) else {
+            respond_modal(
+                &ctx,
+                &interaction,
+                "Appeal not found",
+                "This appeal no longer exists.",
+            )
+            .await;
+            return;
+        };
+        if interaction.guild_id.map(|guild_id| guild_id.get() as i64) != Some(appeal.guild_id) {
+            respond_modal(
+                &ctx,
+                &interaction,
+                "Appeal not found",
+                "This appeal does not belong to this server.",
+            )
+            .await;
+            return;
+        }
+        if appeal.status != AppealStatus::Pending {
+            respond_modal(
+                &ctx,
+                &interaction,
+                "Appeal already reviewed",
+                &format!("This appeal has already been {}.", appeal.status),
+            )
+            .await;
+            return;
+        }
+
+        let status = if accept {
+            AppealStatus::Accepted
+        } else {
+            AppealStatus::Denied
+        };
+        let response = get_modal_value(&interaction);
+
+        // Claiming the appeal first stops two reviewers from resolving it at the same time
+        match sqlx::query!(
+            "UPDATE appeals SET status = $1, reviewer_id = $2, response = $3, reviewed_at = now() WHERE id = $4 AND status = 'pending'",
+            status.to_string(),
+            interaction.user.id.get() as i64,
+            response,
+            appeal.id
+        )
+        .execute(&self.main_database)
+        .await
+        {
+            Ok(result) if result.rows_affected() == 0 => {
+                respond_modal(
+                    &ctx,
+                    &interaction,
+                    "Appeal already reviewed",
+                    "Another moderator reviewed this appeal first.",
+                )
+                .await;
+                return;
+            }
+            Ok(_) => {}
+            Err(err) => {
+                error!("Failed to update appeal {}: {:?}", appeal.id, err);
+                respond_modal(
+                    &ctx,
+                    &interaction,
+                    "Could not review appeal",
+                    "The appeal could not be updated. Please try again later.",
+                )
+                .await;
+                return;
+            }
+        }
+
+        if accept {
+            if let Err(err) = self
+                .expire_action(
+                    &ctx,
+                    appeal.guild_id,
+                    &appeal.action_id,
+                    interaction.user.id.get() as i64,
+                )
+                .await
+            {
+                error!(
+                    "Failed to expire action {} for appeal {}: {:?}",
+                    appeal.action_id, appeal.id, err
+                );
+                if let Err(err) = sqlx::query!(
+                    "UPDATE appeals SET status = 'pending', reviewer_id = NULL, response = NULL, reviewed_at = NULL WHERE id = $1",
+                    appeal.id
+                )
+                .execute(&self.main_database)
+                .await
+                {
+                    error!("Failed to reopen appeal {}: {:?}", appeal.id, err);
+                }
+                respond_modal(
+                    &ctx,
+                    &interaction,
+                    "Could not accept appeal",
+                    "The action could not be lifted, so the appeal is still pending. Please try again later.",
+                )
+                .await;
+                return;
+            }
+        }
+
+        if sqlx::query!("SELECT active FROM global_kills WHERE feature = 'commands.dm'")
+            .fetch_one(&self.main_database)
+            .await
+            .map_or(true, |record| record.active)
+        {
+            if let Ok(dm_channel) = UserId::new(appeal.user_id as u64)
+                .create_dm_channel(&ctx.http)
+                .await
+            {
+                let mut embed = CreateEmbed::new()
+                    .title(if accept {
+                        "Appeal accepted"
+                    } else {
+                        "Appeal denied"
+                    })
+                    .description(match GuildId::new(appeal.guild_id as u64).name(&ctx) {
+                        Some(guild_name) => {
+                            format!("Your appeal in {guild_name} has been {status}")
+                        }
+                        None => format!("Your appeal has been {status}"),
+                    })
+                    .footer(CreateEmbedFooter::new(format!(
+                        "UUID: {}",
+                        appeal.action_id
+                    )))
+                    .color(if accept { 0x00ff00 } else { 0xff0000 });
+                if let Some(response) = &response {
+                    embed = embed.field("Response", response.clone(), false);
+                }
+                if let Err(err) = dm_channel
+                    .send_message(&ctx, CreateMessage::new().embed(embed))
+                    .await
+                {
+                    error!(
+                        "Failed to notify user of appeal {} outcome: {:?}",
+                        appeal.id, err
+                    );
+                }
+            }
+        }
+
+        let mut embed = match interaction
+            .message
+            .as_ref()
+            .and_then(|message| message.embeds.first())
+        {
+            Some(embed) => CreateEmbed::from(embed.clone()),
+            None => CreateEmbed::new().title("Appeal"),
+        };
+        embed = embed
+            .field(
+                "Outcome",
+                format!(
+                    "{} by <@{}>{}",
+                    if accept { "Accepted" } else { "Denied" },
+                    interaction.user.id.get(),
+                    match &response {
+                        Some(response) => format!(": {response}"),
+                        None => String::new(),
+                    }
+                ),
+                false,
+            )
+            .color(if accept { 0x00ff00 } else { 0xff0000 });
+
+        if let Err(err) = interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(embed)
+                        .components(vec![]),
+                ),
+            )
+            .await
+        {
+            error!("Failed to update appeal {} message: {:?}", appeal.id, err);
+        }
+    }
+}
@@ -1,9 +1,21 @@
 use std::sync::{atomic::AtomicBool, Arc};
 
-use serenity::{all::ActionExecution, prelude::Context};
+use serenity::{
+    all::{ActionExecution, ChannelId},
+    builder::{CreateEmbed, CreateEmbedFooter},
+    prelude::Context,
+};
 use tracing::error;
 
-use crate::models::{command::CommandContext, handler::Handler};
+use crate::{
+    common::{
+        duration::Duration,
+        logging::{get_log_channel, LogType},
+    },
+    models::{
+        automod::AutoModMapping, command::CommandContext, config::LoggingConfig, handler::Handler,
+    },
+};
 
 impl Handler {
     pub async fn on_automod_trigger(&self, ctx: Context, execution: ActionExecution) {
@@ -16,6 +28,23 @@ impl Handler {
             return;
         }
 
+        let guild_id = execution.guild_id.get() as i64;
+        let mapping = match sqlx::query_as!(
+            AutoModMapping,
+            "SELECT rule_id, action, duration, reason FROM automod_mappings WHERE guild_id = $1 AND rule_id = $2",
+            guild_id,
+            execution.rule_id.get() as i64
+        )
+        .fetch_optional(&self.main_database)
+        .await
+        {
+            Ok(mapping) => mapping,
+            Err(err) => {
+                error!("Could not get automod mapping for rule {}. Failed with error: {:?}", execution.rule_id, err);
+                return;
+            }
+        };
+
         let Ok(rule) = ctx
             .http
             .get_automod_rule(execution.guild_id, execution.rule_id)
@@ -28,9 +57,10 @@ impl Handler {
             return;
         };
 
-        if !rule.name.to_ascii_lowercase().contains("strike") {
+        let Some(mapping) = mapping else {
+            self.log_unmapped_automod(&execution, &rule.name).await;
             return;
-        }
+        };
 
         let Ok(guild) = execution.guild_id.to_partial_guild(&ctx.http).await else {
             error!("Could not get guild {} from cache", execution.guild_id);
@@ -41,21 +71,96 @@ impl Handler {
             ctx,
             has_responsed: Arc::new(AtomicBool::new(false)),
             user_permissions: vec![],
-            highest_role: u16::max_value(),
+            highest_role: u16::MAX,
             guild,
         };
+        let user_id = execution.user_id.get() as i64;
+        let reason = mapping.format_reason(&rule.name, execution.matched_keyword.as_deref());
+        let duration = mapping.duration.as_deref().map(Duration::new);
+
+        let result = match mapping.action.as_str() {
+            "mute" => Box::pin(self.mute_user(
+                &context,
+                guild_id,
+                user_id,
+                reason,
+                None,
+                duration.unwrap_or_else(|| Duration::new("10m")),
+            ))
+            .await
+            .map(|_| ()),
+            "kick" => Box::pin(self.kick_user(&context, guild_id, user_id, reason, None))
+                .await
+                .map(|_| ()),
+            "ban" => Box::pin(self.ban_user(&context, guild_id, user_id, reason, None, duration))
+                .await
+                .map(|_| ()),
+            _ => Box::pin(self.strike_user(&context, guild_id, user_id, reason, None, duration, 1))
+                .await
+                .map(|_| ()),
+        };
+        if let Err(err) = result {
+            error!(
+                "Could not {} user for automod rule. Failed with error: {:?}",
+                mapping.action, err
+            );
+        }
+    }
 
-        if let Err(err) = Box::pin(self.strike_user(
-            &context,
-            context.guild.id.get() as i64,
-            execution.user_id.get() as i64,
-            format!("Violated \"{}\" automod rule", rule.name),
-            None,
-            None,
-        ))
+    async fn log_unmapped_automod(&self, execution: &ActionExecution, rule: &str) {
+        let guild_id = execution.guild_id.get() as i64;
+        if !sqlx::query!(
+            "SELECT automod_log_unmapped FROM moderation_configuration WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_optional(&self.main_database)
         .await
+        .ok()
+        .flatten()
+        .is_some_and(|config| config.automod_log_unmapped)
         {
-            error!("Could not strike user. Failed with error: {:?}", err);
+            return;
+        }
+
+        let Ok(logging_config) = sqlx::query_as!(
+            LoggingConfig,
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
+            guild_id
+        )
+        .fetch_one(&self.main_database)
+        .await else {
+            return;
+        };
+        let Some(channel) = get_log_channel(self, &logging_config, &LogType::Action).await else {
+            return;
+        };
+
+        let mut embed = CreateEmbed::new()
+            .title("AutoMod rule triggered")
+            .description(format!(
+                "<@{}> triggered the \"{rule}\" automod rule, which is not mapped to an action",
+                execution.user_id
+            ))
+            .footer(CreateEmbedFooter::new(format!(
+                "User {} | Rule: {}",
+                execution.user_id, execution.rule_id
+            )))
+            .color(0x2e4045);
+        if let Some(channel_id) = execution.channel_id {
+            embed = embed.field("Channel", format!("<#{channel_id}>"), true);
+        }
+        if let Some(keyword) = &execution.matched_keyword {
+            embed = embed.field("Keyword", format!("`{}`", keyword.replace('`', "'")), true);
         }
+        if !execution.content.is_empty() {
+            embed = embed.field(
+                "Content",
+                execution.content.chars().take(1024).collect::<String>(),
+                false,
+            );
+        }
+
+        self.log_dispatcher
+            .dispatch(ChannelId::new(channel as u64), embed);
     }
 }
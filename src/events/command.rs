@@ -8,7 +8,7 @@ use crate::{
     commands::{get_command_list, global::get_kill_commands},
     database::postgres::permissions::{get_role, get_user},
     models::{
-        command::{CommandContext, CommandContextReply, FailedCommandContext},
+        command::{Command, CommandContext, CommandContextReply, FailedCommandContext},
         handler::Handler,
         permissions::Permission,
         response::{Response, ResponseError},
@@ -83,6 +83,13 @@ impl Handler {
 
         let Some(guild_id) = command.guild_id else {
             let fail_context = FailedCommandContext { ctx };
+            if let Some(dm_command) = get_command_list().into_iter().find(|existing_command| {
+                existing_command.dm_enabled() && existing_command.name() == command.data.name
+            }) {
+                self.on_dm_command(&fail_context, &command, dm_command.as_ref())
+                    .await;
+                return;
+            }
             if let Err(err) = fail_context
                 .reply(
                     &command,
@@ -304,4 +311,56 @@ impl Handler {
 
         debug!("Took {:?} to handle a command", start.elapsed());
     }
+
+    async fn on_dm_command(
+        &self,
+        ctx: &FailedCommandContext,
+        command: &CommandInteraction,
+        dm_command: &dyn Command,
+    ) {
+        if !sqlx::query!(
+            "SELECT active FROM global_kills WHERE feature = $1",
+            format!("commands.{}", command.data.name)
+        )
+        .fetch_one(&self.main_database)
+        .await
+        .unwrap()
+        .active
+        {
+            if let Err(err) = ctx
+                .reply(
+                    command,
+                    Response::new()
+                        .embed(
+                            CreateEmbed::new()
+                                .title(format!("{} is currently disabled", command.data.name.to_title_case()))
+                                .color(0xff0000)
+                                .description("Please reach out to the [support server](https://discord.gg/jhD3Xc5cm6) for more information.")
+                        )
+                )
+                .await
+            {
+                error!("Failed to reply to command: {:?}", err);
+            }
+            return;
+        }
+
+        if let Err(err) = dm_command.dm_router(self, ctx, command).await {
+            error!("Failed to handle command: {:?}. Sending error message", err);
+            let embed = match err {
+                ResponseError::Execution(title, description) => CreateEmbed::new()
+                    .title(title)
+                    .description(description.unwrap_or(String::new()))
+                    .color(0xff0000),
+                ResponseError::Serenity(err) => CreateEmbed::new()
+                    .title("A Discord error occured while executing the command")
+                    .description(format!("```{err:?}```"))
+                    .color(0xff0000),
+                ResponseError::Redis(_) => return,
+            };
+            if let Err(err) = ctx.reply(command, Response::new().embed(embed)).await {
+                error!("Failed to send error message: {:?}", err);
+            }
+        }
+    }
 }
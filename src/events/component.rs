@@ -0,0 +1,49 @@
+use serenity::{
+    all::{ComponentInteraction, ModalInteraction},
+    prelude::Context,
+};
+
+use crate::models::handler::Handler;
+
+impl Handler {
+    pub async fn on_component(&self, ctx: Context, interaction: ComponentInteraction) {
+        let Some((kind, id)) = interaction.data.custom_id.split_once(':') else {
+            return;
+        };
+        let id = id.to_string();
+
+        match kind {
+            "appeal" => self.on_appeal_button(ctx, interaction, &id).await,
+            "appeal_accept" => {
+                self.on_appeal_review_button(ctx, interaction, &id, true)
+                    .await;
+            }
+            "appeal_deny" => {
+                self.on_appeal_review_button(ctx, interaction, &id, false)
+                    .await;
+            }
+            "lockdown_end" => self.on_lockdown_end_button(ctx, interaction, &id).await,
+            _ => {}
+        }
+    }
+
+    pub async fn on_modal(&self, ctx: Context, interaction: ModalInteraction) {
+        let Some((kind, id)) = interaction.data.custom_id.split_once(':') else {
+            return;
+        };
+        let id = id.to_string();
+
+        match kind {
+            "appeal_submit" => self.on_appeal_submit(ctx, interaction, &id).await,
+            "appeal_accept_submit" => {
+                self.on_appeal_review_submit(ctx, interaction, &id, true)
+                    .await;
+            }
+            "appeal_deny_submit" => {
+                self.on_appeal_review_submit(ctx, interaction, &id, false)
+                    .await;
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    sync::{atomic::AtomicBool, Arc},
     time::{Duration, Instant},
 };
 
@@ -9,142 +9,488 @@ use serenity::{
 };
 use tracing::{debug, error};
 
-use crate::models::{
-    actions::{ActionType, DatabaseAction},
-    handler::Handler,
+use crate::{
+    common::{
+        duration::Duration as ActionDuration,
+        scheduler::ScheduledTask,
+        timeout::{apply_timeout, remove_timeout, TIMEOUT_RENEWAL_SECONDS},
+    },
+    database::postgres::{actions::get_custom_action, guild::get_moderation_config},
+    models::{
+        actions::{ActionType, DatabaseAction},
+        command::CommandContext,
+        config::ModerationConfig,
+        handler::Handler,
+        response::ResponseError,
+        scheduled::{ScheduledAction, ScheduledActionType},
+    },
 };
 
-struct MuteRole {
-    mute_role: Option<i64>,
-}
+const KILLED_RETRY_DELAY: time::Duration = time::Duration::seconds(45);
+// Keeps a task that woke up before the database clock from spinning while the clocks catch up
+const EARLY_RETRY_DELAY: time::Duration = time::Duration::seconds(1);
+const MAINTENANCE_INTERVAL: Duration = Duration::from_hours(1);
 
-pub async fn expire_actions(handler: Handler, ctx: Context) {
-    loop {
-        if !sqlx::query!("SELECT active FROM global_kills WHERE feature = 'event.expiry'")
-            .fetch_one(&handler.main_database)
-            .await
-            .unwrap()
-            .active
+async fn renew_timeouts(handler: &Handler, ctx: &Context) {
+    let mutes = match sqlx::query_as!(
+        DatabaseAction,
+        "SELECT * FROM actions WHERE action_type = 'mute' AND active = true AND (expiry > now() + interval '1 day' OR expiry IS NULL) AND guild_id IN (SELECT guild_id FROM moderation_configuration WHERE use_timeouts = true)"
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(mutes) => mutes,
+        Err(err) => {
+            error!("Failed to fetch timeouts to renew: {}", err);
+            return;
+        }
+    };
+
+    let renewal_threshold =
+        time::OffsetDateTime::now_utc().unix_timestamp() + TIMEOUT_RENEWAL_SECONDS;
+    for mute in mutes {
+        let guild_id = GuildId::new(mute.guild_id as u64);
+        let user_id = UserId::new(mute.user_id as u64);
+        let Ok(member) = guild_id.member(ctx, user_id).await else {
+            continue;
+        };
+        if member
+            .communication_disabled_until
+            .is_some_and(|until| until.unix_timestamp() > renewal_threshold)
         {
-            tokio::time::sleep(Duration::from_secs(45)).await;
+            continue;
         }
 
-        let start = Instant::now();
-        let actions = match sqlx::query_as!(
-            DatabaseAction,
-            "SELECT * FROM actions WHERE expiry < now() AND active=true"
+        debug!(
+            "Renewing timeout for mute with ID {} from guild {}",
+            mute.id, mute.guild_id
+        );
+        if let Err(err) = apply_timeout(
+            ctx,
+            guild_id,
+            user_id,
+            mute.expiry.map(time::PrimitiveDateTime::assume_utc),
+            &format!("Renewing mute {}", mute.id),
         )
-        .fetch_all(&handler.main_database)
         .await
         {
-            Ok(actions) => actions,
-            Err(e) => {
-                error!("Failed to fetch actions: {}", e);
-                continue;
-            }
-        };
+            error!("Failed to renew timeout: {}", err);
+        }
+    }
+}
 
-        let mut guild_configurations: HashMap<i64, i64> = HashMap::new();
-        for action in &actions {
-            let guild_id = &action.guild_id;
-            if !guild_configurations.contains_key(guild_id) {
-                if let Ok(config) = sqlx::query_as!(
-                    MuteRole,
-                    "SELECT mute_role FROM moderation_configuration WHERE guild_id = $1",
-                    guild_id
+pub async fn lift_action(handler: &Handler, ctx: &Context, action: &DatabaseAction) {
+    match ActionType::from(action.action_type.as_str()) {
+        ActionType::Mute => match get_moderation_config(handler, action.guild_id).await {
+            Some(ModerationConfig {
+                use_timeouts: true, ..
+            }) => {
+                if let Err(err) = remove_timeout(
+                    ctx,
+                    GuildId::new(action.guild_id as u64),
+                    UserId::new(action.user_id as u64),
+                    &format!("Expiring mute {}", action.id),
                 )
-                .fetch_one(&handler.main_database)
                 .await
                 {
-                    if config.mute_role.is_some() {
-                        guild_configurations.insert(*guild_id, config.mute_role.unwrap());
-                    }
-                };
+                    error!("Failed to remove timeout: {}", err);
+                }
             }
-        }
+            Some(ModerationConfig {
+                mute_role: Some(mute_role),
+                ..
+            }) => {
+                if let Err(err) = ctx
+                    .http
+                    .remove_member_role(
+                        GuildId::new(action.guild_id as u64),
+                        UserId::new(action.user_id as u64),
+                        RoleId::new(mute_role as u64),
+                        Some(&format!("Expiring mute {}", action.id)),
+                    )
+                    .await
+                {
+                    error!("Failed to remove mute: {}", err);
+                }
 
-        for action in actions {
-            debug!(
-                "Expiring action with ID {} from guild {}",
-                action.id, action.guild_id
-            );
-            match ActionType::from(action.action_type.as_str()) {
-                ActionType::Mute => {
-                    if let Some(mute_role) = guild_configurations.get(&action.guild_id) {
-                        if let Err(err) = ctx
-                            .http
-                            .remove_member_role(
-                                GuildId::new(action.guild_id as u64),
-                                UserId::new(action.user_id as u64),
-                                RoleId::new(*mute_role as u64),
-                                Some(&format!("Expiring mute {}", action.id)),
-                            )
-                            .await
-                        {
-                            error!("Failed to remove mute: {}", err);
-                        }
-
-                        if let Err(err) = sqlx::query!("DELETE FROM role_recovery WHERE guild_id = $1 AND user_id = $2 AND role_id = $3", action.guild_id, action.user_id, mute_role).execute(&handler.main_database).await {
-                            error!("Could not delete mute role from role recovery roles. Failed with error: {:?}", err);
-                        }
-                    }
+                if let Err(err) = sqlx::query!("DELETE FROM role_recovery WHERE guild_id = $1 AND user_id = $2 AND role_id = $3", action.guild_id, action.user_id, mute_role).execute(&handler.main_database).await {
+                    error!("Could not delete mute role from role recovery roles. Failed with error: {:?}", err);
+                }
+            }
+            _ => {}
+        },
+        ActionType::Custom => {
+            if let Some(custom_action) = match &action.custom_action {
+                Some(name) => get_custom_action(handler, action.guild_id, name).await,
+                None => None,
+            } {
+                if let Err(err) = ctx
+                    .http
+                    .remove_member_role(
+                        GuildId::new(action.guild_id as u64),
+                        UserId::new(action.user_id as u64),
+                        RoleId::new(custom_action.role_id as u64),
+                        Some(&format!("Expiring {} {}", custom_action.name, action.id)),
+                    )
+                    .await
+                {
+                    error!("Failed to remove punishment role: {}", err);
                 }
-                ActionType::Ban => {
-                    if let Err(err) = ctx
-                        .http
-                        .remove_ban(
-                            GuildId::new(action.guild_id as u64),
-                            UserId::new(action.user_id as u64),
-                            Some(&format!("Expiring ban {}", action.id)),
-                        )
-                        .await
-                    {
-                        error!("Failed to remove ban: {}", err);
-                    }
+
+                if let Err(err) = sqlx::query!("DELETE FROM role_recovery WHERE guild_id = $1 AND user_id = $2 AND role_id = $3", action.guild_id, action.user_id, custom_action.role_id).execute(&handler.main_database).await {
+                    error!("Could not delete punishment role from role recovery roles. Failed with error: {:?}", err);
                 }
-                _ => {}
             }
-            if let Err(err) = sqlx::query!("UPDATE actions SET active=false WHERE id=$1", action.id)
-                .execute(&handler.main_database)
+        }
+        ActionType::Ban => {
+            if let Err(err) = ctx
+                .http
+                .remove_ban(
+                    GuildId::new(action.guild_id as u64),
+                    UserId::new(action.user_id as u64),
+                    Some(&format!("Expiring ban {}", action.id)),
+                )
                 .await
             {
-                error!("Failed to expire action with ID {}: {}", action.id, err);
-                continue;
+                error!("Failed to remove ban: {}", err);
             }
         }
+        _ => {}
+    }
+}
 
-        debug!(
-            "Finished expiring actions in {}ms",
-            start.elapsed().as_millis()
+async fn purge_removed_actions(handler: &Handler) {
+    match sqlx::query!(
+        "WITH purged AS (DELETE FROM removed_actions USING moderation_configuration WHERE removed_actions.guild_id = moderation_configuration.guild_id AND removed_at < now() - make_interval(days => removal_recovery_days) RETURNING id), history AS (DELETE FROM action_history WHERE action_id IN (SELECT id FROM purged)) DELETE FROM appeals WHERE action_id IN (SELECT id FROM purged)"
+    )
+    .execute(&handler.main_database)
+    .await
+    {
+        Ok(_) => debug!("Purged removed actions past their recovery period"),
+        Err(err) => error!("Failed to purge removed actions: {}", err),
+    }
+}
+
+async fn lift_scheduled(
+    handler: &Handler,
+    ctx: &Context,
+    scheduled: &ScheduledAction,
+    action_type: &str,
+) -> Result<(), ResponseError> {
+    let actions = sqlx::query!(
+        "SELECT id FROM actions WHERE guild_id = $1 AND user_id = $2 AND action_type = $3 AND active = true",
+        scheduled.guild_id,
+        scheduled.user_id,
+        action_type
+    )
+    .fetch_all(&handler.main_database)
+    .await?;
+
+    if actions.is_empty() && action_type == "ban" {
+        ctx.http
+            .remove_ban(
+                GuildId::new(scheduled.guild_id as u64),
+                UserId::new(scheduled.user_id as u64),
+                Some(&format!("Scheduled unban {}", scheduled.id)),
+            )
+            .await
+            .map_err(ResponseError::Serenity)?;
+    }
+    for action in actions {
+        handler
+            .expire_action(ctx, scheduled.guild_id, &action.id, scheduled.moderator_id)
+            .await?;
+    }
+    Ok(())
+}
+
+async fn execute_scheduled(handler: &Handler, ctx: &Context, scheduled: ScheduledAction) {
+    let guild_id = GuildId::new(scheduled.guild_id as u64);
+    let Ok(guild) = guild_id.to_partial_guild(&ctx.http).await else {
+        error!(
+            "Could not get guild {} for scheduled action {}",
+            guild_id, scheduled.id
+        );
+        return;
+    };
+    let context = CommandContext {
+        ctx: ctx.clone(),
+        has_responsed: Arc::new(AtomicBool::new(false)),
+        user_permissions: vec![],
+        highest_role: u16::MAX,
+        guild,
+    };
+    let duration = scheduled.duration.as_deref().map(ActionDuration::new);
+    let moderator_id = Some(scheduled.moderator_id);
+
+    let result = match ScheduledActionType::from(scheduled.action_type.as_str()) {
+        ScheduledActionType::Strike => Box::pin(handler.strike_user(
+            &context,
+            scheduled.guild_id,
+            scheduled.user_id,
+            scheduled.reason.clone(),
+            moderator_id,
+            duration,
+            1,
+        ))
+        .await
+        .map(|_| ()),
+        ScheduledActionType::Mute => Box::pin(handler.mute_user(
+            &context,
+            scheduled.guild_id,
+            scheduled.user_id,
+            scheduled.reason.clone(),
+            moderator_id,
+            duration.unwrap_or_else(|| ActionDuration::new("1d")),
+        ))
+        .await
+        .map(|_| ()),
+        ScheduledActionType::Kick => handler
+            .kick_user(
+                &context,
+                scheduled.guild_id,
+                scheduled.user_id,
+                scheduled.reason.clone(),
+                moderator_id,
+            )
+            .await
+            .map(|_| ()),
+        ScheduledActionType::Ban => Box::pin(handler.ban_user(
+            &context,
+            scheduled.guild_id,
+            scheduled.user_id,
+            scheduled.reason.clone(),
+            moderator_id,
+            duration,
+        ))
+        .await
+        .map(|_| ()),
+        ScheduledActionType::Unmute => lift_scheduled(handler, ctx, &scheduled, "mute").await,
+        ScheduledActionType::Unban => lift_scheduled(handler, ctx, &scheduled, "ban").await,
+    };
+
+    if let Err(err) = result {
+        error!(
+            "Failed to execute scheduled action {} from guild {}: {:?}",
+            scheduled.id, scheduled.guild_id, err
         );
-        tokio::time::sleep(Duration::from_secs(45)).await;
     }
 }
 
-pub async fn expire_giveaways(handler: Handler) {
-    loop {
-        if !sqlx::query!("SELECT active FROM global_kills WHERE feature = 'event.giveaways'")
-            .fetch_one(&handler.main_database)
+async fn feature_active(handler: &Handler, feature: &str) -> bool {
+    sqlx::query!(
+        "SELECT active FROM global_kills WHERE feature = $1",
+        feature
+    )
+    .fetch_one(&handler.main_database)
+    .await
+    .map_or(true, |record| record.active)
+}
+
+fn reschedule_early(handler: &Handler, at: time::OffsetDateTime, task: ScheduledTask) {
+    let at = at.max(time::OffsetDateTime::now_utc() + EARLY_RETRY_DELAY);
+    debug!(
+        "Task {:?} ran before it was due, rescheduling for {}",
+        task, at
+    );
+    handler.scheduler.schedule(at, task);
+}
+
+async fn expire_action(handler: &Handler, ctx: &Context, id: &str) {
+    // The expiry may have been changed or the action lifted since it was queued
+    let action = match sqlx::query_as!(
+        DatabaseAction,
+        "SELECT * FROM actions WHERE id = $1 AND active = true AND expiry <= now()",
+        id
+    )
+    .fetch_optional(&handler.main_database)
+    .await
+    {
+        Ok(Some(action)) => action,
+        Ok(None) => {
+            // The scheduler can wake up slightly before the database considers the action due
+            match sqlx::query!(
+                r#"SELECT expiry AS "expiry!" FROM actions WHERE id = $1 AND active = true AND expiry > now()"#,
+                id
+            )
+            .fetch_optional(&handler.main_database)
             .await
-            .unwrap()
-            .active
-        {
-            tokio::time::sleep(Duration::from_secs(45)).await;
+            {
+                Ok(Some(action)) => reschedule_early(
+                    handler,
+                    action.expiry.assume_utc(),
+                    ScheduledTask::ActionExpiry(id.to_string()),
+                ),
+                Ok(None) => {}
+                Err(err) => error!("Failed to fetch action with ID {}: {}", id, err),
+            }
+            return;
+        }
+        Err(err) => {
+            error!("Failed to fetch action with ID {}: {}", id, err);
+            return;
         }
+    };
 
-        let start = Instant::now();
+    debug!(
+        "Expiring action with ID {} from guild {}",
+        action.id, action.guild_id
+    );
+    lift_action(handler, ctx, &action).await;
+    if let Err(err) = sqlx::query!("UPDATE actions SET active=false WHERE id=$1", action.id)
+        .execute(&handler.main_database)
+        .await
+    {
+        error!("Failed to expire action with ID {}: {}", action.id, err);
+    }
+}
 
-        if let Err(err) = sqlx::query!("DELETE FROM giveaways WHERE duration < NOW()")
-            .execute(&handler.main_database)
-            .await
-        {
-            error!("Failed to delete expired giveaways: {}", err);
+async fn run_scheduled_action(handler: &Handler, ctx: &Context, id: i32) {
+    let scheduled = match sqlx::query_as!(
+        ScheduledAction,
+        "DELETE FROM scheduled_actions WHERE id = $1 AND execute_at <= now() RETURNING *",
+        id
+    )
+    .fetch_optional(&handler.main_database)
+    .await
+    {
+        Ok(Some(scheduled)) => scheduled,
+        Ok(None) => {
+            match sqlx::query!("SELECT execute_at FROM scheduled_actions WHERE id = $1", id)
+                .fetch_optional(&handler.main_database)
+                .await
+            {
+                Ok(Some(scheduled)) => reschedule_early(
+                    handler,
+                    scheduled.execute_at.assume_utc(),
+                    ScheduledTask::ScheduledAction(id),
+                ),
+                Ok(None) => {}
+                Err(err) => error!("Failed to fetch scheduled action with ID {}: {}", id, err),
+            }
+            return;
+        }
+        Err(err) => {
+            error!("Failed to fetch scheduled action with ID {}: {}", id, err);
+            return;
+        }
+    };
+
+    debug!(
+        "Executing scheduled action with ID {} from guild {}",
+        scheduled.id, scheduled.guild_id
+    );
+    execute_scheduled(handler, ctx, scheduled).await;
+}
+
+async fn cleanup_giveaway(handler: &Handler, id: i64) {
+    if let Err(err) = sqlx::query!(
+        "DELETE FROM giveaways WHERE id = $1 AND duration < NOW()",
+        id
+    )
+    .execute(&handler.main_database)
+    .await
+    {
+        error!("Failed to delete expired giveaway {}: {}", id, err);
+    }
+}
+
+pub async fn load_scheduler(handler: &Handler) {
+    let start = Instant::now();
+    let mut tasks = vec![];
+
+    match sqlx::query!(
+        r#"SELECT id, expiry AS "expiry!" FROM actions WHERE active = true AND expiry IS NOT NULL"#
+    )
+    .fetch_all(&handler.main_database)
+    .await
+    {
+        Ok(actions) => tasks.extend(actions.into_iter().map(|action| {
+            (
+                action.expiry.assume_utc(),
+                ScheduledTask::ActionExpiry(action.id),
+            )
+        })),
+        Err(err) => error!("Failed to load action expiries: {}", err),
+    }
+
+    match sqlx::query!("SELECT id, execute_at FROM scheduled_actions")
+        .fetch_all(&handler.main_database)
+        .await
+    {
+        Ok(scheduled) => tasks.extend(scheduled.into_iter().map(|scheduled| {
+            (
+                scheduled.execute_at.assume_utc(),
+                ScheduledTask::ScheduledAction(scheduled.id),
+            )
+        })),
+        Err(err) => error!("Failed to load scheduled actions: {}", err),
+    }
+
+    match sqlx::query!("SELECT id, duration FROM giveaways")
+        .fetch_all(&handler.main_database)
+        .await
+    {
+        Ok(giveaways) => tasks.extend(giveaways.into_iter().map(|giveaway| {
+            (
+                giveaway.duration.assume_utc(),
+                ScheduledTask::GiveawayCleanup(giveaway.id),
+            )
+        })),
+        Err(err) => error!("Failed to load giveaway expiries: {}", err),
+    }
+
+    handler.scheduler.replace(tasks);
+    debug!(
+        "Loaded {} scheduled tasks in {}ms",
+        handler.scheduler.pending(),
+        start.elapsed().as_millis()
+    );
+}
+
+pub async fn run_scheduler(handler: Handler, ctx: Context) {
+    loop {
+        handler.scheduler.wait().await;
+
+        for task in handler.scheduler.take_due() {
+            let feature = match task {
+                ScheduledTask::ActionExpiry(_) => "event.expiry",
+                ScheduledTask::ScheduledAction(_) => "event.scheduled",
+                ScheduledTask::GiveawayCleanup(_) => "event.giveaways",
+            };
+            if !feature_active(&handler, feature).await {
+                handler
+                    .scheduler
+                    .schedule(time::OffsetDateTime::now_utc() + KILLED_RETRY_DELAY, task);
+                continue;
+            }
+
+            match task {
+                ScheduledTask::ActionExpiry(id) => expire_action(&handler, &ctx, &id).await,
+                ScheduledTask::ScheduledAction(id) => {
+                    run_scheduled_action(&handler, &ctx, id).await;
+                }
+                ScheduledTask::GiveawayCleanup(id) => cleanup_giveaway(&handler, id).await,
+            }
         }
+    }
+}
+
+pub async fn maintain_actions(handler: Handler, ctx: Context) {
+    loop {
+        tokio::time::sleep(MAINTENANCE_INTERVAL).await;
 
+        let start = Instant::now();
+        if feature_active(&handler, "event.expiry").await {
+            renew_timeouts(&handler, &ctx).await;
+        }
+        // Retention has its own switch so pausing expiries doesn't keep removed actions around
+        if feature_active(&handler, "event.removal_purge").await {
+            purge_removed_actions(&handler).await;
+        }
         debug!(
-            "Finished expiring giveaways in {}ms",
+            "Finished action maintenance in {}ms",
             start.elapsed().as_millis()
         );
-        tokio::time::sleep(Duration::from_secs(45)).await;
     }
 }
@@ -0,0 +1,130 @@
+use std::sync::{atomic::AtomicBool, Arc};
+
+use serenity::{all::Message as DiscordMessage, prelude::Context};
+use tracing::{debug, error};
+
+use crate::{
+    common::duration::Duration,
+    database::postgres::filters::get_compiled_filter_rules,
+    events::spam::FALLBACK_DURATION,
+    models::{
+        command::CommandContext,
+        filter::{find_matching_rule, CompiledFilterRule, FilterAction},
+        handler::Handler,
+    },
+};
+
+impl Handler {
+    pub async fn on_filter_check(&self, ctx: &Context, message: &DiscordMessage) {
+        let Some(guild_id) = message.guild_id else {
+            return;
+        };
+        if message.author.bot || message.content.is_empty() {
+            return;
+        }
+
+        if !sqlx::query!("SELECT active FROM global_kills WHERE feature = 'event.filter'")
+            .fetch_one(&self.main_database)
+            .await
+            .unwrap()
+            .active
+        {
+            return;
+        }
+
+        let roles = message
+            .member
+            .as_ref()
+            .map(|member| {
+                member
+                    .roles
+                    .iter()
+                    .map(|role| role.get() as i64)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let guild_rules = get_compiled_filter_rules(self, guild_id.get() as i64).await;
+        let Some(CompiledFilterRule { rule, .. }) = find_matching_rule(
+            &guild_rules,
+            &message.content,
+            message.channel_id.get() as i64,
+            &roles,
+        ) else {
+            return;
+        };
+        debug!(
+            "Message {} by {} matched filter rule {}",
+            message.id, message.author.id, rule.id
+        );
+
+        if let Err(err) = message.delete(&ctx.http).await {
+            error!("Failed to delete filtered message: {:?}", err);
+        }
+        if rule.action == FilterAction::Delete {
+            return;
+        }
+
+        let Ok(guild) = guild_id.to_partial_guild(&ctx.http).await else {
+            error!("Could not get guild {} from cache", guild_id);
+            return;
+        };
+        let context = CommandContext {
+            ctx: ctx.clone(),
+            has_responsed: Arc::new(AtomicBool::new(false)),
+            user_permissions: vec![],
+            highest_role: u16::MAX,
+            guild,
+        };
+        let guild_id = guild_id.get() as i64;
+        let user_id = message.author.id.get() as i64;
+        let reason = format!("Triggered filter rule \"{}\"", rule.name);
+        let duration = rule.duration.as_deref().map(Duration::new);
+
+        let result = match rule.action {
+            FilterAction::Strike => {
+                let duration = match duration {
+                    Some(duration) => duration,
+                    None => self.automatic_strike_duration(guild_id).await,
+                };
+                Box::pin(self.strike_user(
+                    &context,
+                    guild_id,
+                    user_id,
+                    reason,
+                    None,
+                    Some(duration),
+                    1,
+                ))
+                .await
+                .map(|_| ())
+            }
+            FilterAction::Mute => Box::pin(self.mute_user(
+                &context,
+                guild_id,
+                user_id,
+                reason,
+                None,
+                duration.unwrap_or_else(|| Duration::new(FALLBACK_DURATION)),
+            ))
+            .await
+            .map(|_| ()),
+            FilterAction::Kick => {
+                Box::pin(self.kick_user(&context, guild_id, user_id, reason, None))
+                    .await
+                    .map(|_| ())
+            }
+            FilterAction::Ban => {
+                Box::pin(self.ban_user(&context, guild_id, user_id, reason, None, duration))
+                    .await
+                    .map(|_| ())
+            }
+            FilterAction::Delete => Ok(()),
+        };
+        if let Err(err) = result {
+            error!(
+                "Could not punish user for filtered message. Failed with error: {:?}",
+                err
+            );
+        }
+    }
+}
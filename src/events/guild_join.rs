@@ -27,5 +27,21 @@ impl Handler {
         .execute(&self.main_database)
         .await
         .expect("Failed to insert guild role recovery config for guild");
+
+        sqlx::query!(
+            "INSERT INTO anti_raid_configuration (guild_id) VALUES ($1)",
+            guild.id.get() as i64
+        )
+        .execute(&self.main_database)
+        .await
+        .expect("Failed to insert anti-raid configuration for guild");
+
+        sqlx::query!(
+            "INSERT INTO spam_configuration (guild_id) VALUES ($1)",
+            guild.id.get() as i64
+        )
+        .execute(&self.main_database)
+        .await
+        .expect("Failed to insert spam configuration for guild");
     }
 }
@@ -19,5 +19,39 @@ impl Handler {
         .execute(&self.main_database)
         .await
         .expect("Failed to delete logging configuration for guild");
+
+        sqlx::query!(
+            "DELETE FROM logging_ignores WHERE guild_id = $1",
+            guild.id.get() as i64
+        )
+        .execute(&self.main_database)
+        .await
+        .expect("Failed to delete logging ignores for guild");
+
+        sqlx::query!(
+            "DELETE FROM anti_raid_configuration WHERE guild_id = $1",
+            guild.id.get() as i64
+        )
+        .execute(&self.main_database)
+        .await
+        .expect("Failed to delete anti-raid configuration for guild");
+
+        sqlx::query!(
+            "DELETE FROM spam_configuration WHERE guild_id = $1",
+            guild.id.get() as i64
+        )
+        .execute(&self.main_database)
+        .await
+        .expect("Failed to delete spam configuration for guild");
+
+        sqlx::query!(
+            "DELETE FROM scheduled_actions WHERE guild_id = $1",
+            guild.id.get() as i64
+        )
+        .execute(&self.main_database)
+        .await
+        .expect("Failed to delete scheduled actions for guild");
+
+        self.filter_cache.invalidate(guild.id.get() as i64);
     }
 }
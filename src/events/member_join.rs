@@ -4,7 +4,10 @@ use serenity::{
 };
 use tracing::error;
 
-use crate::models::handler::Handler;
+use crate::{
+    common::timeout::apply_timeout, database::postgres::guild::get_moderation_config,
+    models::handler::Handler,
+};
 
 impl Handler {
     pub async fn on_member_join(&self, ctx: Context, member: Member) {
@@ -58,5 +61,45 @@ impl Handler {
                 );
             }
         }
+
+        if !get_moderation_config(self, member.guild_id.get() as i64)
+            .await
+            .is_some_and(|config| config.use_timeouts)
+        {
+            return;
+        }
+
+        match sqlx::query!(
+            "SELECT expiry FROM actions WHERE guild_id = $1 AND user_id = $2 AND action_type = 'mute' AND active = true AND (expiry > now() OR expiry IS NULL) ORDER BY expiry DESC NULLS FIRST LIMIT 1",
+            member.guild_id.get() as i64,
+            member.user.id.get() as i64
+        )
+        .fetch_optional(&self.main_database)
+        .await
+        {
+            Ok(Some(mute)) => {
+                if let Err(err) = apply_timeout(
+                    &ctx,
+                    member.guild_id,
+                    member.user.id,
+                    mute.expiry.map(time::PrimitiveDateTime::assume_utc),
+                    "Role recovery on rejoin",
+                )
+                .await
+                {
+                    error!(
+                        "Could not timeout user during rejoin. Failed with error: {:?}",
+                        err
+                    );
+                }
+            }
+            Ok(None) => {}
+            Err(err) => {
+                error!(
+                    "Could not get active mutes from database during rejoin. Failed with error: {:?}",
+                    err
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,407 @@
+use std::{collections::HashMap, fmt::Write};
+
+use serenity::{
+    all::{
+        audit_log::{Action, MemberAction},
+        GuildId, InviteCreateEvent, Member, RichInvite, RoleId, User, UserId,
+    },
+    builder::{CreateEmbed, CreateEmbedFooter},
+    prelude::Context,
+};
+use tracing::error;
+
+use crate::{
+    common::logging::{
+        cached_roles, get_audit_log_entry, get_guild_log_channel, is_log_ignored, LogType,
+    },
+    models::handler::Handler,
+};
+
+const MAX_LISTED_ROLES: usize = 20;
+
+fn invites_key(guild_id: GuildId) -> String {
+    format!("member_log:{guild_id}:invites")
+}
+
+fn account_age(user: &User) -> String {
+    let age = time::Duration::seconds(
+        time::OffsetDateTime::now_utc().unix_timestamp() - user.created_at().unix_timestamp(),
+    );
+    match age.whole_days() {
+        0 => format!("{} hours (new account)", age.whole_hours()),
+        days @ 1..=6 => format!("{days} days (new account)"),
+        days => format!("{days} days"),
+    }
+}
+
+fn format_roles(roles: &[RoleId]) -> String {
+    let mut formatted = roles
+        .iter()
+        .take(MAX_LISTED_ROLES)
+        .map(|role| format!("<@&{role}>"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if roles.len() > MAX_LISTED_ROLES {
+        write!(formatted, " and {} more", roles.len() - MAX_LISTED_ROLES).unwrap();
+    }
+    formatted
+}
+
+async fn audit_log_executor(
+    ctx: &Context,
+    guild_id: GuildId,
+    action: MemberAction,
+    target: UserId,
+) -> Option<(UserId, Option<String>)> {
+    get_audit_log_entry(ctx, guild_id, Action::Member(action), Some(target.get()))
+        .await
+        .map(|entry| (entry.user_id, entry.reason))
+}
+
+async fn cache_invites(
+    connection: &mut redis::aio::MultiplexedConnection,
+    guild_id: GuildId,
+    invites: &[RichInvite],
+) {
+    let mut pipe = redis::pipe();
+    pipe.cmd("DEL").arg(invites_key(guild_id)).ignore();
+    for invite in invites {
+        pipe.cmd("HSET")
+            .arg(invites_key(guild_id))
+            .arg(&invite.code)
+            .arg(invite.uses)
+            .ignore();
+    }
+    if let Err(err) = pipe.query_async::<_, ()>(connection).await {
+        error!("Failed to cache invites in Redis: {:?}", err);
+    }
+}
+
+impl Handler {
+    /// Caches the guild's invite uses when it becomes available, so the first join after a
+    /// restart can still be matched to an invite
+    pub async fn seed_invite_cache(&self, ctx: &Context, guild_id: GuildId) {
+        if get_guild_log_channel(self, guild_id, &LogType::Member)
+            .await
+            .is_none()
+        {
+            return;
+        }
+        let invites = match ctx.http.get_guild_invites(guild_id).await {
+            Ok(invites) => invites,
+            Err(err) => {
+                error!("Failed to get invites for guild {}: {:?}", guild_id, err);
+                return;
+            }
+        };
+        match self.redis_database.get_multiplexed_async_connection().await {
+            Ok(mut connection) => cache_invites(&mut connection, guild_id, &invites).await,
+            Err(err) => error!("Failed to get Redis connection: {:?}", err),
+        }
+    }
+
+    async fn find_used_invite(&self, ctx: &Context, guild_id: GuildId) -> Option<String> {
+        let invites = match ctx.http.get_guild_invites(guild_id).await {
+            Ok(invites) => invites,
+            Err(err) => {
+                error!("Failed to get invites for guild {}: {:?}", guild_id, err);
+                return None;
+            }
+        };
+
+        let mut connection = match self.redis_database.get_multiplexed_async_connection().await {
+            Ok(connection) => connection,
+            Err(err) => {
+                error!("Failed to get Redis connection: {:?}", err);
+                return None;
+            }
+        };
+        let cached: HashMap<String, u64> = match redis::cmd("HGETALL")
+            .arg(invites_key(guild_id))
+            .query_async(&mut connection)
+            .await
+        {
+            Ok(cached) => cached,
+            Err(err) => {
+                error!("Failed to get cached invites from Redis: {:?}", err);
+                HashMap::new()
+            }
+        };
+        cache_invites(&mut connection, guild_id, &invites).await;
+
+        if cached.is_empty() {
+            return None;
+        }
+        if let Some(invite) = invites.iter().find(|invite| {
+            cached
+                .get(&invite.code)
+                .is_some_and(|uses| invite.uses > *uses)
+                || (!cached.contains_key(&invite.code) && invite.uses > 0)
+        }) {
+            return Some(invite.code.clone());
+        }
+        // An invite that hit its use limit is deleted before the join arrives
+        let mut missing = cached
+            .keys()
+            .filter(|code| !invites.iter().any(|invite| &invite.code == *code));
+        match (missing.next(), missing.next()) {
+            (Some(code), None) => Some(code.clone()),
+            _ => None,
+        }
+    }
+
+    pub async fn on_invite_create(&self, event: InviteCreateEvent) {
+        let Some(guild_id) = event.guild_id else {
+            return;
+        };
+        let mut connection = match self.redis_database.get_multiplexed_async_connection().await {
+            Ok(connection) => connection,
+            Err(err) => {
+                error!("Failed to get Redis connection: {:?}", err);
+                return;
+            }
+        };
+        if let Err(err) = redis::cmd("HSET")
+            .arg(invites_key(guild_id))
+            .arg(event.code)
+            .arg(0)
+            .query_async::<_, ()>(&mut connection)
+            .await
+        {
+            error!("Failed to cache invite in Redis: {:?}", err);
+        }
+    }
+
+    pub async fn on_member_join_log(&self, ctx: &Context, member: &Member) {
+        let Some(channel) = get_guild_log_channel(self, member.guild_id, &LogType::Member).await
+        else {
+            return;
+        };
+        if is_log_ignored(
+            self,
+            ctx,
+            member.guild_id,
+            &LogType::Member,
+            &[],
+            Some(member.user.id),
+            &member.roles,
+        )
+        .await
+        {
+            return;
+        }
+        let invite = self.find_used_invite(ctx, member.guild_id).await;
+
+        let embed = CreateEmbed::new()
+            .title("Member joined")
+            .description(format!("<@{}> joined the server", member.user.id))
+            .field("Account age", account_age(&member.user), true)
+            .field(
+                "Invite",
+                invite.map_or("Unknown".to_string(), |code| format!("`{code}`")),
+                true,
+            )
+            .footer(CreateEmbedFooter::new(format!(
+                "User {} joined",
+                member.user.id
+            )))
+            .color(0x2dc770);
+        self.log_dispatcher
+            .dispatch_mention(channel, member.user.id, embed);
+    }
+
+    pub async fn on_member_leave_log(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+        user: &User,
+        member: Option<&Member>,
+    ) {
+        let Some(channel) = get_guild_log_channel(self, guild_id, &LogType::Member).await else {
+            return;
+        };
+        if is_log_ignored(
+            self,
+            ctx,
+            guild_id,
+            &LogType::Member,
+            &[],
+            Some(user.id),
+            member.map_or(&[], |member| member.roles.as_slice()),
+        )
+        .await
+        {
+            return;
+        }
+
+        let mut embed = CreateEmbed::new()
+            .title("Member left")
+            .description(format!("<@{}> left the server", user.id))
+            .footer(CreateEmbedFooter::new(format!("User {} left", user.id)))
+            .color(0xee2e46);
+        if let Some(member) = member {
+            if let Some(joined_at) = member.joined_at {
+                embed = embed.field(
+                    "Joined",
+                    format!("<t:{}:R>", joined_at.unix_timestamp()),
+                    true,
+                );
+            }
+            if !member.roles.is_empty() {
+                embed = embed.field("Roles", format_roles(&member.roles), false);
+            }
+        }
+        self.log_dispatcher
+            .dispatch_mention(channel, user.id, embed);
+    }
+
+    pub async fn on_member_update_log(&self, ctx: &Context, old: &Member, new: &Member) {
+        if old.nick == new.nick && old.roles == new.roles {
+            return;
+        }
+        let Some(channel) = get_guild_log_channel(self, new.guild_id, &LogType::Member).await
+        else {
+            return;
+        };
+        if is_log_ignored(
+            self,
+            ctx,
+            new.guild_id,
+            &LogType::Member,
+            &[],
+            Some(new.user.id),
+            &new.roles,
+        )
+        .await
+        {
+            return;
+        }
+
+        if old.nick != new.nick {
+            let mut embed = CreateEmbed::new()
+                .title("Nickname changed")
+                .description(format!("<@{}> changed their nickname", new.user.id))
+                .field(
+                    "Before",
+                    old.nick.clone().unwrap_or("None".to_string()),
+                    true,
+                )
+                .field(
+                    "After",
+                    new.nick.clone().unwrap_or("None".to_string()),
+                    true,
+                )
+                .footer(CreateEmbedFooter::new(format!(
+                    "User {} changed nickname",
+                    new.user.id
+                )))
+                .color(0x0abfd6);
+            if let Some((executor, _)) =
+                audit_log_executor(ctx, new.guild_id, MemberAction::Update, new.user.id).await
+            {
+                if executor != new.user.id {
+                    embed = embed
+                        .description(format!("<@{}>'s nickname was changed", new.user.id))
+                        .field("Moderator", format!("<@{executor}>"), true);
+                }
+            }
+            self.log_dispatcher
+                .dispatch_mention(channel, new.user.id, embed);
+        }
+
+        let added = new
+            .roles
+            .iter()
+            .copied()
+            .filter(|role| !old.roles.contains(role))
+            .collect::<Vec<_>>();
+        let removed = old
+            .roles
+            .iter()
+            .copied()
+            .filter(|role| !new.roles.contains(role))
+            .collect::<Vec<_>>();
+        if added.is_empty() && removed.is_empty() {
+            return;
+        }
+
+        let mut embed = CreateEmbed::new()
+            .title("Roles updated")
+            .description(format!("<@{}>'s roles were updated", new.user.id))
+            .footer(CreateEmbedFooter::new(format!(
+                "User {} roles updated",
+                new.user.id
+            )))
+            .color(0x0abfd6);
+        if !added.is_empty() {
+            embed = embed.field("Added", format_roles(&added), false);
+        }
+        if !removed.is_empty() {
+            embed = embed.field("Removed", format_roles(&removed), false);
+        }
+        if let Some((executor, _)) =
+            audit_log_executor(ctx, new.guild_id, MemberAction::RoleUpdate, new.user.id).await
+        {
+            embed = embed.field("Moderator", format!("<@{executor}>"), true);
+        }
+        self.log_dispatcher
+            .dispatch_mention(channel, new.user.id, embed);
+    }
+
+    pub async fn on_ban_log(&self, ctx: &Context, guild_id: GuildId, user: &User, banned: bool) {
+        let Some(channel) = get_guild_log_channel(self, guild_id, &LogType::Member).await else {
+            return;
+        };
+        if is_log_ignored(
+            self,
+            ctx,
+            guild_id,
+            &LogType::Member,
+            &[],
+            Some(user.id),
+            &cached_roles(ctx, guild_id, user.id),
+        )
+        .await
+        {
+            return;
+        }
+        let action = if banned {
+            MemberAction::BanAdd
+        } else {
+            MemberAction::BanRemove
+        };
+        let executor = audit_log_executor(ctx, guild_id, action, user.id).await;
+        // Reaper logs its own bans and unbans as actions
+        if executor
+            .as_ref()
+            .is_some_and(|(executor, _)| *executor == ctx.cache.current_user().id)
+        {
+            return;
+        }
+
+        let (title, verb) = if banned {
+            ("Member banned", "banned")
+        } else {
+            ("Member unbanned", "unbanned")
+        };
+        let mut embed = CreateEmbed::new()
+            .title(title)
+            .description(format!("<@{}> was {verb} outside of Reaper", user.id))
+            .footer(CreateEmbedFooter::new(format!("User {} {verb}", user.id)))
+            .color(if banned { 0xee2e46 } else { 0x2dc770 });
+        match executor {
+            Some((executor, reason)) => {
+                embed = embed
+                    .field("Moderator", format!("<@{executor}>"), true)
+                    .field(
+                        "Reason",
+                        reason.unwrap_or("No reason provided".to_string()),
+                        true,
+                    );
+            }
+            None => embed = embed.field("Moderator", "Unknown", true),
+        }
+        self.log_dispatcher
+            .dispatch_mention(channel, user.id, embed);
+    }
+}
@@ -1,10 +1,10 @@
-use serenity::all::Message as DiscordMessage;
+use serenity::{all::Message as DiscordMessage, prelude::Context};
 use tracing::error;
 
 use crate::models::{handler::Handler, message::Message};
 
 impl Handler {
-    pub async fn on_message(&self, message: DiscordMessage) {
+    pub async fn on_message(&self, ctx: Context, message: DiscordMessage) {
         let attachment_url = message
             .attachments
             .first()
@@ -16,12 +16,16 @@ impl Handler {
             message.author.id.get() as i64,
             message.channel_id.get() as i64,
             message.id.get() as i64,
-            message.content,
+            message.content.clone(),
             attachment_url,
         )
         .await
         {
             error!("Failed to create message: {:?}", err);
-        };
+        }
+
+        if !self.on_spam_check(&ctx, &message).await {
+            self.on_filter_check(&ctx, &message).await;
+        }
     }
 }
@@ -0,0 +1,218 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use serenity::{
+    all::{
+        audit_log::{Action, MessageAction},
+        ChannelId, GuildId, MessageId, UserId,
+    },
+    builder::{CreateAttachment, CreateEmbed, CreateEmbedFooter},
+    prelude::Context,
+};
+use tracing::error;
+
+use crate::{
+    common::{
+        log_dispatcher::send_log_file,
+        logging::{
+            cached_roles, get_audit_log_entry, get_guild_log_channel, is_log_ignored, LogType,
+        },
+    },
+    models::{
+        handler::Handler,
+        message::{Message, MessageQuery},
+    },
+};
+
+fn format_timestamp(message_id: MessageId) -> String {
+    let timestamp =
+        time::OffsetDateTime::from_unix_timestamp(message_id.created_at().unix_timestamp())
+            .unwrap_or(time::OffsetDateTime::UNIX_EPOCH);
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        timestamp.year(),
+        u8::from(timestamp.month()),
+        timestamp.day(),
+        timestamp.hour(),
+        timestamp.minute(),
+        timestamp.second()
+    )
+}
+
+fn channel_name(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> String {
+    ctx.cache
+        .guild(guild_id)
+        .and_then(|guild| {
+            guild
+                .channels
+                .get(&channel_id)
+                .map(|channel| channel.name.clone())
+                .or_else(|| {
+                    guild
+                        .threads
+                        .iter()
+                        .find(|thread| thread.id == channel_id)
+                        .map(|thread| thread.name.clone())
+                })
+        })
+        .unwrap_or_else(|| channel_id.to_string())
+}
+
+fn render_transcript(
+    channel_name: &str,
+    deleted: usize,
+    messages: &[Message],
+    authors: &HashMap<i64, String>,
+) -> String {
+    let mut transcript = format!(
+        "{} messages deleted from #{}, {} of them are logged\n\n",
+        deleted,
+        channel_name,
+        messages.len()
+    );
+    for message in messages {
+        let author = authors
+            .get(&message.user_id)
+            .map_or("Unknown user", String::as_str);
+        writeln!(
+            transcript,
+            "[{}] {} ({}): {}",
+            format_timestamp(MessageId::new(message.id as u64)),
+            author,
+            message.user_id,
+            message.content
+        )
+        .unwrap();
+        if let Some(attachment) = &message.attachment {
+            writeln!(transcript, "    Attachment: {attachment}").unwrap();
+        }
+    }
+    transcript
+}
+
+impl Handler {
+    async fn author_name(&self, ctx: &Context, user_id: UserId) -> String {
+        if let Some(user) = ctx.cache.user(user_id) {
+            return user.name.clone();
+        }
+        match ctx.http.get_user(user_id).await {
+            Ok(user) => user.name,
+            Err(err) => {
+                error!("Failed to get author: {:?}", err);
+                "Unknown user".to_string()
+            }
+        }
+    }
+
+    pub async fn on_message_delete_bulk(
+        &self,
+        ctx: Context,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_ids: Vec<MessageId>,
+    ) {
+        let Some(channel) = get_guild_log_channel(self, guild_id, &LogType::Message).await else {
+            return;
+        };
+        if is_log_ignored(
+            self,
+            &ctx,
+            guild_id,
+            &LogType::Message,
+            &[channel_id],
+            None,
+            &[],
+        )
+        .await
+        {
+            return;
+        }
+
+        // Messages sent before Reaper cached them are only counted
+        let queries = message_ids
+            .iter()
+            .map(|message_id| MessageQuery {
+                guild: guild_id.get() as i64,
+                channel: channel_id.get() as i64,
+                message: message_id.get() as i64,
+            })
+            .collect::<Vec<_>>();
+        let mut messages = MessageQuery::get_messages(&queries, &self.redis_database)
+            .await
+            .unwrap_or_default();
+        messages.sort_by_key(|message| message.id);
+
+        // Ignored users and roles are left out of the transcript, but still counted
+        let mut authors = HashMap::new();
+        let mut ignored = HashSet::new();
+        for message in &messages {
+            let user_id = UserId::new(message.user_id as u64);
+            if authors.contains_key(&message.user_id) || ignored.contains(&message.user_id) {
+                continue;
+            }
+            if is_log_ignored(
+                self,
+                &ctx,
+                guild_id,
+                &LogType::Message,
+                &[],
+                Some(user_id),
+                &cached_roles(&ctx, guild_id, user_id),
+            )
+            .await
+            {
+                ignored.insert(message.user_id);
+            } else {
+                authors.insert(message.user_id, self.author_name(&ctx, user_id).await);
+            }
+        }
+        messages.retain(|message| !ignored.contains(&message.user_id));
+
+        let mut embed = CreateEmbed::new()
+            .title("Messages Bulk Deleted")
+            .description(format!(
+                "{} messages were deleted from <#{}>",
+                message_ids.len(),
+                channel_id
+            ))
+            .field("Logged", messages.len().to_string(), true)
+            .footer(CreateEmbedFooter::new(format!(
+                "{} messages deleted from {}",
+                message_ids.len(),
+                channel_id
+            )))
+            .color(0xee2e46);
+        embed = match get_audit_log_entry(
+            &ctx,
+            guild_id,
+            Action::Message(MessageAction::BulkDelete),
+            Some(channel_id.get()),
+        )
+        .await
+        {
+            Some(entry) => embed.field("Moderator", format!("<@{}>", entry.user_id), true),
+            None => embed.field("Moderator", "Unknown", true),
+        };
+
+        if messages.is_empty() {
+            self.log_dispatcher.dispatch(channel, embed);
+            return;
+        }
+        let transcript = render_transcript(
+            &channel_name(&ctx, guild_id, channel_id),
+            message_ids.len(),
+            &messages,
+            &authors,
+        );
+        send_log_file(
+            self,
+            &ctx,
+            channel,
+            embed,
+            CreateAttachment::bytes(transcript, format!("transcript-{channel_id}.txt")),
+        )
+        .await;
+    }
+}
@@ -1,12 +1,12 @@
 use serenity::{
-    all::{ChannelId, MessageUpdateEvent, UserId},
-    builder::{CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, CreateMessage},
+    all::{ChannelId, GuildId, MessageUpdateEvent, UserId},
+    builder::{CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter},
     prelude::Context,
 };
 use tracing::error;
 
 use crate::{
-    common::logging::{get_log_channel, LogType},
+    common::logging::{cached_roles, get_log_channel, is_log_ignored, LogType},
     models::{config::LoggingConfig, handler::Handler, message::MessageQuery},
 };
 
@@ -30,14 +30,6 @@ impl Handler {
             }
         };
 
-        let author = match ctx.http.get_user(UserId::new(message.user_id as u64)).await {
-            Ok(author) => author,
-            Err(err) => {
-                error!("Failed to get author: {:?}", err);
-                return;
-            }
-        };
-
         if event.content.is_none() && message.attachment.is_none() && event.attachments.is_none() {
             return;
         }
@@ -73,6 +65,37 @@ impl Handler {
             }
         }
 
+        let guild = GuildId::new(guild_id as u64);
+        let user_id = UserId::new(message.user_id as u64);
+        if is_log_ignored(
+            self,
+            &ctx,
+            guild,
+            &LogType::Message,
+            &[event.channel_id],
+            Some(user_id),
+            &cached_roles(&ctx, guild, user_id),
+        )
+        .await
+        {
+            // The cached copy still has to follow the edit for later logs
+            if let Err(err) = message
+                .update(&self.redis_database, content, current_attachment)
+                .await
+            {
+                error!("Failed to update message: {:?}", err);
+            }
+            return;
+        }
+
+        let author = match ctx.http.get_user(user_id).await {
+            Ok(author) => author,
+            Err(err) => {
+                error!("Failed to get author: {:?}", err);
+                return;
+            }
+        };
+
         let mut embed = CreateEmbed::new()
             .title("Message Edited")
             .color(0xf5e0a9)
@@ -98,20 +121,14 @@ impl Handler {
 
         if let Ok(config) = sqlx::query_as!(
             LoggingConfig,
-            "SELECT log_actions, log_messages, log_voice, log_channel, log_action_channel, log_message_channel, log_voice_channel FROM logging_configuration WHERE guild_id = $1",
+            "SELECT log_actions, log_messages, log_voice, log_members, log_server, log_channel, log_action_channel, log_message_channel, log_voice_channel, log_member_channel, log_server_channel FROM logging_configuration WHERE guild_id = $1",
             guild_id
         )
         .fetch_one(&self.main_database)
         .await {
             if let Some(channel) = get_log_channel(self, &config, &LogType::Message).await {
-                if let Err(err) = ChannelId::new(channel as u64)
-                    .send_message(
-                        &ctx,
-                        CreateMessage::new()
-                            .embed(embed)
-                ).await {
-                    error!("Failed to send ban log message: {}", err);
-                }
+                self.log_dispatcher
+                    .dispatch(ChannelId::new(channel as u64), embed);
             }
         }
 
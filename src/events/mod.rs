@@ -1,16 +1,24 @@
+pub mod anti_raid;
+pub mod appeal;
 pub mod automod_trigger;
 pub mod command;
+pub mod component;
 pub mod expire;
+pub mod filter;
 pub mod guild_join;
 pub mod guild_leave;
 pub mod member_join;
+pub mod member_log;
 pub mod member_update;
 pub mod message;
 pub mod message_delete;
+pub mod message_delete_bulk;
 pub mod message_edit;
 pub mod reaction_add;
 pub mod ready;
 pub mod router;
+pub mod server_log;
+pub mod spam;
 pub mod voice_join;
 pub mod voice_leave;
 pub mod voice_move;
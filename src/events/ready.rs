@@ -13,7 +13,8 @@ use crate::{
         get_command_list, giveaway::interaction::new_giveaway_entry_handler,
         global::get_kill_commands,
     },
-    events::expire::{expire_actions, expire_giveaways},
+    common::log_dispatcher::run_log_dispatcher,
+    events::expire::{load_scheduler, maintain_actions, run_scheduler},
     models::{
         command::CommandContext,
         giveaway::{DatabaseGiveaway, Giveaway},
@@ -29,10 +30,18 @@ impl Handler {
             "with users' emotions (but faster)",
         )));
 
-        debug!("Starting action expiration loop");
-        tokio::spawn(expire_actions(self.clone(), ctx.clone()));
-        debug!("Starting giveaway expiration loop");
-        tokio::spawn(expire_giveaways(self.clone()));
+        debug!("Loading scheduled tasks");
+        load_scheduler(self).await;
+        if self.scheduler.start() {
+            debug!("Starting scheduler");
+            tokio::spawn(run_scheduler(self.clone(), ctx.clone()));
+            debug!("Starting action maintenance loop");
+            tokio::spawn(maintain_actions(self.clone(), ctx.clone()));
+        }
+        if self.log_dispatcher.start() {
+            debug!("Starting log dispatcher");
+            tokio::spawn(run_log_dispatcher(self.clone(), ctx.clone()));
+        }
 
         match sqlx::query_as!(DatabaseGiveaway, "SELECT * FROM giveaways")
             .fetch_all(&self.main_database)
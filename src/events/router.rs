@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use serenity::{
     all::{
-        ActionExecution, ChannelId, Guild, GuildId, GuildMemberUpdateEvent, Interaction,
-        InteractionType, Member, Message, MessageId, MessageUpdateEvent, Reaction,
-        UnavailableGuild, VoiceState,
+        ActionExecution, ChannelId, Emoji, EmojiId, Guild, GuildChannel, GuildId,
+        GuildMemberUpdateEvent, Interaction, InteractionType, InviteCreateEvent, Member, Message,
+        MessageId, MessageUpdateEvent, PartialGuild, Reaction, Role, RoleId, Sticker, StickerId,
+        UnavailableGuild, User, VoiceState,
     },
     model::prelude::Ready,
     prelude::{Context, EventHandler},
@@ -17,12 +20,24 @@ impl EventHandler for Handler {
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if interaction.kind() == InteractionType::Command {
-            self.on_command(ctx, interaction.command().unwrap()).await;
+        match interaction.kind() {
+            InteractionType::Command => {
+                self.on_command(ctx, interaction.command().unwrap()).await;
+            }
+            InteractionType::Component => {
+                self.on_component(ctx, interaction.message_component().unwrap())
+                    .await;
+            }
+            InteractionType::Modal => {
+                self.on_modal(ctx, interaction.modal_submit().unwrap())
+                    .await;
+            }
+            _ => {}
         }
     }
 
-    async fn guild_create(&self, _ctx: Context, guild: Guild, is_new: Option<bool>) {
+    async fn guild_create(&self, ctx: Context, guild: Guild, is_new: Option<bool>) {
+        self.seed_invite_cache(&ctx, guild.id).await;
         if is_new.is_none() || !is_new.unwrap() {
             return;
         }
@@ -31,21 +46,131 @@ impl EventHandler for Handler {
 
     async fn guild_member_update(
         &self,
-        _ctx: Context,
-        _old_if_available: Option<Member>,
+        ctx: Context,
+        old_if_available: Option<Member>,
         new: Option<Member>,
         event: GuildMemberUpdateEvent,
     ) {
         let Some(member) = new else {
             return;
         };
+        if let Some(old) = old_if_available {
+            self.on_member_update_log(&ctx, &old, &member).await;
+        }
         self.on_member_update(member, event).await;
     }
 
     async fn guild_member_addition(&self, ctx: Context, member: Member) {
+        self.on_anti_raid_join(&ctx, &member).await;
+        self.on_member_join_log(&ctx, &member).await;
         self.on_member_join(ctx, member).await;
     }
 
+    async fn guild_member_removal(
+        &self,
+        ctx: Context,
+        guild_id: GuildId,
+        user: User,
+        member_data_if_available: Option<Member>,
+    ) {
+        self.on_member_leave_log(&ctx, guild_id, &user, member_data_if_available.as_ref())
+            .await;
+    }
+
+    async fn guild_ban_addition(&self, ctx: Context, guild_id: GuildId, banned_user: User) {
+        self.on_ban_log(&ctx, guild_id, &banned_user, true).await;
+    }
+
+    async fn guild_ban_removal(&self, ctx: Context, guild_id: GuildId, unbanned_user: User) {
+        self.on_ban_log(&ctx, guild_id, &unbanned_user, false).await;
+    }
+
+    async fn invite_create(&self, ctx: Context, data: InviteCreateEvent) {
+        self.on_invite_create_log(&ctx, &data).await;
+        self.on_invite_create(data).await;
+    }
+
+    async fn channel_create(&self, ctx: Context, channel: GuildChannel) {
+        self.on_channel_create_log(&ctx, &channel).await;
+    }
+
+    async fn channel_delete(
+        &self,
+        ctx: Context,
+        channel: GuildChannel,
+        _messages: Option<Vec<Message>>,
+    ) {
+        self.on_channel_delete_log(&ctx, &channel).await;
+    }
+
+    async fn channel_update(&self, ctx: Context, old: Option<GuildChannel>, new: GuildChannel) {
+        if let Some(old) = old {
+            self.on_channel_update_log(&ctx, &old, &new).await;
+        }
+    }
+
+    async fn guild_role_create(&self, ctx: Context, new: Role) {
+        self.on_role_create_log(&ctx, &new).await;
+    }
+
+    async fn guild_role_delete(
+        &self,
+        ctx: Context,
+        guild_id: GuildId,
+        removed_role_id: RoleId,
+        removed_role_data_if_available: Option<Role>,
+    ) {
+        self.on_role_delete_log(
+            &ctx,
+            guild_id,
+            removed_role_id,
+            removed_role_data_if_available.as_ref(),
+        )
+        .await;
+    }
+
+    async fn guild_role_update(
+        &self,
+        ctx: Context,
+        old_data_if_available: Option<Role>,
+        new: Role,
+    ) {
+        if let Some(old) = old_data_if_available {
+            self.on_role_update_log(&ctx, &old, &new).await;
+        }
+    }
+
+    async fn guild_emojis_update(
+        &self,
+        ctx: Context,
+        guild_id: GuildId,
+        current_state: HashMap<EmojiId, Emoji>,
+    ) {
+        self.on_emojis_update_log(&ctx, guild_id, &current_state)
+            .await;
+    }
+
+    async fn guild_stickers_update(
+        &self,
+        ctx: Context,
+        guild_id: GuildId,
+        current_state: HashMap<StickerId, Sticker>,
+    ) {
+        self.on_stickers_update_log(&ctx, guild_id, &current_state)
+            .await;
+    }
+
+    async fn guild_update(
+        &self,
+        ctx: Context,
+        old_data_if_available: Option<Guild>,
+        new_data: PartialGuild,
+    ) {
+        if let Some(old) = old_data_if_available {
+            self.on_guild_update_log(&ctx, &old, &new_data).await;
+        }
+    }
+
     async fn guild_delete(
         &self,
         _ctx: Context,
@@ -66,7 +191,7 @@ impl EventHandler for Handler {
         Box::pin(self.on_automod_trigger(ctx, execution)).await;
     }
 
-    async fn message(&self, _ctx: Context, new_message: Message) {
+    async fn message(&self, ctx: Context, new_message: Message) {
         if new_message.author.bot {
             return;
         }
@@ -75,7 +200,7 @@ impl EventHandler for Handler {
             return;
         }
 
-        self.on_message(new_message).await;
+        self.on_message(ctx, new_message).await;
     }
 
     async fn message_update(
@@ -118,6 +243,21 @@ impl EventHandler for Handler {
         .await;
     }
 
+    async fn message_delete_bulk(
+        &self,
+        ctx: Context,
+        channel_id: ChannelId,
+        multiple_deleted_messages_ids: Vec<MessageId>,
+        guild_id: Option<GuildId>,
+    ) {
+        let Some(guild_id) = guild_id else {
+            return;
+        };
+
+        self.on_message_delete_bulk(ctx, guild_id, channel_id, multiple_deleted_messages_ids)
+            .await;
+    }
+
     async fn voice_state_update(&self, ctx: Context, old: Option<VoiceState>, new: VoiceState) {
         if new.guild_id.is_none() {
             return;
@@ -0,0 +1,789 @@
+use std::collections::HashMap;
+
+use serenity::{
+    all::{
+        audit_log::{Action, ChannelAction, ChannelOverwriteAction, RoleAction},
+        AuditLogEntry, ChannelId, Emoji, EmojiId, Guild, GuildChannel, GuildId, InviteCreateEvent,
+        PartialGuild, PermissionOverwriteType, Permissions, Role, RoleId, Sticker, StickerId,
+    },
+    builder::{CreateEmbed, CreateEmbedFooter},
+    model::guild::audit_log::Change,
+    prelude::Context,
+};
+use tracing::error;
+
+use crate::{
+    common::logging::{
+        get_audit_log_entry, get_guild_log_channel, is_log_ignored, is_recent_entry, LogType,
+    },
+    models::handler::Handler,
+};
+
+const CREATE_COLOR: u32 = 0x2dc770;
+const DELETE_COLOR: u32 = 0xee2e46;
+const UPDATE_COLOR: u32 = 0x0abfd6;
+const MAX_DIFF_FIELDS: usize = 20;
+const MAX_DIFF_LENGTH: usize = 450;
+
+type Changes = Vec<(&'static str, String, String)>;
+
+fn push_change<T: PartialEq>(
+    changes: &mut Changes,
+    name: &'static str,
+    old: &T,
+    new: &T,
+    display: impl Fn(&T) -> String,
+) {
+    if old != new {
+        changes.push((name, display(old), display(new)));
+    }
+}
+
+fn optional(value: Option<&impl ToString>) -> String {
+    value.map_or("None".to_string(), ToString::to_string)
+}
+
+fn channel_mention(channel: Option<ChannelId>) -> String {
+    channel.map_or("None".to_string(), |channel| format!("<#{channel}>"))
+}
+
+fn truncate(value: &str) -> String {
+    if value.chars().count() > MAX_DIFF_LENGTH {
+        format!(
+            "{}...",
+            value.chars().take(MAX_DIFF_LENGTH).collect::<String>()
+        )
+    } else {
+        value.to_string()
+    }
+}
+
+fn permission_names(permissions: Permissions) -> String {
+    permissions
+        .get_permission_names()
+        .into_iter()
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn diff_embed(title: &str, description: String, changes: &Changes) -> CreateEmbed {
+    CreateEmbed::new()
+        .title(title)
+        .description(description)
+        .fields(
+            changes
+                .iter()
+                .take(MAX_DIFF_FIELDS)
+                .map(|(name, old, new)| {
+                    (
+                        *name,
+                        format!(
+                            "**Before:** {}\n**After:** {}",
+                            truncate(old),
+                            truncate(new)
+                        ),
+                        false,
+                    )
+                }),
+        )
+        .color(UPDATE_COLOR)
+}
+
+fn with_executor(embed: CreateEmbed, entry: Option<AuditLogEntry>) -> CreateEmbed {
+    match entry {
+        Some(entry) => {
+            let embed = embed.field("Responsible", format!("<@{}>", entry.user_id), true);
+            match entry.reason {
+                Some(reason) => embed.field("Reason", truncate(&reason), true),
+                None => embed,
+            }
+        }
+        None => embed.field("Responsible", "Unknown", true),
+    }
+}
+
+fn send_server_log(handler: &Handler, channel: ChannelId, embed: CreateEmbed, footer: String) {
+    handler
+        .log_dispatcher
+        .dispatch(channel, embed.footer(CreateEmbedFooter::new(footer)));
+}
+
+/// Deleted channels are already gone from the cache, so the category is passed along explicitly
+fn channel_scope(channel: &GuildChannel) -> Vec<ChannelId> {
+    channel.parent_id.into_iter().chain([channel.id]).collect()
+}
+
+fn overwrite_target(kind: PermissionOverwriteType) -> String {
+    match kind {
+        PermissionOverwriteType::Member(user) => format!("<@{user}>"),
+        PermissionOverwriteType::Role(role) => format!("<@&{role}>"),
+        _ => "Unknown".to_string(),
+    }
+}
+
+fn channel_changes(old: &GuildChannel, new: &GuildChannel) -> Changes {
+    let mut changes = vec![];
+    push_change(&mut changes, "Name", &old.name, &new.name, |name| {
+        format!("`{name}`")
+    });
+    push_change(&mut changes, "Topic", &old.topic, &new.topic, |topic| {
+        optional(topic.as_ref())
+    });
+    push_change(
+        &mut changes,
+        "NSFW",
+        &old.nsfw,
+        &new.nsfw,
+        ToString::to_string,
+    );
+    push_change(
+        &mut changes,
+        "Slowmode",
+        &old.rate_limit_per_user,
+        &new.rate_limit_per_user,
+        |seconds| seconds.map_or("Off".to_string(), |seconds| format!("{seconds}s")),
+    );
+    push_change(
+        &mut changes,
+        "Category",
+        &old.parent_id,
+        &new.parent_id,
+        |channel| channel_mention(*channel),
+    );
+    push_change(
+        &mut changes,
+        "Bitrate",
+        &old.bitrate,
+        &new.bitrate,
+        |value| optional(value.as_ref()),
+    );
+    push_change(
+        &mut changes,
+        "User limit",
+        &old.user_limit,
+        &new.user_limit,
+        |value| optional(value.as_ref()),
+    );
+
+    let mut targets = vec![];
+    for overwrite in old
+        .permission_overwrites
+        .iter()
+        .chain(&new.permission_overwrites)
+    {
+        if !targets.contains(&overwrite.kind) {
+            targets.push(overwrite.kind);
+        }
+    }
+    for target in targets {
+        let find = |overwrites: &[serenity::all::PermissionOverwrite]| {
+            overwrites
+                .iter()
+                .find(|overwrite| overwrite.kind == target)
+                .map(|overwrite| (overwrite.allow, overwrite.deny))
+        };
+        let (before, after) = (
+            find(&old.permission_overwrites),
+            find(&new.permission_overwrites),
+        );
+        if before == after {
+            continue;
+        }
+        let describe = |overwrite: Option<(Permissions, Permissions)>| match overwrite {
+            Some((allow, deny)) => format!(
+                "{}\nAllow: {}\nDeny: {}",
+                overwrite_target(target),
+                if allow.is_empty() {
+                    "None".to_string()
+                } else {
+                    permission_names(allow)
+                },
+                if deny.is_empty() {
+                    "None".to_string()
+                } else {
+                    permission_names(deny)
+                }
+            ),
+            None => format!("{}\nNo overwrite", overwrite_target(target)),
+        };
+        changes.push(("Permissions", describe(before), describe(after)));
+    }
+    changes
+}
+
+fn role_changes(old: &Role, new: &Role) -> Changes {
+    let mut changes = vec![];
+    push_change(&mut changes, "Name", &old.name, &new.name, |name| {
+        format!("`{name}`")
+    });
+    push_change(&mut changes, "Color", &old.colour, &new.colour, |colour| {
+        format!("#{}", colour.hex())
+    });
+    push_change(
+        &mut changes,
+        "Hoisted",
+        &old.hoist,
+        &new.hoist,
+        ToString::to_string,
+    );
+    push_change(
+        &mut changes,
+        "Mentionable",
+        &old.mentionable,
+        &new.mentionable,
+        ToString::to_string,
+    );
+    let granted = new.permissions.difference(old.permissions);
+    let revoked = old.permissions.difference(new.permissions);
+    if !granted.is_empty() {
+        changes.push((
+            "Permissions granted",
+            "None".to_string(),
+            permission_names(granted),
+        ));
+    }
+    if !revoked.is_empty() {
+        changes.push((
+            "Permissions revoked",
+            permission_names(revoked),
+            "None".to_string(),
+        ));
+    }
+    changes
+}
+
+fn guild_changes(old: &Guild, new: &PartialGuild) -> Changes {
+    let mut changes = vec![];
+    push_change(&mut changes, "Name", &old.name, &new.name, |name| {
+        format!("`{name}`")
+    });
+    push_change(
+        &mut changes,
+        "Description",
+        &old.description,
+        &new.description,
+        |value| optional(value.as_ref()),
+    );
+    push_change(&mut changes, "Icon", &old.icon, &new.icon, |icon| {
+        icon.map_or("None".to_string(), |_| "Custom icon".to_string())
+    });
+    push_change(&mut changes, "Banner", &old.banner, &new.banner, |banner| {
+        banner
+            .as_ref()
+            .map_or("None".to_string(), |_| "Custom banner".to_string())
+    });
+    push_change(
+        &mut changes,
+        "Owner",
+        &old.owner_id,
+        &new.owner_id,
+        |owner| format!("<@{owner}>"),
+    );
+    push_change(
+        &mut changes,
+        "AFK channel",
+        &old.afk_metadata.as_ref().map(|afk| afk.afk_channel_id),
+        &new.afk_metadata.as_ref().map(|afk| afk.afk_channel_id),
+        |channel| channel_mention(*channel),
+    );
+    push_change(
+        &mut changes,
+        "AFK timeout",
+        &old.afk_metadata.as_ref().map(|afk| afk.afk_timeout),
+        &new.afk_metadata.as_ref().map(|afk| afk.afk_timeout),
+        |timeout| timeout.map_or("None".to_string(), |timeout| format!("{timeout:?}")),
+    );
+    push_change(
+        &mut changes,
+        "System channel",
+        &old.system_channel_id,
+        &new.system_channel_id,
+        |channel| channel_mention(*channel),
+    );
+    push_change(
+        &mut changes,
+        "Rules channel",
+        &old.rules_channel_id,
+        &new.rules_channel_id,
+        |channel| channel_mention(*channel),
+    );
+    push_change(
+        &mut changes,
+        "Verification level",
+        &old.verification_level,
+        &new.verification_level,
+        |level| format!("{level:?}"),
+    );
+    push_change(
+        &mut changes,
+        "Explicit content filter",
+        &old.explicit_content_filter,
+        &new.explicit_content_filter,
+        |filter| format!("{filter:?}"),
+    );
+    push_change(
+        &mut changes,
+        "Default notifications",
+        &old.default_message_notifications,
+        &new.default_message_notifications,
+        |level| format!("{level:?}"),
+    );
+    push_change(
+        &mut changes,
+        "2FA requirement",
+        &old.mfa_level,
+        &new.mfa_level,
+        |level| format!("{level:?}"),
+    );
+    push_change(
+        &mut changes,
+        "Vanity URL",
+        &old.vanity_url_code,
+        &new.vanity_url_code,
+        |value| optional(value.as_ref()),
+    );
+    changes
+}
+
+async fn latest_entry(
+    ctx: &Context,
+    guild_id: GuildId,
+    matches: impl Fn(&Action) -> bool,
+) -> Option<AuditLogEntry> {
+    match ctx
+        .http
+        .get_audit_logs(guild_id, None, None, None, Some(5))
+        .await
+    {
+        Ok(audit_log) => audit_log
+            .entries
+            .into_iter()
+            .find(|entry| is_recent_entry(entry) && matches(&entry.action)),
+        Err(err) => {
+            error!("Failed to get audit log: {:?}", err);
+            None
+        }
+    }
+}
+
+fn changed_name(entry: &AuditLogEntry) -> (Option<String>, Option<String>) {
+    entry
+        .changes
+        .iter()
+        .flatten()
+        .find_map(|change| match change {
+            Change::Name { old, new } => Some((old.clone(), new.clone())),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+impl Handler {
+    pub async fn on_channel_create_log(&self, ctx: &Context, channel: &GuildChannel) {
+        let Some(log_channel) =
+            get_guild_log_channel(self, channel.guild_id, &LogType::Server).await
+        else {
+            return;
+        };
+        if is_log_ignored(
+            self,
+            ctx,
+            channel.guild_id,
+            &LogType::Server,
+            &channel_scope(channel),
+            None,
+            &[],
+        )
+        .await
+        {
+            return;
+        }
+        let entry = get_audit_log_entry(
+            ctx,
+            channel.guild_id,
+            Action::Channel(ChannelAction::Create),
+            Some(channel.id.get()),
+        )
+        .await;
+        let embed = CreateEmbed::new()
+            .title("Channel created")
+            .description(format!("<#{}> (`{}`)", channel.id, channel.name))
+            .field("Type", format!("{:?}", channel.kind), true)
+            .field("Category", channel_mention(channel.parent_id), true)
+            .color(CREATE_COLOR);
+        send_server_log(
+            self,
+            log_channel,
+            with_executor(embed, entry),
+            format!("Channel {} created", channel.id),
+        );
+    }
+
+    pub async fn on_channel_delete_log(&self, ctx: &Context, channel: &GuildChannel) {
+        let Some(log_channel) =
+            get_guild_log_channel(self, channel.guild_id, &LogType::Server).await
+        else {
+            return;
+        };
+        if is_log_ignored(
+            self,
+            ctx,
+            channel.guild_id,
+            &LogType::Server,
+            &channel_scope(channel),
+            None,
+            &[],
+        )
+        .await
+        {
+            return;
+        }
+        let entry = get_audit_log_entry(
+            ctx,
+            channel.guild_id,
+            Action::Channel(ChannelAction::Delete),
+            Some(channel.id.get()),
+        )
+        .await;
+        let embed = CreateEmbed::new()
+            .title("Channel deleted")
+            .description(format!("`{}`", channel.name))
+            .field("Type", format!("{:?}", channel.kind), true)
+            .field("Category", channel_mention(channel.parent_id), true)
+            .color(DELETE_COLOR);
+        send_server_log(
+            self,
+            log_channel,
+            with_executor(embed, entry),
+            format!("Channel {} deleted", channel.id),
+        );
+    }
+
+    pub async fn on_channel_update_log(
+        &self,
+        ctx: &Context,
+        old: &GuildChannel,
+        new: &GuildChannel,
+    ) {
+        let changes = channel_changes(old, new);
+        if changes.is_empty() {
+            return;
+        }
+        let Some(log_channel) = get_guild_log_channel(self, new.guild_id, &LogType::Server).await
+        else {
+            return;
+        };
+        if is_log_ignored(
+            self,
+            ctx,
+            new.guild_id,
+            &LogType::Server,
+            &channel_scope(new),
+            None,
+            &[],
+        )
+        .await
+        {
+            return;
+        }
+
+        let overwrites_only = changes.iter().all(|(name, _, _)| *name == "Permissions");
+        let action = if overwrites_only {
+            Action::ChannelOverwrite(ChannelOverwriteAction::Update)
+        } else {
+            Action::Channel(ChannelAction::Update)
+        };
+        let entry = get_audit_log_entry(ctx, new.guild_id, action, Some(new.id.get())).await;
+        let embed = diff_embed("Channel updated", format!("<#{}>", new.id), &changes);
+        send_server_log(
+            self,
+            log_channel,
+            with_executor(embed, entry),
+            format!("Channel {} updated", new.id),
+        );
+    }
+
+    pub async fn on_role_create_log(&self, ctx: &Context, role: &Role) {
+        let Some(log_channel) = get_guild_log_channel(self, role.guild_id, &LogType::Server).await
+        else {
+            return;
+        };
+        if is_log_ignored(
+            self,
+            ctx,
+            role.guild_id,
+            &LogType::Server,
+            &[],
+            None,
+            &[role.id],
+        )
+        .await
+        {
+            return;
+        }
+        let entry = get_audit_log_entry(
+            ctx,
+            role.guild_id,
+            Action::Role(RoleAction::Create),
+            Some(role.id.get()),
+        )
+        .await;
+        let embed = CreateEmbed::new()
+            .title("Role created")
+            .description(format!("<@&{}> (`{}`)", role.id, role.name))
+            .color(CREATE_COLOR);
+        send_server_log(
+            self,
+            log_channel,
+            with_executor(embed, entry),
+            format!("Role {} created", role.id),
+        );
+    }
+
+    pub async fn on_role_delete_log(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+        role_id: RoleId,
+        role: Option<&Role>,
+    ) {
+        let Some(log_channel) = get_guild_log_channel(self, guild_id, &LogType::Server).await
+        else {
+            return;
+        };
+        if is_log_ignored(self, ctx, guild_id, &LogType::Server, &[], None, &[role_id]).await {
+            return;
+        }
+        let entry = get_audit_log_entry(
+            ctx,
+            guild_id,
+            Action::Role(RoleAction::Delete),
+            Some(role_id.get()),
+        )
+        .await;
+        let embed = CreateEmbed::new()
+            .title("Role deleted")
+            .description(role.map_or(format!("`{role_id}`"), |role| format!("`{}`", role.name)))
+            .color(DELETE_COLOR);
+        send_server_log(
+            self,
+            log_channel,
+            with_executor(embed, entry),
+            format!("Role {role_id} deleted"),
+        );
+    }
+
+    pub async fn on_role_update_log(&self, ctx: &Context, old: &Role, new: &Role) {
+        let changes = role_changes(old, new);
+        if changes.is_empty() {
+            return;
+        }
+        let Some(log_channel) = get_guild_log_channel(self, new.guild_id, &LogType::Server).await
+        else {
+            return;
+        };
+        if is_log_ignored(
+            self,
+            ctx,
+            new.guild_id,
+            &LogType::Server,
+            &[],
+            None,
+            &[new.id],
+        )
+        .await
+        {
+            return;
+        }
+        let entry = get_audit_log_entry(
+            ctx,
+            new.guild_id,
+            Action::Role(RoleAction::Update),
+            Some(new.id.get()),
+        )
+        .await;
+        let embed = diff_embed("Role updated", format!("<@&{}>", new.id), &changes);
+        send_server_log(
+            self,
+            log_channel,
+            with_executor(embed, entry),
+            format!("Role {} updated", new.id),
+        );
+    }
+
+    pub async fn on_emojis_update_log(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+        emojis: &HashMap<EmojiId, Emoji>,
+    ) {
+        let Some(log_channel) = get_guild_log_channel(self, guild_id, &LogType::Server).await
+        else {
+            return;
+        };
+        let Some(entry) =
+            latest_entry(ctx, guild_id, |action| matches!(action, Action::Emoji(_))).await
+        else {
+            return;
+        };
+
+        let (old_name, new_name) = changed_name(&entry);
+        let emoji = entry
+            .target_id
+            .and_then(|id| emojis.get(&EmojiId::new(id.get())));
+        let (title, description, color) = match (emoji, old_name.is_some(), new_name.is_some()) {
+            (Some(emoji), true, true) => (
+                "Emoji renamed",
+                format!(
+                    "{emoji} `{}` → `{}`",
+                    optional(old_name.as_ref()),
+                    optional(new_name.as_ref())
+                ),
+                UPDATE_COLOR,
+            ),
+            (Some(emoji), _, _) => (
+                "Emoji created",
+                format!("{emoji} `{}`", emoji.name),
+                CREATE_COLOR,
+            ),
+            (None, _, _) => (
+                "Emoji deleted",
+                format!("`{}`", optional(old_name.as_ref())),
+                DELETE_COLOR,
+            ),
+        };
+        let footer = format!("Emoji {} updated", optional(entry.target_id.as_ref()));
+        let embed = CreateEmbed::new()
+            .title(title)
+            .description(description)
+            .color(color);
+        send_server_log(self, log_channel, with_executor(embed, Some(entry)), footer);
+    }
+
+    pub async fn on_stickers_update_log(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+        stickers: &HashMap<StickerId, Sticker>,
+    ) {
+        let Some(log_channel) = get_guild_log_channel(self, guild_id, &LogType::Server).await
+        else {
+            return;
+        };
+        let Some(entry) =
+            latest_entry(ctx, guild_id, |action| matches!(action, Action::Sticker(_))).await
+        else {
+            return;
+        };
+
+        let (old_name, new_name) = changed_name(&entry);
+        let sticker = entry
+            .target_id
+            .and_then(|id| stickers.get(&StickerId::new(id.get())));
+        let (title, description, color) = match (sticker, old_name.is_some(), new_name.is_some()) {
+            (Some(_), true, true) => (
+                "Sticker renamed",
+                format!(
+                    "`{}` → `{}`",
+                    optional(old_name.as_ref()),
+                    optional(new_name.as_ref())
+                ),
+                UPDATE_COLOR,
+            ),
+            (Some(sticker), _, _) => (
+                "Sticker created",
+                format!("`{}`", sticker.name),
+                CREATE_COLOR,
+            ),
+            (None, _, _) => (
+                "Sticker deleted",
+                format!("`{}`", optional(old_name.as_ref())),
+                DELETE_COLOR,
+            ),
+        };
+        let footer = format!("Sticker {} updated", optional(entry.target_id.as_ref()));
+        let embed = CreateEmbed::new()
+            .title(title)
+            .description(description)
+            .color(color);
+        send_server_log(self, log_channel, with_executor(embed, Some(entry)), footer);
+    }
+
+    pub async fn on_invite_create_log(&self, ctx: &Context, invite: &InviteCreateEvent) {
+        let Some(guild_id) = invite.guild_id else {
+            return;
+        };
+        let Some(log_channel) = get_guild_log_channel(self, guild_id, &LogType::Server).await
+        else {
+            return;
+        };
+        if is_log_ignored(
+            self,
+            ctx,
+            guild_id,
+            &LogType::Server,
+            &[invite.channel_id],
+            invite.inviter.as_ref().map(|inviter| inviter.id),
+            &[],
+        )
+        .await
+        {
+            return;
+        }
+        let mut embed = CreateEmbed::new()
+            .title("Invite created")
+            .description(format!("`{}` for <#{}>", invite.code, invite.channel_id))
+            .field(
+                "Max uses",
+                match invite.max_uses {
+                    0 => "Unlimited".to_string(),
+                    uses => uses.to_string(),
+                },
+                true,
+            )
+            .field(
+                "Expires",
+                match invite.max_age {
+                    0 => "Never".to_string(),
+                    age => format!(
+                        "<t:{}:R>",
+                        invite.created_at.unix_timestamp() + i64::from(age)
+                    ),
+                },
+                true,
+            )
+            .color(CREATE_COLOR);
+        if let Some(inviter) = &invite.inviter {
+            embed = embed.field("Responsible", format!("<@{}>", inviter.id), true);
+        }
+        send_server_log(
+            self,
+            log_channel,
+            embed,
+            format!("Invite {} created", invite.code),
+        );
+    }
+
+    pub async fn on_guild_update_log(&self, ctx: &Context, old: &Guild, new: &PartialGuild) {
+        let changes = guild_changes(old, new);
+        if changes.is_empty() {
+            return;
+        }
+        let Some(log_channel) = get_guild_log_channel(self, new.id, &LogType::Server).await else {
+            return;
+        };
+        let entry = get_audit_log_entry(ctx, new.id, Action::GuildUpdate, None).await;
+        let embed = diff_embed(
+            "Server updated",
+            "The server settings were changed".to_string(),
+            &changes,
+        );
+        send_server_log(
+            self,
+            log_channel,
+            with_executor(embed, entry),
+            format!("Guild {} updated", new.id),
+        );
+    }
+}
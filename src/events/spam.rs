@@ -0,0 +1,275 @@
+use std::{
+    collections::HashMap,
+    sync::{atomic::AtomicBool, Arc, LazyLock},
+};
+
+use regex::Regex;
+use serenity::{
+    all::{ChannelId, Message as DiscordMessage, MessageId},
+    prelude::Context,
+};
+use tracing::{debug, error};
+
+use crate::{
+    common::duration::Duration,
+    database::postgres::guild::{get_moderation_config, get_spam_config},
+    models::{
+        command::{get_member_permissions, CommandContext},
+        config::SpamConfig,
+        handler::Handler,
+        message::MessageQuery,
+        permissions::Permission,
+    },
+};
+
+pub const FALLBACK_DURATION: &str = "10m";
+
+static CUSTOM_EMOJI_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<a?:\w+:\d+>").unwrap());
+
+fn count_emojis(content: &str) -> usize {
+    let custom = CUSTOM_EMOJI_REGEX.find_iter(content).count();
+    let unicode = content
+        .chars()
+        .filter(|c| matches!(u32::from(*c), 0x1F000..=0x1FAFF | 0x2600..=0x27BF))
+        .count();
+    custom + unicode
+}
+
+fn exceeds(value: usize, limit: Option<i32>) -> bool {
+    limit.is_some_and(|limit| value > usize::try_from(limit).unwrap_or(usize::MAX))
+}
+
+fn check_content(config: &SpamConfig, message: &DiscordMessage) -> Option<&'static str> {
+    let mentions = message.mentions.len()
+        + message.mention_roles.len()
+        + usize::from(message.mention_everyone);
+    if exceeds(mentions, config.mention_limit) {
+        return Some("mass mentions");
+    }
+    if exceeds(message.content.matches('\n').count(), config.newline_limit) {
+        return Some("excessive newlines");
+    }
+    if exceeds(count_emojis(&message.content), config.emoji_limit) {
+        return Some("excessive emojis");
+    }
+    None
+}
+
+impl Handler {
+    /// Without a default strike duration automatic strikes would be rejected and go unpunished,
+    /// so they fall back to a short one
+    pub async fn automatic_strike_duration(&self, guild_id: i64) -> Duration {
+        get_moderation_config(self, guild_id)
+            .await
+            .and_then(|config| config.default_strike_duration)
+            .map_or_else(
+                || Duration::new(FALLBACK_DURATION),
+                |duration| Duration::new(&duration),
+            )
+    }
+
+    /// Returns true when the message was removed as spam, so it isn't also run through the filter
+    pub async fn on_spam_check(&self, ctx: &Context, message: &DiscordMessage) -> bool {
+        let Some(guild_id) = message.guild_id else {
+            return false;
+        };
+
+        if !sqlx::query!("SELECT active FROM global_kills WHERE feature = 'event.spam'")
+            .fetch_one(&self.main_database)
+            .await
+            .unwrap()
+            .active
+        {
+            return false;
+        }
+
+        let Some(config) = get_spam_config(self, guild_id.get() as i64).await else {
+            return false;
+        };
+        if !config.enabled {
+            return false;
+        }
+
+        let mut connection = match self.redis_database.get_multiplexed_async_connection().await {
+            Ok(connection) => connection,
+            Err(err) => {
+                error!("Failed to get Redis connection: {:?}", err);
+                return false;
+            }
+        };
+
+        let key = format!("spam:{}:{}", guild_id, message.author.id);
+        let now = i64::try_from(time::OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000)
+            .unwrap_or_default();
+        let recent: Vec<String> = match redis::pipe()
+            .cmd("ZADD")
+            .arg(&key)
+            .arg(now)
+            .arg(format!("{}:{}", message.channel_id, message.id))
+            .ignore()
+            .cmd("ZREMRANGEBYSCORE")
+            .arg(&key)
+            .arg("-inf")
+            .arg(now - i64::from(config.message_window) * 1000)
+            .ignore()
+            .cmd("EXPIRE")
+            .arg(&key)
+            .arg(config.message_window)
+            .ignore()
+            .cmd("ZRANGE")
+            .arg(&key)
+            .arg(0)
+            .arg(-1)
+            .query_async::<_, (Vec<String>,)>(&mut connection)
+            .await
+        {
+            Ok((recent,)) => recent,
+            Err(err) => {
+                error!("Failed to track message rate in Redis: {:?}", err);
+                return false;
+            }
+        };
+        let recent = recent
+            .iter()
+            .filter_map(|entry| entry.split_once(':'))
+            .filter_map(|(channel, message)| {
+                Some((
+                    ChannelId::new(channel.parse().ok()?),
+                    MessageId::new(message.parse().ok()?),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let mut offending = vec![(message.channel_id, message.id)];
+        let violation = if let Some(violation) = check_content(&config, message) {
+            violation
+        } else if exceeds(recent.len(), config.message_limit) {
+            offending.clone_from(&recent);
+            "sending messages too quickly"
+        } else if config.duplicate_limit.is_some() && !message.content.trim().is_empty() {
+            let mut duplicates = vec![(message.channel_id, message.id)];
+            for (channel_id, message_id) in &recent {
+                if *message_id == message.id {
+                    continue;
+                }
+                let query = MessageQuery {
+                    guild: guild_id.get() as i64,
+                    channel: channel_id.get() as i64,
+                    message: message_id.get() as i64,
+                };
+                if let Ok(cached) = query.get_message(&self.redis_database).await {
+                    if cached.content == message.content {
+                        duplicates.push((*channel_id, *message_id));
+                    }
+                }
+            }
+            if !exceeds(duplicates.len(), config.duplicate_limit) {
+                return false;
+            }
+            offending = duplicates;
+            "repeated messages"
+        } else {
+            return false;
+        };
+
+        if get_member_permissions(self, ctx, guild_id, message.author.id)
+            .await
+            .contains(&Permission::ModerationStrike)
+        {
+            debug!(
+                "Ignoring spam by {} in {} as they are a moderator",
+                message.author.id, guild_id
+            );
+            return false;
+        }
+
+        // Only punish once per burst, the remaining messages of the burst are still deleted
+        let first: Option<String> = match redis::cmd("SET")
+            .arg(format!("{key}:punished"))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(config.message_window.max(10))
+            .query_async(&mut connection)
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => {
+                error!("Failed to set spam cooldown in Redis: {:?}", err);
+                return false;
+            }
+        };
+        if let Err(err) = redis::cmd("DEL")
+            .arg(&key)
+            .query_async::<_, ()>(&mut connection)
+            .await
+        {
+            error!("Failed to clear message rate in Redis: {:?}", err);
+        }
+
+        let mut by_channel: HashMap<ChannelId, Vec<MessageId>> = HashMap::new();
+        for (channel_id, message_id) in offending {
+            by_channel.entry(channel_id).or_default().push(message_id);
+        }
+        for (channel_id, message_ids) in by_channel {
+            let result = if message_ids.len() == 1 {
+                channel_id.delete_message(&ctx.http, message_ids[0]).await
+            } else {
+                channel_id.delete_messages(&ctx.http, message_ids).await
+            };
+            if let Err(err) = result {
+                error!("Failed to delete spam messages in {channel_id}: {:?}", err);
+            }
+        }
+
+        if first.is_none() || config.punishment == "none" {
+            return true;
+        }
+
+        let Ok(guild) = guild_id.to_partial_guild(&ctx.http).await else {
+            error!("Could not get guild {} from cache", guild_id);
+            return true;
+        };
+        let context = CommandContext {
+            ctx: ctx.clone(),
+            has_responsed: Arc::new(AtomicBool::new(false)),
+            user_permissions: vec![],
+            highest_role: u16::MAX,
+            guild,
+        };
+        let reason = format!("{} ({violation})", config.reason);
+
+        let result = if config.punishment == "mute" {
+            Box::pin(self.mute_user(
+                &context,
+                guild_id.get() as i64,
+                message.author.id.get() as i64,
+                reason,
+                None,
+                Duration::new(config.mute_duration.as_deref().unwrap_or(FALLBACK_DURATION)),
+            ))
+            .await
+            .map(|_| ())
+        } else {
+            let duration = self.automatic_strike_duration(guild_id.get() as i64).await;
+            Box::pin(self.strike_user(
+                &context,
+                guild_id.get() as i64,
+                message.author.id.get() as i64,
+                reason,
+                None,
+                Some(duration),
+                1,
+            ))
+            .await
+            .map(|_| ())
+        };
+        if let Err(err) = result {
+            error!(
+                "Could not punish user for spam. Failed with error: {:?}",
+                err
+            );
+        }
+        true
+    }
+}
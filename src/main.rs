@@ -6,7 +6,7 @@
 
 use serenity::{prelude::GatewayIntents, Client};
 use sqlx::postgres::PgPoolOptions;
-use std::{env, time::Instant};
+use std::{env, sync::Arc, time::Instant};
 use tracing::{error, info};
 
 mod commands;
@@ -81,6 +81,9 @@ async fn main() {
         start_time: Instant::now(),
         global_kill_guild: global_kill_guild.parse().unwrap(),
         global_kill_role: global_kill_role.parse().unwrap(),
+        scheduler: Arc::new(common::scheduler::Scheduler::new()),
+        log_dispatcher: Arc::new(common::log_dispatcher::LogDispatcher::new()),
+        filter_cache: Arc::new(common::filter_cache::FilterCache::new()),
     };
     let intents = GatewayIntents::non_privileged()
         | GatewayIntents::GUILD_MEMBERS
@@ -0,0 +1,82 @@
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum RevisionField {
+    Reason,
+    Expiry,
+    Active,
+    Removed,
+    Restored,
+}
+
+impl Display for RevisionField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RevisionField::Reason => write!(f, "reason"),
+            RevisionField::Expiry => write!(f, "expiry"),
+            RevisionField::Active => write!(f, "active"),
+            RevisionField::Removed => write!(f, "removed"),
+            RevisionField::Restored => write!(f, "restored"),
+        }
+    }
+}
+
+impl From<&str> for RevisionField {
+    fn from(value: &str) -> Self {
+        match value {
+            "reason" => RevisionField::Reason,
+            "expiry" => RevisionField::Expiry,
+            "active" => RevisionField::Active,
+            "removed" => RevisionField::Removed,
+            "restored" => RevisionField::Restored,
+            _ => panic!("Invalid revision field"),
+        }
+    }
+}
+
+impl From<String> for RevisionField {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+fn format_expiry(value: Option<&String>) -> String {
+    match value {
+        Some(timestamp) => format!("<t:{timestamp}:F>"),
+        None => "Never".to_string(),
+    }
+}
+
+pub struct ActionRevision {
+    pub action_id: String,
+    pub field: RevisionField,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub changed_by: i64,
+    pub changed_at: time::PrimitiveDateTime,
+}
+
+impl ActionRevision {
+    pub fn describe(&self) -> String {
+        let change = match self.field {
+            RevisionField::Reason => format!(
+                "changed the reason from \"{}\" to \"{}\"",
+                self.old_value.as_deref().unwrap_or_default(),
+                self.new_value.as_deref().unwrap_or_default()
+            ),
+            RevisionField::Expiry => format!(
+                "changed the expiry from {} to {}",
+                format_expiry(self.old_value.as_ref()),
+                format_expiry(self.new_value.as_ref())
+            ),
+            RevisionField::Active => "expired the action".to_string(),
+            RevisionField::Removed => "removed the action".to_string(),
+            RevisionField::Restored => "restored the action".to_string(),
+        };
+        format!(
+            "<t:{}:R> <@{}> {change}",
+            self.changed_at.assume_utc().unix_timestamp(),
+            self.changed_by
+        )
+    }
+}
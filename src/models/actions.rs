@@ -5,7 +5,10 @@ use std::{
 
 use tracing::error;
 
-use crate::{common::duration::Duration, models::response::ResponseError};
+use crate::{
+    common::{duration::Duration, scheduler::ScheduledTask},
+    models::response::ResponseError,
+};
 
 use super::{handler::Handler, response::ResponseResult};
 
@@ -15,6 +18,7 @@ pub enum ActionType {
     Mute,
     Kick,
     Ban,
+    Custom,
 }
 
 impl Display for ActionType {
@@ -24,6 +28,7 @@ impl Display for ActionType {
             ActionType::Mute => write!(f, "mute"),
             ActionType::Kick => write!(f, "kick"),
             ActionType::Ban => write!(f, "ban"),
+            ActionType::Custom => write!(f, "custom"),
         }
     }
 }
@@ -35,6 +40,7 @@ impl From<&str> for ActionType {
             "mute" => ActionType::Mute,
             "kick" => ActionType::Kick,
             "ban" => ActionType::Ban,
+            "custom" => ActionType::Custom,
             _ => panic!("Invalid action type"),
         }
     }
@@ -46,6 +52,49 @@ impl From<String> for ActionType {
     }
 }
 
+#[derive(Copy, Clone, PartialEq)]
+pub enum EscalationType {
+    Mute,
+    Kick,
+    Ban,
+    Custom,
+    Warn,
+    Alert,
+}
+
+impl Display for EscalationType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EscalationType::Mute => write!(f, "mute"),
+            EscalationType::Kick => write!(f, "kick"),
+            EscalationType::Ban => write!(f, "ban"),
+            EscalationType::Custom => write!(f, "custom"),
+            EscalationType::Warn => write!(f, "warn"),
+            EscalationType::Alert => write!(f, "alert"),
+        }
+    }
+}
+
+impl From<&str> for EscalationType {
+    fn from(value: &str) -> Self {
+        match value {
+            "mute" => EscalationType::Mute,
+            "kick" => EscalationType::Kick,
+            "ban" => EscalationType::Ban,
+            "custom" => EscalationType::Custom,
+            "warn" => EscalationType::Warn,
+            "alert" => EscalationType::Alert,
+            _ => panic!("Invalid escalation type"),
+        }
+    }
+}
+
+impl From<String> for EscalationType {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
 #[derive(Clone)]
 pub struct Action {
     pub id: Result<objectid::ObjectId, String>,
@@ -57,6 +106,10 @@ pub struct Action {
     pub active: bool,
     pub expiry: Option<time::OffsetDateTime>,
     pub created_at: time::OffsetDateTime,
+    pub custom_type: Option<String>,
+    pub points: Option<i32>,
+    pub case_number: i32,
+    pub import_source: Option<String>,
 }
 
 impl Action {
@@ -82,6 +135,17 @@ impl Action {
             },
             active,
             created_at: time::OffsetDateTime::now_utc(),
+            custom_type: None,
+            points: None,
+            case_number: 0,
+            import_source: None,
+        }
+    }
+
+    pub fn type_name(&self) -> String {
+        match &self.custom_type {
+            Some(name) if self.typ == ActionType::Custom => name.clone(),
+            _ => self.typ.to_string(),
         }
     }
 
@@ -92,8 +156,13 @@ impl Action {
         }
     }
 
-    pub async fn insert(&self, handler: &Handler) -> ResponseResult {
-        DatabaseAction::from(self.clone()).insert(handler).await
+    pub fn case(&self) -> String {
+        format!("Case #{}", self.case_number)
+    }
+
+    pub async fn insert(&mut self, handler: &Handler) -> ResponseResult {
+        self.case_number = DatabaseAction::from(self.clone()).insert(handler).await?;
+        Ok(())
     }
 }
 
@@ -109,6 +178,10 @@ impl From<DatabaseAction> for Action {
             active: value.active,
             expiry: value.expiry.map(time::PrimitiveDateTime::assume_utc),
             created_at: value.created_at.assume_utc(),
+            custom_type: value.custom_action,
+            points: value.points,
+            case_number: value.case_number,
+            import_source: value.import_source,
         }
     }
 }
@@ -124,6 +197,10 @@ pub struct DatabaseAction {
     pub active: bool,
     pub expiry: Option<time::PrimitiveDateTime>,
     pub created_at: time::PrimitiveDateTime,
+    pub custom_action: Option<String>,
+    pub points: Option<i32>,
+    pub case_number: i32,
+    pub import_source: Option<String>,
 }
 
 impl From<Action> for DatabaseAction {
@@ -146,14 +223,18 @@ impl From<Action> for DatabaseAction {
                 value.created_at.date(),
                 value.created_at.time(),
             ),
+            custom_action: value.custom_type,
+            points: value.points,
+            case_number: value.case_number,
+            import_source: value.import_source,
         }
     }
 }
 
 impl DatabaseAction {
-    pub async fn insert(&self, handler: &Handler) -> ResponseResult {
+    pub async fn insert(&self, handler: &Handler) -> Result<i32, ResponseError> {
         match sqlx::query!(
-            "INSERT INTO actions (id, action_type, user_id, moderator_id, guild_id, reason, active, expiry, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            "WITH next_case AS (INSERT INTO case_counters (guild_id, last_case) VALUES ($5, 1) ON CONFLICT (guild_id) DO UPDATE SET last_case = case_counters.last_case + 1 RETURNING last_case) INSERT INTO actions (id, action_type, user_id, moderator_id, guild_id, reason, active, expiry, created_at, custom_action, points, case_number, import_source) SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, last_case, $12 FROM next_case RETURNING case_number",
             self.id,
             self.action_type,
             self.user_id,
@@ -162,9 +243,19 @@ impl DatabaseAction {
             self.reason,
             self.active,
             self.expiry,
-            self.created_at
-        ).execute(&handler.main_database).await {
-            Ok(_) => Ok(()),
+            self.created_at,
+            self.custom_action,
+            self.points,
+            self.import_source
+        ).fetch_one(&handler.main_database).await {
+            Ok(record) => {
+                if let (true, Some(expiry)) = (self.active, self.expiry) {
+                    handler
+                        .scheduler
+                        .schedule(expiry.assume_utc(), ScheduledTask::ActionExpiry(self.id.clone()));
+                }
+                Ok(record.case_number)
+            }
             Err(err) => {
                 error!("Failed to insert action into database: {}", err);
                 Err(ResponseError::Execution(
@@ -184,25 +275,37 @@ pub struct ActionDatabaseInsert {
 #[derive(Clone)]
 pub struct ActionEscalation {
     pub guild_id: i64,
-    pub strike_count: i64,
-    pub action_type: ActionType,
+    pub points: i64,
+    pub action_type: EscalationType,
     pub action_duration: Option<String>,
+    pub custom_action: Option<String>,
+    pub message: Option<String>,
 }
 
 impl From<DatabaseActionEscalation> for ActionEscalation {
     fn from(value: DatabaseActionEscalation) -> Self {
         ActionEscalation {
             guild_id: value.guild_id,
-            strike_count: value.strike_count,
-            action_type: ActionType::from(value.action_type.as_str()),
+            points: value.points,
+            action_type: EscalationType::from(value.action_type.as_str()),
             action_duration: value.action_duration,
+            custom_action: value.custom_action,
+            message: value.message,
         }
     }
 }
 
 pub struct DatabaseActionEscalation {
     pub guild_id: i64,
-    pub strike_count: i64,
+    pub points: i64,
     pub action_type: String,
     pub action_duration: Option<String>,
+    pub custom_action: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct CustomAction {
+    pub name: String,
+    pub role_id: i64,
 }
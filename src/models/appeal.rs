@@ -0,0 +1,46 @@
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum AppealStatus {
+    Pending,
+    Accepted,
+    Denied,
+}
+
+impl Display for AppealStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AppealStatus::Pending => write!(f, "pending"),
+            AppealStatus::Accepted => write!(f, "accepted"),
+            AppealStatus::Denied => write!(f, "denied"),
+        }
+    }
+}
+
+impl From<&str> for AppealStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "pending" => AppealStatus::Pending,
+            "accepted" => AppealStatus::Accepted,
+            "denied" => AppealStatus::Denied,
+            _ => panic!("Invalid appeal status"),
+        }
+    }
+}
+
+impl From<String> for AppealStatus {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+pub struct Appeal {
+    pub id: i32,
+    pub action_id: String,
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub reason: String,
+    pub status: AppealStatus,
+    pub reviewer_id: Option<i64>,
+    pub response: Option<String>,
+}
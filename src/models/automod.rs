@@ -0,0 +1,14 @@
+pub struct AutoModMapping {
+    pub rule_id: i64,
+    pub action: String,
+    pub duration: Option<String>,
+    pub reason: String,
+}
+
+impl AutoModMapping {
+    pub fn format_reason(&self, rule: &str, keyword: Option<&str>) -> String {
+        self.reason
+            .replace("{rule}", rule)
+            .replace("{keyword}", keyword.unwrap_or("unknown"))
+    }
+}
@@ -1,5 +1,8 @@
 use serenity::{
-    all::{CommandInteraction, ComponentInteraction, Message, PartialGuild, Permissions},
+    all::{
+        CommandInteraction, ComponentInteraction, GuildId, Message, PartialGuild, Permissions,
+        UserId,
+    },
     builder::{CreateCommand, CreateEmbed},
     prelude::Context as IncomingContext,
 };
@@ -52,71 +55,18 @@ impl InteractionContext {
         ctx: IncomingContext,
         interaction: &ComponentInteraction,
     ) -> Self {
-        let Some(guild_id) = interaction.guild_id else {
-            return Self {
-                ctx,
-                interaction: interaction.clone(),
-                has_responsed: Arc::new(AtomicBool::new(false)),
-                user_permissions: vec![],
-            };
-        };
-
-        let mut temp_guild = guild_id
-            .to_guild_cached(&ctx.cache)
-            .map(|guild| PartialGuild::from(guild.clone()));
-        if temp_guild.is_none() {
-            temp_guild = if let Ok(guild) = guild_id.to_partial_guild(&ctx.http).await {
-                Some(guild)
-            } else {
-                None
-            }
-        }
-
-        let guild = temp_guild.unwrap();
-
-        if guild.owner_id == interaction.user.id {
-            return Self {
-                ctx,
-                interaction: interaction.clone(),
-                has_responsed: Arc::new(AtomicBool::new(false)),
-                user_permissions: Permission::iter().collect::<Vec<_>>(),
-            };
-        }
-
-        let mut permissions = get_user(
-            handler,
-            guild_id.get() as i64,
-            interaction.user.id.get() as i64,
-        )
-        .await;
-
-        if let Ok(member) = guild.member(&ctx.http, interaction.user.id).await {
-            for role in member.roles {
-                if let Some(role) = guild.roles.get(&role) {
-                    if role.permissions.contains(Permissions::ADMINISTRATOR) {
-                        permissions = Permission::iter().collect::<Vec<_>>();
-                        break;
-                    }
-                }
-
-                permissions
-                    .append(&mut get_role(handler, guild_id.get() as i64, role.get() as i64).await);
+        let user_permissions = match interaction.guild_id {
+            Some(guild_id) => {
+                get_member_permissions(handler, &ctx, guild_id, interaction.user.id).await
             }
-        } else {
-            error!("Failed to get member from guild {}", guild_id.get());
-            return Self {
-                ctx,
-                interaction: interaction.clone(),
-                has_responsed: Arc::new(AtomicBool::new(false)),
-                user_permissions: permissions,
-            };
-        }
+            None => vec![],
+        };
 
         Self {
             ctx,
             interaction: interaction.clone(),
             has_responsed: Arc::new(AtomicBool::new(false)),
-            user_permissions: permissions,
+            user_permissions,
         }
     }
 
@@ -138,6 +88,50 @@ impl InteractionContext {
     }
 }
 
+pub async fn get_member_permissions(
+    handler: &Handler,
+    ctx: &IncomingContext,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Vec<Permission> {
+    let mut temp_guild = guild_id
+        .to_guild_cached(&ctx.cache)
+        .map(|guild| PartialGuild::from(guild.clone()));
+    if temp_guild.is_none() {
+        temp_guild = if let Ok(guild) = guild_id.to_partial_guild(&ctx.http).await {
+            Some(guild)
+        } else {
+            None
+        }
+    }
+
+    let guild = temp_guild.unwrap();
+
+    if guild.owner_id == user_id {
+        return Permission::iter().collect::<Vec<_>>();
+    }
+
+    let mut permissions = get_user(handler, guild_id.get() as i64, user_id.get() as i64).await;
+
+    if let Ok(member) = guild.member(&ctx.http, user_id).await {
+        for role in member.roles {
+            if let Some(role) = guild.roles.get(&role) {
+                if role.permissions.contains(Permissions::ADMINISTRATOR) {
+                    permissions = Permission::iter().collect::<Vec<_>>();
+                    break;
+                }
+            }
+
+            permissions
+                .append(&mut get_role(handler, guild_id.get() as i64, role.get() as i64).await);
+        }
+    } else {
+        error!("Failed to get member from guild {}", guild_id.get());
+    }
+
+    permissions
+}
+
 pub struct FailedCommandContext {
     pub ctx: IncomingContext,
 }
@@ -152,4 +146,15 @@ pub trait Command: Send + Sync {
         ctx: &CommandContext,
         command: &CommandInteraction,
     ) -> ResponseResult;
+    fn dm_enabled(&self) -> bool {
+        false
+    }
+    async fn dm_router(
+        &self,
+        _handler: &Handler,
+        _ctx: &FailedCommandContext,
+        _command: &CommandInteraction,
+    ) -> ResponseResult {
+        Ok(())
+    }
 }
@@ -2,14 +2,52 @@
 pub struct ModerationConfig {
     pub mute_role: Option<i64>,
     pub default_strike_duration: Option<String>,
+    pub use_timeouts: bool,
+    pub escalation_channel: Option<i64>,
+    pub strike_window: Option<i32>,
 }
 
+pub struct AntiRaidConfig {
+    pub enabled: bool,
+    pub join_threshold: i32,
+    pub join_window: i32,
+    pub lockdown_duration: i32,
+    pub quarantine_role: Option<i64>,
+    pub kick_account_age: Option<i32>,
+    pub lock_channels: Vec<i64>,
+}
+
+pub struct SpamConfig {
+    pub enabled: bool,
+    pub message_limit: Option<i32>,
+    pub message_window: i32,
+    pub duplicate_limit: Option<i32>,
+    pub mention_limit: Option<i32>,
+    pub newline_limit: Option<i32>,
+    pub emoji_limit: Option<i32>,
+    pub punishment: String,
+    pub mute_duration: Option<String>,
+    pub reason: String,
+}
+
+#[allow(clippy::struct_excessive_bools)]
 pub struct LoggingConfig {
     pub log_actions: bool,
     pub log_messages: bool,
     pub log_voice: bool,
+    pub log_members: bool,
+    pub log_server: bool,
     pub log_channel: Option<i64>,
     pub log_action_channel: Option<i64>,
     pub log_message_channel: Option<i64>,
     pub log_voice_channel: Option<i64>,
+    pub log_member_channel: Option<i64>,
+    pub log_server_channel: Option<i64>,
+}
+
+#[derive(Default)]
+pub struct LoggingIgnores {
+    pub ignored_channels: Vec<i64>,
+    pub ignored_roles: Vec<i64>,
+    pub ignored_users: Vec<i64>,
 }
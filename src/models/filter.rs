@@ -0,0 +1,215 @@
+use std::fmt::{self, Display, Formatter};
+
+use regex::{Regex, RegexBuilder};
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum FilterPatternType {
+    Literal,
+    Glob,
+    Regex,
+}
+
+impl Display for FilterPatternType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterPatternType::Literal => write!(f, "literal"),
+            FilterPatternType::Glob => write!(f, "glob"),
+            FilterPatternType::Regex => write!(f, "regex"),
+        }
+    }
+}
+
+impl From<&str> for FilterPatternType {
+    fn from(value: &str) -> Self {
+        match value {
+            "literal" => FilterPatternType::Literal,
+            "glob" => FilterPatternType::Glob,
+            "regex" => FilterPatternType::Regex,
+            _ => panic!("Invalid filter pattern type"),
+        }
+    }
+}
+
+impl From<String> for FilterPatternType {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum FilterAction {
+    Delete,
+    Strike,
+    Mute,
+    Kick,
+    Ban,
+}
+
+impl Display for FilterAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterAction::Delete => write!(f, "delete"),
+            FilterAction::Strike => write!(f, "strike"),
+            FilterAction::Mute => write!(f, "mute"),
+            FilterAction::Kick => write!(f, "kick"),
+            FilterAction::Ban => write!(f, "ban"),
+        }
+    }
+}
+
+impl From<&str> for FilterAction {
+    fn from(value: &str) -> Self {
+        match value {
+            "delete" => FilterAction::Delete,
+            "strike" => FilterAction::Strike,
+            "mute" => FilterAction::Mute,
+            "kick" => FilterAction::Kick,
+            "ban" => FilterAction::Ban,
+            _ => panic!("Invalid filter action"),
+        }
+    }
+}
+
+impl From<String> for FilterAction {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+pub struct FilterRule {
+    pub id: i32,
+    pub name: String,
+    pub pattern: String,
+    pub pattern_type: FilterPatternType,
+    pub action: FilterAction,
+    pub duration: Option<String>,
+    pub exempt_roles: Vec<i64>,
+    pub exempt_channels: Vec<i64>,
+}
+
+impl FilterRule {
+    pub fn compile(pattern: &str, pattern_type: FilterPatternType) -> Result<Regex, regex::Error> {
+        let pattern = match pattern_type {
+            FilterPatternType::Literal => regex::escape(pattern),
+            FilterPatternType::Glob => regex::escape(pattern)
+                .replace(r"\*", r"\S*")
+                .replace(r"\?", r"\S"),
+            FilterPatternType::Regex => pattern.to_string(),
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(pattern_type != FilterPatternType::Regex)
+            .size_limit(1 << 20)
+            .build()
+    }
+
+    pub fn regex(&self) -> Result<Regex, regex::Error> {
+        Self::compile(&self.pattern, self.pattern_type)
+    }
+}
+
+pub struct CompiledFilterRule {
+    pub rule: FilterRule,
+    pub regex: Regex,
+}
+
+impl CompiledFilterRule {
+    pub fn is_exempt(&self, channel_id: i64, roles: &[i64]) -> bool {
+        self.rule.exempt_channels.contains(&channel_id)
+            || roles
+                .iter()
+                .any(|role| self.rule.exempt_roles.contains(role))
+    }
+}
+
+/// Returns the first rule, in creation order, that matches the content and does not exempt the
+/// channel or any of the roles
+pub fn find_matching_rule<'a>(
+    rules: &'a [CompiledFilterRule],
+    content: &str,
+    channel_id: i64,
+    member_roles: &[i64],
+) -> Option<&'a CompiledFilterRule> {
+    rules
+        .iter()
+        .find(|rule| !rule.is_exempt(channel_id, member_roles) && rule.regex.is_match(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        find_matching_rule, CompiledFilterRule, FilterAction, FilterPatternType, FilterRule,
+    };
+
+    fn rule(id: i32, pattern: &str, pattern_type: FilterPatternType) -> CompiledFilterRule {
+        let rule = FilterRule {
+            id,
+            name: format!("rule-{id}"),
+            pattern: pattern.to_string(),
+            pattern_type,
+            action: FilterAction::Delete,
+            duration: None,
+            exempt_roles: vec![],
+            exempt_channels: vec![],
+        };
+        let regex = rule.regex().unwrap();
+        CompiledFilterRule { rule, regex }
+    }
+
+    #[test]
+    fn literal_patterns_are_escaped_and_case_insensitive() {
+        let regex = FilterRule::compile("a.b", FilterPatternType::Literal).unwrap();
+        assert!(regex.is_match("xx A.B yy"));
+        assert!(!regex.is_match("axb"));
+    }
+
+    #[test]
+    fn glob_wildcards_stay_within_a_word() {
+        let regex = FilterRule::compile("free*nitro", FilterPatternType::Glob).unwrap();
+        assert!(regex.is_match("get FREE-nitro now"));
+        assert!(!regex.is_match("free stuff and nitro"));
+
+        let regex = FilterRule::compile("b?d", FilterPatternType::Glob).unwrap();
+        assert!(regex.is_match("bad"));
+        assert!(!regex.is_match("b d"));
+    }
+
+    #[test]
+    fn regex_patterns_are_case_sensitive() {
+        let regex = FilterRule::compile(r"\bspam\d+", FilterPatternType::Regex).unwrap();
+        assert!(regex.is_match("spam123"));
+        assert!(!regex.is_match("SPAM123"));
+        assert!(FilterRule::compile("(unclosed", FilterPatternType::Regex).is_err());
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let rules = vec![
+            rule(1, "hello", FilterPatternType::Literal),
+            rule(2, "hel*", FilterPatternType::Glob),
+        ];
+        assert_eq!(
+            find_matching_rule(&rules, "hello there", 1, &[]).map(|rule| rule.rule.id),
+            Some(1)
+        );
+        assert_eq!(
+            find_matching_rule(&rules, "help", 1, &[]).map(|rule| rule.rule.id),
+            Some(2)
+        );
+        assert!(find_matching_rule(&rules, "goodbye", 1, &[]).is_none());
+    }
+
+    #[test]
+    fn exempt_channels_and_roles_skip_the_rule() {
+        let mut exempt = rule(1, "hello", FilterPatternType::Literal);
+        exempt.rule.exempt_channels = vec![10];
+        exempt.rule.exempt_roles = vec![20];
+        let rules = vec![exempt, rule(2, "hello", FilterPatternType::Literal)];
+
+        let matched = |channel, roles: &[i64]| {
+            find_matching_rule(&rules, "hello", channel, roles).map(|rule| rule.rule.id)
+        };
+        assert_eq!(matched(1, &[]), Some(1));
+        assert_eq!(matched(10, &[]), Some(2));
+        assert_eq!(matched(1, &[5, 20]), Some(2));
+    }
+}
@@ -1,7 +1,11 @@
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 use serenity::all::{GuildId, RoleId};
 
+use crate::common::{
+    filter_cache::FilterCache, log_dispatcher::LogDispatcher, scheduler::Scheduler,
+};
+
 #[derive(Clone)]
 pub struct Handler {
     pub main_database: sqlx::PgPool,
@@ -9,4 +13,7 @@ pub struct Handler {
     pub start_time: Instant,
     pub global_kill_guild: GuildId,
     pub global_kill_role: RoleId,
+    pub scheduler: Arc<Scheduler>,
+    pub log_dispatcher: Arc<LogDispatcher>,
+    pub filter_cache: Arc<FilterCache>,
 }
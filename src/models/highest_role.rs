@@ -1,13 +1,17 @@
-use serenity::all::{Permissions, User};
+use serenity::all::{Permissions, User, UserId};
 
 use super::command::CommandContext;
 
 pub async fn get_highest_role(ctx: &CommandContext, user: &User) -> u16 {
-    if ctx.guild.owner_id == user.id {
+    get_highest_role_by_id(ctx, user.id).await
+}
+
+pub async fn get_highest_role_by_id(ctx: &CommandContext, user_id: UserId) -> u16 {
+    if ctx.guild.owner_id == user_id {
         return u16::max_value();
     }
 
-    let Ok(member) = ctx.guild.member(&ctx.ctx.http, user.id).await else {
+    let Ok(member) = ctx.guild.member(&ctx.ctx.http, user_id).await else {
         return 0;
     };
 
@@ -0,0 +1,576 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+};
+
+use super::actions::{Action, ActionType};
+
+pub const MAX_IMPORT_ROWS: usize = 10_000;
+// Matches the length of the reason column on actions
+pub const MAX_REASON_LENGTH: usize = 255;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum ImportFormat {
+    Reaper,
+    Generic,
+    Zeppelin,
+}
+
+impl Display for ImportFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportFormat::Reaper => write!(f, "reaper"),
+            ImportFormat::Generic => write!(f, "generic"),
+            ImportFormat::Zeppelin => write!(f, "zeppelin"),
+        }
+    }
+}
+
+impl From<&str> for ImportFormat {
+    fn from(value: &str) -> Self {
+        match value {
+            "reaper" => ImportFormat::Reaper,
+            "generic" => ImportFormat::Generic,
+            "zeppelin" => ImportFormat::Zeppelin,
+            _ => panic!("Invalid import format"),
+        }
+    }
+}
+
+impl From<String> for ImportFormat {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+pub struct ImportedAction {
+    pub typ: ActionType,
+    pub custom_action: Option<String>,
+    pub user_id: i64,
+    pub moderator_id: i64,
+    pub reason: String,
+    pub active: bool,
+    pub expiry: Option<time::OffsetDateTime>,
+    pub created_at: time::OffsetDateTime,
+    pub points: Option<i32>,
+}
+
+impl ImportedAction {
+    pub fn into_action(self, guild_id: i64, source: &str) -> Action {
+        let mut action = Action::new(
+            self.typ,
+            self.user_id,
+            self.moderator_id,
+            guild_id,
+            self.reason,
+            None,
+        );
+        action.custom_type = self.custom_action;
+        action.active = self.active;
+        action.expiry = self.expiry;
+        action.created_at = self.created_at;
+        action.points = self.points;
+        action.import_source = Some(source.to_string());
+        action
+    }
+}
+
+impl ImportFormat {
+    fn aliases(self, field: &'static str) -> &'static [&'static str] {
+        if self == ImportFormat::Reaper {
+            return match field {
+                "type" => &["type"],
+                "user_id" => &["user_id"],
+                "moderator_id" => &["moderator_id"],
+                "reason" => &["reason"],
+                "active" => &["active"],
+                "points" => &["points"],
+                "created_at" => &["created_at"],
+                _ => &["expiry"],
+            };
+        }
+        match field {
+            "type" => &["type", "action", "action_type", "punishment", "case_type"],
+            "user_id" => &[
+                "user_id",
+                "user",
+                "target_id",
+                "target",
+                "offender_id",
+                "member_id",
+            ],
+            "moderator_id" => &[
+                "moderator_id",
+                "moderator",
+                "mod_id",
+                "mod",
+                "responsible_moderator",
+                "issuer_id",
+            ],
+            "reason" => &["reason", "description", "note", "notes"],
+            "active" => &["active"],
+            "points" => &["points", "strikes", "weight"],
+            "created_at" => &["created_at", "timestamp", "date", "time", "created"],
+            _ => &["expiry", "expires_at", "expires", "until", "end"],
+        }
+    }
+
+    fn field<'a>(self, row: &'a HashMap<String, String>, field: &'static str) -> Option<&'a str> {
+        self.aliases(field)
+            .iter()
+            .find_map(|alias| row.get(*alias))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn action_type(
+        self,
+        value: &str,
+        custom_actions: &[String],
+    ) -> Result<Option<(ActionType, Option<String>)>, String> {
+        let lowered = value.to_lowercase();
+        match self {
+            // Reaper exports custom punishments by name, they must exist in the importing guild
+            ImportFormat::Reaper => {
+                return match lowered.as_str() {
+                    "strike" | "mute" | "kick" | "ban" => {
+                        Ok(Some((ActionType::from(lowered.as_str()), None)))
+                    }
+                    _ => match custom_actions.iter().find(|name| **name == value) {
+                        Some(name) => Ok(Some((ActionType::Custom, Some(name.clone())))),
+                        None => Err(format!(
+                            "unknown action type `{value}`, custom punishments must be created before importing"
+                        )),
+                    },
+                };
+            }
+            // Zeppelin stores case types as numbers: ban, unban, note, warn, kick, mute, unmute,
+            // deleted and softban
+            ImportFormat::Zeppelin if lowered.parse::<u8>().is_ok() => {
+                return match lowered.as_str() {
+                    "1" => Ok(Some((ActionType::Ban, None))),
+                    "4" => Ok(Some((ActionType::Strike, None))),
+                    "5" | "9" => Ok(Some((ActionType::Kick, None))),
+                    "6" => Ok(Some((ActionType::Mute, None))),
+                    "2" | "3" | "7" | "8" => Ok(None),
+                    _ => Err(format!("unknown action type `{value}`")),
+                };
+            }
+            _ => {}
+        }
+        Ok(match lowered.as_str() {
+            "strike" | "warn" | "warning" => Some((ActionType::Strike, None)),
+            "mute" | "timeout" | "tempmute" => Some((ActionType::Mute, None)),
+            "kick" | "softban" => Some((ActionType::Kick, None)),
+            "ban" | "tempban" | "forceban" | "hackban" => Some((ActionType::Ban, None)),
+            "unban" | "unmute" | "untimeout" | "note" | "pardon" => None,
+            _ => return Err(format!("unknown action type `{value}`")),
+        })
+    }
+
+    /// Parses and validates a single entry, returning `None` for entries that have no Reaper
+    /// equivalent, such as unbans or notes
+    pub fn parse_row(
+        self,
+        row: &HashMap<String, String>,
+        custom_actions: &[String],
+    ) -> Result<Option<ImportedAction>, String> {
+        let Some((typ, custom_action)) = self.action_type(
+            self.field(row, "type").ok_or("missing action type")?,
+            custom_actions,
+        )?
+        else {
+            return Ok(None);
+        };
+        let user_id = parse_snowflake(self.field(row, "user_id").ok_or("missing user ID")?)
+            .ok_or("invalid user ID")?;
+        let moderator_id = parse_snowflake(
+            self.field(row, "moderator_id")
+                .ok_or("missing moderator ID")?,
+        )
+        .ok_or("invalid moderator ID")?;
+        let created_at = parse_timestamp(
+            self.field(row, "created_at")
+                .ok_or("missing creation date")?,
+        )
+        .ok_or("invalid creation date")?;
+        let expiry = match self.field(row, "expiry") {
+            Some(value) => Some(parse_timestamp(value).ok_or("invalid expiry date")?),
+            None => None,
+        };
+        let points = match self.field(row, "points") {
+            Some(value) => Some(value.parse::<i32>().map_err(|_| "invalid points")?),
+            None if typ == ActionType::Strike => Some(1),
+            None => None,
+        };
+        let active = match self.field(row, "active").map(str::to_lowercase).as_deref() {
+            Some("true" | "1" | "yes") | None => true,
+            Some("false" | "0" | "no") => false,
+            Some(_) => return Err("invalid active state".to_string()),
+        };
+        // Reaper never applied imported punishments, so only strikes stay active and nothing is
+        // unbanned or unmuted when the others expire
+        let active = active
+            && typ == ActionType::Strike
+            && expiry.is_none_or(|expiry| expiry > time::OffsetDateTime::now_utc());
+        let reason = self.field(row, "reason").unwrap_or("No reason provided");
+        if reason.chars().count() > MAX_REASON_LENGTH {
+            return Err(format!(
+                "reason is longer than {MAX_REASON_LENGTH} characters"
+            ));
+        }
+
+        Ok(Some(ImportedAction {
+            typ,
+            custom_action,
+            user_id,
+            moderator_id,
+            reason: reason.to_string(),
+            active,
+            expiry,
+            created_at,
+            points,
+        }))
+    }
+}
+
+fn parse_snowflake(value: &str) -> Option<i64> {
+    let id = value
+        .trim_start_matches("<@")
+        .trim_start_matches('!')
+        .trim_end_matches('>')
+        .parse::<u64>()
+        .ok()?;
+    (id > 0).then_some(id as i64)
+}
+
+pub fn parse_timestamp(value: &str) -> Option<time::OffsetDateTime> {
+    if let Ok(unix) = value.parse::<i64>() {
+        // Values this large can only be millisecond timestamps
+        let seconds = if unix > 100_000_000_000 {
+            unix / 1000
+        } else {
+            unix
+        };
+        return time::OffsetDateTime::from_unix_timestamp(seconds).ok();
+    }
+
+    let (date, rest) = value.split_at(value.find(['T', ' ']).unwrap_or(value.len()));
+    let mut date_parts = date.splitn(3, '-');
+    let year = date_parts.next()?.parse().ok()?;
+    let month = time::Month::try_from(date_parts.next()?.parse::<u8>().ok()?).ok()?;
+    let day = date_parts.next()?.parse().ok()?;
+    let date = time::Date::from_calendar_date(year, month, day).ok()?;
+
+    let rest = rest.get(1..).unwrap_or_default();
+    let (clock, offset) = match rest.find(['Z', '+']).or_else(|| rest.rfind('-')) {
+        Some(index) => rest.split_at(index),
+        None => (rest, ""),
+    };
+    let mut clock_parts = clock.split(':').filter(|part| !part.is_empty());
+    let hour = clock_parts.next().map_or(Ok(0), str::parse).ok()?;
+    let minute = clock_parts.next().map_or(Ok(0), str::parse).ok()?;
+    let second = clock_parts
+        .next()
+        .and_then(|second| second.split('.').next())
+        .map_or(Ok(0), str::parse)
+        .ok()?;
+    let time = time::Time::from_hms(hour, minute, second).ok()?;
+
+    let offset = match offset {
+        "" | "Z" | "+00:00" => time::UtcOffset::UTC,
+        _ => {
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let mut offset_parts = offset[1..].split(':');
+            let hours: i8 = offset_parts.next()?.parse().ok()?;
+            let minutes: i8 = offset_parts.next().map_or(Ok(0), str::parse).ok()?;
+            time::UtcOffset::from_hms(sign * hours, sign * minutes, 0).ok()?
+        }
+    };
+    Some(
+        date.with_time(time)
+            .assume_offset(offset)
+            .to_offset(time::UtcOffset::UTC),
+    )
+}
+
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+    while let Some(char) = chars.next() {
+        match (char, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            ('\r', false) => {}
+            _ => field.push(char),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+pub fn parse_file(content: &str, json: bool) -> Result<Vec<HashMap<String, String>>, String> {
+    if !json {
+        let mut rows = parse_csv(content.trim_start_matches('\u{feff}')).into_iter();
+        let header = rows
+            .next()
+            .ok_or("The file is empty")?
+            .into_iter()
+            .map(|column| column.trim().to_lowercase())
+            .collect::<Vec<_>>();
+        return Ok(rows
+            .map(|row| header.iter().cloned().zip(row).collect())
+            .collect());
+    }
+
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|err| format!("The file is not valid JSON: {err}"))?;
+    let rows = match value {
+        serde_json::Value::Array(rows) => rows,
+        serde_json::Value::Object(mut object) => match ["actions", "cases", "data"]
+            .iter()
+            .find_map(|key| object.remove(*key))
+        {
+            Some(serde_json::Value::Array(rows)) => rows,
+            _ => return Err("The file does not contain a list of actions".to_string()),
+        },
+        _ => return Err("The file does not contain a list of actions".to_string()),
+    };
+    Ok(rows
+        .into_iter()
+        .map(|row| match row {
+            serde_json::Value::Object(object) => object
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    let value = match value {
+                        serde_json::Value::String(string) => string,
+                        serde_json::Value::Null => return None,
+                        value => value.to_string(),
+                    };
+                    Some((key.to_lowercase(), value))
+                })
+                .collect(),
+            _ => HashMap::new(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{parse_csv, parse_file, parse_timestamp, ImportFormat, MAX_REASON_LENGTH};
+    use crate::models::actions::ActionType;
+
+    fn row(fields: &[(&str, &str)]) -> HashMap<String, String> {
+        fields
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+            .collect()
+    }
+
+    fn reaper_row(typ: &str) -> HashMap<String, String> {
+        row(&[
+            ("type", typ),
+            ("user_id", "123"),
+            ("moderator_id", "456"),
+            ("reason", "Spamming"),
+            ("created_at", "2024-01-31T18:00:00Z"),
+        ])
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        let expected = 1_706_724_000;
+        for value in [
+            "2024-01-31T18:00:00Z",
+            "2024-01-31T18:00:00.123Z",
+            "2024-01-31 18:00",
+            "2024-01-31T19:00:00+01:00",
+            "2024-01-31T13:00:00-05:00",
+            "1706724000",
+            "1706724000000",
+        ] {
+            assert_eq!(
+                parse_timestamp(value).map(time::OffsetDateTime::unix_timestamp),
+                Some(expected),
+                "{value}"
+            );
+        }
+        assert_eq!(
+            parse_timestamp("2024-01-31").map(time::OffsetDateTime::unix_timestamp),
+            Some(1_706_659_200)
+        );
+        assert!(parse_timestamp("yesterday").is_none());
+        assert!(parse_timestamp("2024-13-01").is_none());
+    }
+
+    #[test]
+    fn parses_quoted_csv() {
+        let rows = parse_csv("a,b\n\"x, y\",\"say \"\"hi\"\"\"\r\n\n1,2");
+        assert_eq!(
+            rows,
+            vec![
+                vec!["a".to_string(), "b".to_string()],
+                vec!["x, y".to_string(), "say \"hi\"".to_string()],
+                vec!["1".to_string(), "2".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_csv_and_json_files() {
+        let csv = parse_file("\u{feff}Type,User_ID\nban,1\n", false).unwrap();
+        assert_eq!(csv, vec![row(&[("type", "ban"), ("user_id", "1")])]);
+
+        let json = parse_file(
+            r#"{"cases": [{"Type": "ban", "user_id": 1, "expiry": null}]}"#,
+            true,
+        )
+        .unwrap();
+        assert_eq!(json, vec![row(&[("type", "ban"), ("user_id", "1")])]);
+
+        assert!(parse_file(r#"{"other": []}"#, true).is_err());
+        assert!(parse_file("not json", true).is_err());
+    }
+
+    #[test]
+    fn parses_a_reaper_row() {
+        let action = ImportFormat::Reaper
+            .parse_row(&reaper_row("strike"), &[])
+            .unwrap()
+            .unwrap();
+        assert!(action.typ == ActionType::Strike);
+        assert_eq!(
+            (action.user_id, action.moderator_id, action.points),
+            (123, 456, Some(1))
+        );
+        assert_eq!(action.reason, "Spamming");
+        assert!(action.active);
+    }
+
+    #[test]
+    fn reaper_rejects_unknown_types() {
+        assert!(ImportFormat::Reaper
+            .parse_row(&reaper_row("Jail"), &[])
+            .is_err());
+
+        let action = ImportFormat::Reaper
+            .parse_row(&reaper_row("Jail"), &["Jail".to_string()])
+            .unwrap()
+            .unwrap();
+        assert!(action.typ == ActionType::Custom);
+        assert_eq!(action.custom_action.as_deref(), Some("Jail"));
+    }
+
+    #[test]
+    fn rejects_long_reasons() {
+        let mut long = reaper_row("ban");
+        long.insert("reason".to_string(), "a".repeat(MAX_REASON_LENGTH + 1));
+        assert!(ImportFormat::Reaper.parse_row(&long, &[]).is_err());
+
+        long.insert("reason".to_string(), "é".repeat(MAX_REASON_LENGTH));
+        assert!(ImportFormat::Reaper.parse_row(&long, &[]).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        let mut invalid = reaper_row("ban");
+        invalid.insert("user_id".to_string(), "abc".to_string());
+        assert!(ImportFormat::Reaper.parse_row(&invalid, &[]).is_err());
+
+        let mut invalid = reaper_row("ban");
+        invalid.remove("created_at");
+        assert!(ImportFormat::Reaper.parse_row(&invalid, &[]).is_err());
+
+        let mut invalid = reaper_row("ban");
+        invalid.insert("active".to_string(), "maybe".to_string());
+        assert!(ImportFormat::Reaper.parse_row(&invalid, &[]).is_err());
+    }
+
+    #[test]
+    fn only_unexpired_strikes_are_active() {
+        let mut expired = reaper_row("mute");
+        expired.insert("expiry".to_string(), "2024-02-01".to_string());
+        let action = ImportFormat::Reaper
+            .parse_row(&expired, &[])
+            .unwrap()
+            .unwrap();
+        assert!(!action.active);
+
+        for typ in ["kick", "mute", "ban", "Jail"] {
+            let mut entry = reaper_row(typ);
+            entry.insert("active".to_string(), "true".to_string());
+            entry.insert("expiry".to_string(), "2099-01-01".to_string());
+            let action = ImportFormat::Reaper
+                .parse_row(&entry, &["Jail".to_string()])
+                .unwrap()
+                .unwrap();
+            assert!(!action.active);
+        }
+    }
+
+    #[test]
+    fn generic_format_maps_other_bots() {
+        let entry = row(&[
+            ("punishment", "Warning"),
+            ("target_id", "<@!123>"),
+            ("mod_id", "456"),
+            ("timestamp", "1706724000"),
+        ]);
+        let action = ImportFormat::Generic
+            .parse_row(&entry, &[])
+            .unwrap()
+            .unwrap();
+        assert!(action.typ == ActionType::Strike);
+        assert_eq!(action.user_id, 123);
+
+        let mut note = entry.clone();
+        note.insert("punishment".to_string(), "note".to_string());
+        assert!(ImportFormat::Generic
+            .parse_row(&note, &[])
+            .unwrap()
+            .is_none());
+
+        let mut unknown = entry;
+        unknown.insert("punishment".to_string(), "jail".to_string());
+        assert!(ImportFormat::Generic.parse_row(&unknown, &[]).is_err());
+    }
+
+    #[test]
+    fn zeppelin_case_types() {
+        let case = |typ: &str| {
+            ImportFormat::Zeppelin.parse_row(
+                &row(&[
+                    ("type", typ),
+                    ("user_id", "123"),
+                    ("mod_id", "456"),
+                    ("created_at", "2024-01-31 18:00:00"),
+                ]),
+                &[],
+            )
+        };
+        let typ = |value: &str| case(value).unwrap().map(|action| action.typ.to_string());
+        assert_eq!(typ("1").as_deref(), Some("ban"));
+        assert_eq!(typ("4").as_deref(), Some("strike"));
+        assert_eq!(typ("6").as_deref(), Some("mute"));
+        assert_eq!(typ("9").as_deref(), Some("kick"));
+        assert_eq!(typ("3"), None);
+        assert!(case("42").is_err());
+    }
+}
@@ -262,6 +262,63 @@ impl MessageQuery {
     pub fn key(&self) -> String {
         format!("{}:{}:{}", self.guild, self.channel, self.message)
     }
+
+    /// Fetches many messages in a single round trip, messages that aren't cached are left out
+    pub async fn get_messages(
+        queries: &[MessageQuery],
+        redis: &redis::Client,
+    ) -> Result<Vec<Message>, ResponseError> {
+        let start = Instant::now();
+
+        let mut connection = match redis.get_multiplexed_async_connection().await {
+            Ok(connection) => connection,
+            Err(err) => {
+                error!("Failed to get Redis connection: {:?}", err);
+                return Err(ResponseError::Redis(err));
+            }
+        };
+
+        let mut pipe = redis::pipe();
+        for query in queries {
+            pipe.cmd("HMGET")
+                .arg(query.key())
+                .arg("guild_id")
+                .arg("user_id")
+                .arg("channel_id")
+                .arg("message_id")
+                .arg("content")
+                .arg("attachment");
+        }
+        let rows: Vec<Vec<Option<String>>> = match pipe.query_async(&mut connection).await {
+            Ok(rows) => rows,
+            Err(err) => {
+                error!("Failed to get messages from Redis: {:?}", err);
+                return Err(ResponseError::Redis(err));
+            }
+        };
+
+        debug!(
+            "Got {} messages from Redis in {:?}",
+            queries.len(),
+            start.elapsed()
+        );
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let [guild_id, user_id, channel_id, message_id, content, attachment] =
+                    <[Option<String>; 6]>::try_from(row).ok()?;
+                Some(Message {
+                    guild_id: guild_id?.parse().ok()?,
+                    user_id: user_id?.parse().ok()?,
+                    channel_id: channel_id?.parse().ok()?,
+                    id: message_id?.parse().ok()?,
+                    content: content?,
+                    attachment: attachment.filter(|attachment| attachment != "null"),
+                })
+            })
+            .collect())
+    }
 }
 
 impl From<Message> for MessageQuery {
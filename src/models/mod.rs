@@ -1,10 +1,18 @@
+pub mod action_history;
 pub mod actions;
+pub mod appeal;
+pub mod automod;
 pub mod boards;
 pub mod command;
 pub mod config;
+pub mod filter;
 pub mod giveaway;
 pub mod handler;
 pub mod highest_role;
+pub mod import;
 pub mod message;
+pub mod note;
 pub mod permissions;
 pub mod response;
+pub mod scheduled;
+pub mod stats;
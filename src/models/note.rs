@@ -0,0 +1,6 @@
+pub struct Note {
+    pub id: i32,
+    pub author_id: i64,
+    pub content: String,
+    pub created_at: time::PrimitiveDateTime,
+}
@@ -20,6 +20,14 @@ pub enum Permission {
     ModerationRemove,
     ModerationDuration,
     ModerationReason,
+    ModerationPunish,
+    ModerationAppeals,
+    ModerationNotes,
+    ModerationLockdown,
+    ModerationFilter,
+    ModerationExport,
+    ModerationImport,
+    ModerationStats,
     GiveawayCreate,
     GiveawayEnd,
     GiveawayReroll,
@@ -49,6 +57,14 @@ impl Display for Permission {
             Permission::ModerationRemove => write!(f, "moderation.remove"),
             Permission::ModerationDuration => write!(f, "moderation.duration"),
             Permission::ModerationReason => write!(f, "moderation.reason"),
+            Permission::ModerationPunish => write!(f, "moderation.punish"),
+            Permission::ModerationAppeals => write!(f, "moderation.appeals"),
+            Permission::ModerationNotes => write!(f, "moderation.notes"),
+            Permission::ModerationLockdown => write!(f, "moderation.lockdown"),
+            Permission::ModerationFilter => write!(f, "moderation.filter"),
+            Permission::ModerationExport => write!(f, "moderation.export"),
+            Permission::ModerationImport => write!(f, "moderation.import"),
+            Permission::ModerationStats => write!(f, "moderation.stats"),
             Permission::GiveawayCreate => write!(f, "giveaway.create"),
             Permission::GiveawayEnd => write!(f, "giveaway.end"),
             Permission::GiveawayReroll => write!(f, "giveaway.reroll"),
@@ -78,6 +94,14 @@ impl From<&str> for Permission {
             "moderation.remove" => Permission::ModerationRemove,
             "moderation.duration" => Permission::ModerationDuration,
             "moderation.reason" => Permission::ModerationReason,
+            "moderation.punish" => Permission::ModerationPunish,
+            "moderation.appeals" => Permission::ModerationAppeals,
+            "moderation.notes" => Permission::ModerationNotes,
+            "moderation.lockdown" => Permission::ModerationLockdown,
+            "moderation.filter" => Permission::ModerationFilter,
+            "moderation.export" => Permission::ModerationExport,
+            "moderation.import" => Permission::ModerationImport,
+            "moderation.stats" => Permission::ModerationStats,
             "giveaway.create" => Permission::GiveawayCreate,
             "giveaway.end" => Permission::GiveawayEnd,
             "giveaway.reroll" => Permission::GiveawayReroll,
@@ -1,10 +1,11 @@
-use serenity::builder::{CreateActionRow, CreateAllowedMentions, CreateEmbed};
+use serenity::builder::{CreateActionRow, CreateAllowedMentions, CreateAttachment, CreateEmbed};
 
 pub struct Response {
     pub content: Option<String>,
     pub embeds: Option<Vec<CreateEmbed>>,
     pub allowed_mentions: Option<CreateAllowedMentions>,
     pub components: Option<Vec<CreateActionRow>>,
+    pub attachments: Option<Vec<CreateAttachment>>,
     pub ephemeral: bool,
 }
 
@@ -30,6 +31,7 @@ impl Response {
             embeds: None,
             allowed_mentions: None,
             components: None,
+            attachments: None,
             ephemeral: false,
         }
     }
@@ -44,11 +46,23 @@ impl Response {
         self
     }
 
+    pub fn embeds(mut self, embeds: Vec<CreateEmbed>) -> Self {
+        self.embeds = Some(embeds);
+        self
+    }
+
     pub fn components(mut self, components: Vec<CreateActionRow>) -> Self {
         self.components = Some(components);
         self
     }
 
+    pub fn attachment(mut self, attachment: CreateAttachment) -> Self {
+        self.attachments
+            .get_or_insert_with(Vec::new)
+            .push(attachment);
+        self
+    }
+
     pub const fn ephemeral(mut self, ephemeral: bool) -> Self {
         self.ephemeral = ephemeral;
         self
@@ -0,0 +1,71 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::permissions::Permission;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum ScheduledActionType {
+    Strike,
+    Mute,
+    Kick,
+    Ban,
+    Unmute,
+    Unban,
+}
+
+impl Display for ScheduledActionType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduledActionType::Strike => write!(f, "strike"),
+            ScheduledActionType::Mute => write!(f, "mute"),
+            ScheduledActionType::Kick => write!(f, "kick"),
+            ScheduledActionType::Ban => write!(f, "ban"),
+            ScheduledActionType::Unmute => write!(f, "unmute"),
+            ScheduledActionType::Unban => write!(f, "unban"),
+        }
+    }
+}
+
+impl From<&str> for ScheduledActionType {
+    fn from(value: &str) -> Self {
+        match value {
+            "strike" => ScheduledActionType::Strike,
+            "mute" => ScheduledActionType::Mute,
+            "kick" => ScheduledActionType::Kick,
+            "ban" => ScheduledActionType::Ban,
+            "unmute" => ScheduledActionType::Unmute,
+            "unban" => ScheduledActionType::Unban,
+            _ => panic!("Invalid scheduled action type"),
+        }
+    }
+}
+
+impl From<String> for ScheduledActionType {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+impl ScheduledActionType {
+    pub fn permission(self) -> Permission {
+        match self {
+            ScheduledActionType::Strike => Permission::ModerationStrike,
+            ScheduledActionType::Mute => Permission::ModerationMute,
+            ScheduledActionType::Kick => Permission::ModerationKick,
+            ScheduledActionType::Ban => Permission::ModerationBan,
+            ScheduledActionType::Unmute => Permission::ModerationUnmute,
+            ScheduledActionType::Unban => Permission::ModerationUnban,
+        }
+    }
+}
+
+pub struct ScheduledAction {
+    pub id: i32,
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub moderator_id: i64,
+    pub action_type: String,
+    pub reason: String,
+    pub duration: Option<String>,
+    pub execute_at: time::PrimitiveDateTime,
+    pub created_at: time::PrimitiveDateTime,
+}
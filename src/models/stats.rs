@@ -0,0 +1,17 @@
+pub struct ModeratorActionCount {
+    pub moderator_id: i64,
+    pub action_type: String,
+    pub total: i64,
+    pub removed: i64,
+    pub expired_early: i64,
+}
+
+pub struct ActivityCount {
+    pub period: time::PrimitiveDateTime,
+    pub total: i64,
+}
+
+pub struct ReasonCount {
+    pub reason: String,
+    pub total: i64,
+}